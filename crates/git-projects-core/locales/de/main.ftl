@@ -5,6 +5,7 @@ app-name = Git-Projekte-Scanner
 app-description = Git-Repositories im lokalen Dateisystem scannen und katalogisieren
 
 # Scan-Nachrichten
+config-loaded = Verwende Konfigurationsdatei: { $path }
 scan-started = Suche nach Git-Repositories...
 scan-started-path = Durchsuche: { $path }
 scan-progress = Bisher { $count } { $count ->
@@ -27,9 +28,22 @@ header-has-submodules = Hat Submodule
 header-last-scanned = Zuletzt gescannt
 header-service = Dienst
 header-account = Account
+header-fork = Fork
+header-branch = Branch
+header-state = Zustand
+header-upstream = Upstream
+header-tags = Tags
+header-notes = Notizen
+header-identity = Identität
+header-branches = Branches
+header-submodules = Submodule
+header-change = Änderung
 
 # Remote-Informationen
 remote-none = (keine)
+remote-fetch = (fetch)
+remote-push = (push)
+branches-none = (keine Branches)
 remote-count = { $count } { $count ->
     [one] Remote
     *[other] Remotes
@@ -48,6 +62,155 @@ config-none = (nicht konfiguriert)
 submodule-yes = Ja
 submodule-no = Nein
 
+# Fork-Informationen
+fork-of = Fork von { $upstream }
+fork-none = -
+
+# HEAD-Informationen
+head-detached = (losgelöst)
+head-unborn = (keine Commits)
+head-unknown = (unbekannt)
+
+# Gruppierte Ausgabe
+group-none = (keine)
+
+# Doppelte Klone
+duplicates-none = Keine doppelten Klone gefunden.
+duplicates-group = { $identity } ({ $count } Klone)
+duplicates-heads-diverge = HEADs weichen ab
+duplicates-heads-match = gleicher HEAD
+duplicates-summary = { $count } { $count ->
+    [one] Repository ist
+    *[other] Repositories sind
+} mehrfach geklont.
+
+# Tags und Notizen
+tags-none = (keine Tags)
+
+# Arbeitsverzeichnis-Status
+state-clean = sauber
+state-modified = geändert
+upstream-none = -
+upstream-not-fetched = (nicht abgerufen)
+status-summary = { $count } { $count ->
+    [one] Repository
+    *[other] Repositories
+}, davon { $dirty } mit nicht committeten Änderungen.
+
+# Prüfung
+audit-none = Keine Probleme gefunden.
+audit-missing-identity = Keine Commit-Identität (user.email nicht gesetzt)
+audit-no-remote = Kein Remote
+audit-insecure-remote = Unverschlüsseltes Remote (http:// oder git://)
+audit-detached-head = Losgelöster HEAD
+audit-duplicate = Mehrfach geklont
+audit-summary = { $count } { $count ->
+    [one] Problem
+    *[other] Probleme
+} in { $projects } { $projects ->
+    [one] Repository
+    *[other] Repositories
+} gefunden.
+
+# Terminal-Oberfläche
+tui-search = Suche
+tui-count = { $shown } von { $total }
+tui-details = Details
+tui-details-loading = Repository wird gelesen …
+tui-help = Tippen zum Suchen · ↑↓ auswählen · Tab Sortierspalte · Umschalt+Tab umkehren · Enter Shell öffnen · Strg+Y Pfad kopieren · Strg+R neu analysieren · Esc beenden
+tui-help-pick = Tippen zum Suchen · ↑↓ auswählen · Enter übernehmen · Tab Sortierspalte · Umschalt+Tab umkehren · Strg+Y Pfad kopieren · Strg+R neu analysieren · Esc abbrechen
+tui-copied = { $path } kopiert
+tui-shell = Shell in { $path } wird geöffnet; beenden, um zurückzukehren.
+tui-shell-failed = Shell konnte nicht geöffnet werden: { $error }
+tui-reanalyzed = { $name } neu analysiert
+tui-reanalyze-failed = { $path } konnte nicht neu analysiert werden: { $error }
+
+# Befehle ausführen
+exec-dry-run = `{ $command }` würde in { $count } { $count ->
+    [one] Repository
+    *[other] Repositories
+} ausgeführt:
+exec-exit-code = Exit-Code { $code }
+exec-signal = durch ein Signal beendet
+exec-summary = In { $count } { $count ->
+    [one] Repository
+    *[other] Repositories
+} ausgeführt: { $succeeded } erfolgreich, { $failed } fehlgeschlagen, { $skipped } übersprungen.
+
+# Manifeste wiederherstellen
+restore-cloned = geklont
+restore-would-clone = würde geklont
+restore-updated = { $remotes } hinzugefügt
+restore-would-update = { $remotes } würde hinzugefügt
+restore-unchanged = aktuell
+restore-conflict = Konflikt: { $remote } zeigt auf { $actual } statt auf { $expected }
+restore-not-repository = Konflikt: existiert, ist aber kein Git-Repository
+restore-failed = fehlgeschlagen: { $error }
+restore-summary = { $cloned } geklont, { $updated } aktualisiert, { $unchanged } aktuell, { $conflicts } { $conflicts ->
+    [one] Konflikt
+    *[other] Konflikte
+}, { $failed } fehlgeschlagen.
+
+# Manifeste prüfen
+check-clean = Das Manifest stimmt mit den gescannten Repositories überein.
+check-missing = fehlt: nicht geklont
+check-extra = zusätzlich: nicht im Manifest
+check-mismatched = Remotes weichen ab: { $actual } statt { $expected }
+check-summary = { $missing } fehlend, { $extra } zusätzlich, { $mismatched } mit abweichenden Remotes.
+
+# Scans vergleichen
+diff-none = Keine Änderungen.
+diff-none-value = keine
+diff-added = hinzugekommen
+diff-removed = entfernt
+diff-moved = verschoben von { $from }
+diff-remote-added = Remote { $name } hinzugefügt: { $url }
+diff-remote-removed = Remote { $name } entfernt: { $url }
+diff-remote-changed = Remote { $name } geändert: { $old } → { $new }
+diff-identity-changed = Identität geändert: { $old } → { $new }
+diff-branch-changed = Branch gewechselt: { $old } → { $new }
+diff-head-moved = { $branch } bewegt: { $old } → { $new }
+diff-summary = { $count } { $count ->
+    [one] Änderung
+    *[other] Änderungen
+} in { $projects } { $projects ->
+    [one] Repository
+    *[other] Repositories
+}.
+
+# Inventardatenbank
+db-synced = Scan { $scan } gespeichert: { $count } { $count ->
+    [one] Repository
+    *[other] Repositories
+}, { $added } neu, { $removed } verschwunden.
+db-rows = { $count } { $count ->
+    [one] Zeile
+    *[other] Zeilen
+}.
+
+# Dienst
+serve-scanned = { $count } { $count ->
+    [one] Repository
+    *[other] Repositories
+} bereit.
+serve-socket = Lausche auf { $path }
+serve-http = Lausche auf http://{ $address }
+
+# Inventarberichte
+report-title = Git-Projekte-Inventar
+report-generated = Erstellt am { $date }
+report-summary = Zusammenfassung
+report-repositories = Repositories
+report-metric = Kennzahl
+report-value = Wert
+report-stat-total = Repositories
+report-stat-with-remotes = Mit Remotes
+report-stat-local-only = Nur lokal
+report-stat-forks = Forks
+report-stat-submodules = Submodule
+report-stat-duplicates = Mehrfach geklont
+report-stat-services = Dienste
+
 # Sortierprofile
 sort-name = Nach Name (alphabetisch)
 sort-path = Nach Pfad (alphabetisch)
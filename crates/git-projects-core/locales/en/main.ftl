@@ -5,6 +5,7 @@ app-name = Git Projects Scanner
 app-description = Scan and catalog Git repositories on your local filesystem
 
 # Scanning Messages
+config-loaded = Using configuration file: { $path }
 scan-started = Scanning for Git repositories...
 scan-started-path = Scanning: { $path }
 scan-progress = Found { $count } { $count ->
@@ -27,9 +28,22 @@ header-has-submodules = Has Submodules
 header-last-scanned = Last Scanned
 header-service = Service
 header-account = Account
+header-fork = Fork
+header-branch = Branch
+header-state = State
+header-upstream = Upstream
+header-tags = Tags
+header-notes = Notes
+header-identity = Identity
+header-branches = Branches
+header-submodules = Submodules
+header-change = Change
 
 # Remote Information
 remote-none = (none)
+remote-fetch = (fetch)
+remote-push = (push)
+branches-none = (no branches)
 remote-count = { $count } { $count ->
     [one] remote
     *[other] remotes
@@ -48,6 +62,155 @@ config-none = (not configured)
 submodule-yes = Yes
 submodule-no = No
 
+# Fork Information
+fork-of = fork of { $upstream }
+fork-none = -
+
+# HEAD Information
+head-detached = (detached)
+head-unborn = (no commits)
+head-unknown = (unknown)
+
+# Grouped Output
+group-none = (none)
+
+# Duplicate Clones
+duplicates-none = No duplicate clones found.
+duplicates-group = { $identity } ({ $count } clones)
+duplicates-heads-diverge = HEADs diverge
+duplicates-heads-match = same HEAD
+duplicates-summary = Found { $count } { $count ->
+    [one] repository
+    *[other] repositories
+} cloned more than once.
+
+# Tags and Notes
+tags-none = (no tags)
+
+# Working Tree Status
+state-clean = clean
+state-modified = modified
+upstream-none = -
+upstream-not-fetched = (not fetched)
+status-summary = { $count } { $count ->
+    [one] repository
+    *[other] repositories
+}, { $dirty } with uncommitted changes.
+
+# Audit
+audit-none = No problems found.
+audit-missing-identity = No commit identity (user.email not set)
+audit-no-remote = No remote
+audit-insecure-remote = Unencrypted remote (http:// or git://)
+audit-detached-head = Detached HEAD
+audit-duplicate = Cloned more than once
+audit-summary = Found { $count } { $count ->
+    [one] problem
+    *[other] problems
+} in { $projects } { $projects ->
+    [one] repository
+    *[other] repositories
+}.
+
+# Terminal UI
+tui-search = Search
+tui-count = { $shown } of { $total }
+tui-details = Details
+tui-details-loading = Reading the repository…
+tui-help = Type to search · ↑↓ select · Tab sort column · Shift+Tab reverse · Enter open shell · Ctrl+Y copy path · Ctrl+R re-analyze · Esc quit
+tui-help-pick = Type to search · ↑↓ select · Enter pick · Tab sort column · Shift+Tab reverse · Ctrl+Y copy path · Ctrl+R re-analyze · Esc cancel
+tui-copied = Copied { $path }
+tui-shell = Opening a shell in { $path }; exit it to return.
+tui-shell-failed = Could not open a shell: { $error }
+tui-reanalyzed = Re-analyzed { $name }
+tui-reanalyze-failed = Could not re-analyze { $path }: { $error }
+
+# Running Commands
+exec-dry-run = Would run `{ $command }` in { $count } { $count ->
+    [one] repository
+    *[other] repositories
+}:
+exec-exit-code = exit code { $code }
+exec-signal = killed by a signal
+exec-summary = Ran in { $count } { $count ->
+    [one] repository
+    *[other] repositories
+}: { $succeeded } succeeded, { $failed } failed, { $skipped } skipped.
+
+# Restoring Manifests
+restore-cloned = cloned
+restore-would-clone = would clone
+restore-updated = added { $remotes }
+restore-would-update = would add { $remotes }
+restore-unchanged = up to date
+restore-conflict = conflict: { $remote } points to { $actual }, not { $expected }
+restore-not-repository = conflict: exists but is not a Git repository
+restore-failed = failed: { $error }
+restore-summary = { $cloned } cloned, { $updated } updated, { $unchanged } up to date, { $conflicts } { $conflicts ->
+    [one] conflict
+    *[other] conflicts
+}, { $failed } failed.
+
+# Checking Manifests
+check-clean = The manifest matches the scanned repositories.
+check-missing = missing: not cloned
+check-extra = extra: not in the manifest
+check-mismatched = remotes differ: { $actual }, expected { $expected }
+check-summary = { $missing } missing, { $extra } extra, { $mismatched } with different remotes.
+
+# Comparing Scans
+diff-none = No changes.
+diff-none-value = none
+diff-added = added
+diff-removed = removed
+diff-moved = moved from { $from }
+diff-remote-added = remote { $name } added: { $url }
+diff-remote-removed = remote { $name } removed: { $url }
+diff-remote-changed = remote { $name } changed: { $old } → { $new }
+diff-identity-changed = identity changed: { $old } → { $new }
+diff-branch-changed = branch changed: { $old } → { $new }
+diff-head-moved = { $branch } moved: { $old } → { $new }
+diff-summary = { $count } { $count ->
+    [one] change
+    *[other] changes
+} in { $projects } { $projects ->
+    [one] repository
+    *[other] repositories
+}.
+
+# Inventory Database
+db-synced = Recorded scan { $scan }: { $count } { $count ->
+    [one] repository
+    *[other] repositories
+}, { $added } new, { $removed } gone.
+db-rows = { $count } { $count ->
+    [one] row
+    *[other] rows
+}.
+
+# Daemon
+serve-scanned = Serving { $count } { $count ->
+    [one] repository
+    *[other] repositories
+}.
+serve-socket = Listening on { $path }
+serve-http = Listening on http://{ $address }
+
+# Inventory Reports
+report-title = Git Projects Inventory
+report-generated = Generated on { $date }
+report-summary = Summary
+report-repositories = Repositories
+report-metric = Metric
+report-value = Value
+report-stat-total = Repositories
+report-stat-with-remotes = With remotes
+report-stat-local-only = Local only
+report-stat-forks = Forks
+report-stat-submodules = Submodules
+report-stat-duplicates = Cloned more than once
+report-stat-services = Services
+
 # Sorting Profiles
 sort-name = By Name (alphabetical)
 sort-path = By Path (alphabetical)
@@ -0,0 +1,227 @@
+//! Consistency checks over scan results.
+//!
+//! [`audit`] reports common setup problems, such as repositories without a
+//! commit identity, remotes using unencrypted protocols, or projects cloned
+//! more than once. Each problem is an [`AuditFinding`] naming the
+//! [`AuditCheck`] that produced it.
+
+use crate::duplicates::find_duplicates;
+use crate::models::GitProject;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// The checks run by [`audit`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditCheck {
+    /// No `user.email` is configured, so commits get a default identity.
+    MissingIdentity,
+    /// The repository has no remotes and is not a submodule.
+    NoRemote,
+    /// A remote uses `http://` or `git://`, which are not encrypted.
+    InsecureRemote,
+    /// `HEAD` is detached from any branch.
+    DetachedHead,
+    /// The same remote is cloned more than once.
+    Duplicate,
+}
+
+impl AuditCheck {
+    /// All checks, in report order.
+    pub const ALL: &'static [AuditCheck] = &[
+        AuditCheck::MissingIdentity,
+        AuditCheck::NoRemote,
+        AuditCheck::InsecureRemote,
+        AuditCheck::DetachedHead,
+        AuditCheck::Duplicate,
+    ];
+
+    /// Returns the kebab-case name of the check, as used in JSON.
+    pub fn name(self) -> &'static str {
+        match self {
+            AuditCheck::MissingIdentity => "missing-identity",
+            AuditCheck::NoRemote => "no-remote",
+            AuditCheck::InsecureRemote => "insecure-remote",
+            AuditCheck::DetachedHead => "detached-head",
+            AuditCheck::Duplicate => "duplicate",
+        }
+    }
+}
+
+impl fmt::Display for AuditCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A problem found in one project.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditFinding {
+    /// The check that found the problem.
+    pub check: AuditCheck,
+
+    /// The name of the project.
+    pub name: String,
+
+    /// The path of the project.
+    pub path: PathBuf,
+
+    /// What exactly is wrong, e.g. the offending remote URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Runs all [checks](AuditCheck) over a set of projects.
+///
+/// Findings are ordered by project (in the order passed in), then by check.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::{audit, DefaultScanner, ProjectScanner, ScanConfig};
+///
+/// let projects = DefaultScanner::new().scan(&ScanConfig::default())?;
+/// for finding in audit(&projects) {
+///     println!("{}: {}", finding.path.display(), finding.check);
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn audit(projects: &[GitProject]) -> Vec<AuditFinding> {
+    // Clone count and identity by path, for the duplicate check
+    let mut duplicates = HashMap::new();
+    for group in find_duplicates(projects) {
+        for project in &group.projects {
+            duplicates.insert(
+                project.path.clone(),
+                format!("{} ({} clones)", group.identity, group.projects.len()),
+            );
+        }
+    }
+
+    let mut findings = Vec::new();
+    for project in projects {
+        let mut finding = |check, detail: Option<String>| {
+            findings.push(AuditFinding {
+                check,
+                name: project.name.clone(),
+                path: project.path.clone(),
+                detail,
+            })
+        };
+
+        let email = project.config.as_ref().and_then(|c| c.user_email.as_ref());
+        if email.is_none() {
+            finding(AuditCheck::MissingIdentity, None);
+        }
+        if project.remotes.is_empty() && !project.is_submodule {
+            finding(AuditCheck::NoRemote, None);
+        }
+        for remote in &project.remotes {
+            let url = remote.url.to_ascii_lowercase();
+            if url.starts_with("http://") || url.starts_with("git://") {
+                finding(
+                    AuditCheck::InsecureRemote,
+                    Some(format!("{} {}", remote.name, remote.url)),
+                );
+            }
+        }
+        if let Some(head) = &project.head {
+            if head.branch.is_none() {
+                let commit = head.commit.as_deref().map(|c| c.chars().take(7).collect());
+                finding(AuditCheck::DetachedHead, commit);
+            }
+        }
+        if let Some(detail) = duplicates.get(&project.path) {
+            finding(AuditCheck::Duplicate, Some(detail.clone()));
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigScope, GitConfig, HeadInfo};
+
+    use std::path::Path;
+
+    fn project(path: &str, urls: &[&str]) -> GitProject {
+        let project = GitProject {
+            config: Some(GitConfig {
+                user_name: Some("Dev".to_string()),
+                user_email: Some("dev@example.com".to_string()),
+                scope: ConfigScope::Global,
+            }),
+            head: Some(HeadInfo {
+                branch: Some("main".to_string()),
+                commit: Some("0123456789abcdef".to_string()),
+                upstream_remote: None,
+            }),
+            ..GitProject::test(path)
+        };
+        urls.iter().copied().fold(project, GitProject::with_remote)
+    }
+
+    fn checks(findings: &[AuditFinding], path: &str) -> Vec<AuditCheck> {
+        findings
+            .iter()
+            .filter(|f| f.path.as_path() == Path::new(path))
+            .map(|f| f.check)
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_project_has_no_findings() {
+        let projects = [project("/src/tool", &["https://github.com/acme/tool.git"])];
+        assert!(audit(&projects).is_empty());
+    }
+
+    #[test]
+    fn test_checks() {
+        let mut local = project("/src/local", &[]);
+        local.config = None;
+        let mut detached = project("/src/detached", &["git://example.com/acme/old.git"]);
+        detached.head.as_mut().unwrap().branch = None;
+        let projects = [
+            local,
+            detached,
+            project("/src/a", &["https://github.com/acme/tool.git"]),
+            project("/tmp/b", &["git@github.com:acme/tool.git"]),
+        ];
+
+        let findings = audit(&projects);
+        assert_eq!(
+            checks(&findings, "/src/local"),
+            [AuditCheck::MissingIdentity, AuditCheck::NoRemote]
+        );
+        assert_eq!(
+            checks(&findings, "/src/detached"),
+            [AuditCheck::InsecureRemote, AuditCheck::DetachedHead]
+        );
+        assert_eq!(checks(&findings, "/tmp/b"), [AuditCheck::Duplicate]);
+
+        let details: Vec<_> = findings
+            .iter()
+            .filter_map(|f| f.detail.as_deref())
+            .collect();
+        assert_eq!(
+            details,
+            [
+                "origin git://example.com/acme/old.git",
+                "0123456",
+                "github.com/acme/tool (2 clones)",
+                "github.com/acme/tool (2 clones)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_names() {
+        for check in AuditCheck::ALL {
+            let json = serde_json::to_string(check).unwrap();
+            assert_eq!(json, format!("\"{}\"", check.name()));
+        }
+    }
+}
@@ -0,0 +1,743 @@
+//! Command-line arguments.
+//!
+//! Options that affect scanning, filtering and output are global, so they
+//! can be given before or after the subcommand (`projects-cli --json status`
+//! or `projects-cli status --json`).
+
+use clap::{Parser, Subcommand, ValueEnum};
+use git_projects_core::{
+    AuditCheck, ManifestFormat, OutputFormat, ReportFormat, SortPreset, SortSpec,
+};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+/// Git Projects Scanner - Catalog your local Git repositories
+#[derive(Parser, Debug)]
+#[command(
+    name = "projects-cli",
+    version,
+    about = "Scan and catalog Git repositories on your local filesystem",
+    long_about = None
+)]
+pub struct Cli {
+    /// Root directories to scan (can be specified multiple times)
+    #[arg(
+        short = 'r',
+        long = "root",
+        value_name = "PATH",
+        help = "Root directory to scan",
+        global = true
+    )]
+    pub roots: Vec<PathBuf>,
+
+    /// Maximum depth to recurse into subdirectories
+    #[arg(
+        short = 'd',
+        long = "depth",
+        value_name = "N",
+        help = "Maximum recursion depth (default: 3)",
+        global = true
+    )]
+    pub max_depth: Option<usize>,
+
+    /// Don't follow symbolic links during scanning
+    #[arg(
+        long = "no-symlinks",
+        help = "Don't follow symbolic links",
+        global = true
+    )]
+    pub no_symlinks: bool,
+
+    /// Don't include submodule repositories in results
+    #[arg(
+        long = "no-submodules",
+        help = "Don't include submodule repositories",
+        global = true
+    )]
+    pub no_submodules: bool,
+
+    /// Sorting profile for results
+    #[arg(
+        short = 's',
+        long = "sort",
+        value_enum,
+        help = "Sort results by: name, path, recent, or service (default: name)",
+        global = true
+    )]
+    pub sort: Option<SortProfile>,
+
+    /// Hierarchical sort keys (overrides --sort)
+    #[arg(
+        long = "sort-by",
+        value_name = "KEYS",
+        help = "Sort by comma-separated keys, e.g. service,account,name (overrides --sort)",
+        global = true
+    )]
+    pub sort_by: Option<String>,
+
+    /// Sort directions matching --sort-by
+    #[arg(
+        long = "order",
+        value_name = "ORDERS",
+        requires = "sort_by",
+        help = "Comma-separated sort directions (asc, desc) for --sort-by",
+        global = true
+    )]
+    pub order: Option<String>,
+
+    /// Filter expression evaluated against each repository
+    #[arg(
+        short = 'f',
+        long = "filter",
+        value_name = "EXPR",
+        help = "Only show repositories matching EXPR, e.g. 'service == \"github\" && !is_fork'",
+        global = true
+    )]
+    pub filter: Option<String>,
+
+    /// Only show repositories that are forks of another remote
+    #[arg(
+        long = "forks",
+        help = "Only show repositories that are forks",
+        global = true
+    )]
+    pub forks: bool,
+
+    /// Only show repositories with all of these tags
+    #[arg(
+        long = "tag",
+        value_name = "TAG",
+        help = "Only show repositories with this tag (can be repeated)",
+        global = true
+    )]
+    pub tags: Vec<String>,
+
+    /// Group results into a tree
+    #[arg(
+        short = 'g',
+        long = "group-by",
+        value_name = "KEYS",
+        help = "Group results by comma-separated keys: service, account, host, branch, dir",
+        global = true
+    )]
+    pub group_by: Option<String>,
+
+    /// Only show group headers with counts
+    #[arg(
+        long = "collapsed",
+        requires = "group_by",
+        help = "Only show group headers with counts",
+        global = true
+    )]
+    pub collapsed: bool,
+
+    /// Output as JSON instead of a table (same as --format json)
+    #[arg(
+        short = 'j',
+        long = "json",
+        conflicts_with = "format",
+        help = "Output as JSON (same as --format json)",
+        global = true
+    )]
+    pub json: bool,
+
+    /// Output format
+    #[arg(
+        long = "format",
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format: table, json, ndjson, csv, or tsv",
+        global = true
+    )]
+    pub format: Option<FormatArg>,
+
+    /// Columns for csv, tsv, ndjson and json output
+    #[arg(
+        long = "columns",
+        value_name = "COLUMNS",
+        help = "Comma-separated columns for csv/tsv/ndjson/json, e.g. name,path,url",
+        global = true
+    )]
+    pub columns: Option<String>,
+
+    /// Template rendered for each repository
+    #[arg(
+        short = 't',
+        long = "template",
+        value_name = "TEMPLATE",
+        conflicts_with_all = ["format", "columns", "json"],
+        help = "Render each repository with a template, e.g. '{{name}}\\t{{remotes.0.url}}' (\\t and \\n are expanded)",
+        global = true
+    )]
+    pub template: Option<String>,
+
+    /// Template file rendered for each repository
+    #[arg(
+        long = "template-file",
+        value_name = "FILE",
+        conflicts_with_all = ["template", "format", "columns", "json"],
+        help = "Render each repository with a template file",
+        global = true
+    )]
+    pub template_file: Option<PathBuf>,
+
+    /// Render the template once with all repositories as `projects`
+    #[arg(
+        long = "template-document",
+        help = "Render the template once, with all repositories in `projects`",
+        global = true
+    )]
+    pub template_document: bool,
+
+    /// Show paths relative to their scan root
+    #[arg(
+        long = "relative",
+        conflicts_with = "tilde",
+        help = "Show paths relative to the scan root",
+        global = true
+    )]
+    pub relative: bool,
+
+    /// Show paths with the home directory shortened to ~
+    #[arg(
+        long = "tilde",
+        help = "Show paths with the home directory shortened to ~",
+        global = true
+    )]
+    pub tilde: bool,
+
+    /// Show detailed scanning progress
+    #[arg(
+        short = 'v',
+        long = "verbose",
+        help = "Show verbose output",
+        global = true
+    )]
+    pub verbose: bool,
+
+    /// Locale for messages (e.g., en, de)
+    #[arg(
+        short = 'l',
+        long = "locale",
+        value_name = "LOCALE",
+        help = "Locale for messages (e.g., en, de)",
+        global = true
+    )]
+    pub locale: Option<String>,
+
+    /// Configuration file to use instead of the default
+    #[arg(
+        long = "config",
+        value_name = "FILE",
+        conflicts_with = "no_config",
+        help = "Read settings from FILE instead of ~/.config/git-projects/config.toml",
+        global = true
+    )]
+    pub config: Option<PathBuf>,
+
+    /// Ignore all configuration files
+    #[arg(
+        long = "no-config",
+        help = "Ignore configuration files (including .git-projects.toml in scan roots)",
+        global = true
+    )]
+    pub no_config: bool,
+
+    /// Named profile from the configuration file
+    #[arg(
+        long = "profile",
+        value_name = "NAME",
+        conflicts_with = "no_config",
+        help = "Use the roots, scan settings, filter, sort and format of a configured profile",
+        global = true
+    )]
+    pub profile: Option<String>,
+
+    /// What to do; scans and lists the repositories if omitted
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Subcommands; all of them accept the global options
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Scan the roots and list the repositories (the default)
+    #[command(visible_alias = "list")]
+    Scan,
+
+    /// Show remotes, identity, branches, submodules and status of one repository
+    Show {
+        /// Repository path, or name of a scanned repository (matched fuzzily)
+        project: String,
+    },
+
+    /// Show uncommitted changes and commits ahead of or behind the upstream
+    Status {
+        /// Only show repositories with uncommitted changes
+        #[arg(long = "dirty")]
+        dirty: bool,
+    },
+
+    /// Check repositories for common setup problems
+    Audit {
+        /// Only run these checks (can be repeated)
+        #[arg(long = "check", value_enum, value_name = "CHECK")]
+        checks: Vec<CheckArg>,
+    },
+
+    /// Run a command in each repository, e.g. `exec -- git fetch`
+    ///
+    /// Output lines are prefixed with the repository name. Exits with code 1
+    /// if the command failed in any repository. With --json, prints a report
+    /// of each exit code and duration on stdout, and the command output on
+    /// stderr.
+    Exec {
+        /// Maximum number of commands running at once (default: one per CPU)
+        #[arg(long = "jobs", value_name = "N")]
+        jobs: Option<NonZeroUsize>,
+
+        /// Start no further commands once one has failed
+        #[arg(long = "fail-fast")]
+        fail_fast: bool,
+
+        /// List the repositories the command would run in, without running it
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Also write the JSON report to a file
+        #[arg(long = "report", value_name = "FILE")]
+        report: Option<PathBuf>,
+
+        /// The command and its arguments (use `sh -c '...'` for shell syntax)
+        #[arg(
+            required = true,
+            trailing_var_arg = true,
+            allow_hyphen_values = true,
+            value_name = "COMMAND"
+        )]
+        command: Vec<String>,
+    },
+
+    /// Write the scan results in a machine-readable format
+    Export {
+        /// Export format
+        #[arg(value_enum)]
+        kind: ExportKind,
+
+        /// Write to a file instead of stdout
+        #[arg(short = 'o', long = "output", value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Compare a manifest with the scanned repositories
+    ///
+    /// Lists repositories of the manifest that are missing, scanned ones that
+    /// are not in it, and ones whose remotes differ. Paths are compared
+    /// relative to the scan root. Exits with code 1 if there are differences.
+    Check {
+        /// Manifest: from `export`, a .mrconfig, vcstool .repos or repo XML file
+        manifest: PathBuf,
+    },
+
+    /// Clone the repositories of a manifest that are missing
+    ///
+    /// Repositories are cloned from their first remote into their path
+    /// relative to the target directory, and the other remotes are added.
+    /// Existing repositories get missing remotes added; remotes with another
+    /// URL are reported as conflicts. Exits with code 1 if there were
+    /// conflicts or failures.
+    Restore {
+        /// Manifest: from `export`, a .mrconfig, vcstool .repos or repo XML file
+        manifest: PathBuf,
+
+        /// Directory to restore into (default: the first scan root)
+        #[arg(long = "into", value_name = "DIR")]
+        into: Option<PathBuf>,
+
+        /// Show what would be cloned and added, without changing anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Also restore entries whose path is absolute or leaves the directory with ..
+        #[arg(long = "allow-outside-root")]
+        allow_outside_root: bool,
+    },
+
+    /// Show the configuration files and the effective settings
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Group repositories that are clones of the same remote
+    Duplicates,
+
+    /// Compare two saved scans
+    ///
+    /// Lists repositories added, removed or moved, remotes added, removed or
+    /// changed, commit identities that changed, other branches checked out
+    /// and branches that moved. Snapshots are the output of `--json` or
+    /// `--format ndjson`. Exits with code 1 if there are changes.
+    Diff {
+        /// The older snapshot
+        old: PathBuf,
+
+        /// The newer snapshot
+        new: PathBuf,
+    },
+
+    /// Print the JSON Schema of the --json output
+    Schema,
+
+    /// Render an inventory report (grouped by service unless --group-by is given)
+    Report {
+        /// Document format
+        #[arg(value_enum, default_value_t = ReportKind::Markdown)]
+        kind: ReportKind,
+
+        /// Report title (default: localized "Git Projects Inventory")
+        #[arg(long = "title", value_name = "TITLE")]
+        title: Option<String>,
+
+        /// Write the report to a file instead of stdout
+        #[arg(short = 'o', long = "output", value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Record scans in an SQLite inventory and query it
+    Db {
+        /// Database file (default: ~/.local/share/git-projects/inventory.db)
+        #[arg(long = "db", value_name = "FILE", global = true)]
+        db: Option<PathBuf>,
+
+        #[command(subcommand)]
+        action: DbAction,
+    },
+
+    /// Serve scan results to other programs over JSON-RPC and HTTP
+    ///
+    /// Keeps the results of a scan in memory and rescans periodically.
+    /// Clients call scan, query, show and subscribe over a Unix socket, one
+    /// JSON-RPC message per line; --http also serves them on localhost.
+    Serve {
+        /// Socket path (default: $XDG_RUNTIME_DIR/git-projects.sock)
+        #[arg(long = "socket", value_name = "PATH")]
+        socket: Option<PathBuf>,
+
+        /// Also serve HTTP on 127.0.0.1 at this port
+        #[arg(long = "http", value_name = "PORT")]
+        http: Option<u16>,
+
+        /// Seconds between rescans (0 disables rescanning)
+        #[arg(long = "interval", value_name = "SECS", default_value_t = 60)]
+        interval: u64,
+    },
+
+    /// Manage repository tags (stored in ~/.config/git-projects/metadata.toml)
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+
+    /// Show, set or clear the note of a repository
+    Note {
+        /// Repository path (e.g. .) or remote identity (e.g. github.com/acme/tool)
+        project: String,
+
+        /// New note (shows the current note if omitted)
+        text: Option<String>,
+
+        /// Remove the note
+        #[arg(long = "clear", conflicts_with = "text")]
+        clear: bool,
+    },
+
+    /// Browse the repositories in a full-screen terminal UI
+    Tui,
+
+    /// Print the path of a repository chosen by name and frecency
+    ///
+    /// With a query, prints the best match. Without one, opens the terminal
+    /// UI to choose. Uses the repositories of the last scan with the same
+    /// settings if there was one.
+    Pick {
+        /// Part of the repository name (matched fuzzily)
+        query: Option<String>,
+
+        /// Print the names of the repositories instead, for shell completion
+        #[arg(long, conflicts_with = "query")]
+        list: bool,
+
+        /// Scan again instead of using the results of the last scan
+        #[arg(long)]
+        rescan: bool,
+    },
+
+    /// Print shell functions for jumping to repositories, e.g. `p tool`
+    ///
+    /// Add `eval "$(projects-cli init bash)"` to ~/.bashrc, the same with
+    /// zsh to ~/.zshrc, or `projects-cli init fish | source` to
+    /// ~/.config/fish/config.fish.
+    Init {
+        /// The shell to print the functions for
+        shell: InitShell,
+
+        /// Name of the function that jumps to a repository
+        #[arg(long, value_name = "NAME", default_value = "p")]
+        cmd: String,
+    },
+
+    /// Print a completion script for the options and subcommands
+    Completions {
+        /// The shell to print the script for
+        shell: clap_complete::Shell,
+    },
+
+    /// Manage the profiles of the configuration file
+    Profiles {
+        #[command(subcommand)]
+        action: ProfilesAction,
+    },
+}
+
+/// Configuration operations
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print the path of the global configuration file
+    Path,
+
+    /// Print the effective settings, after merging files and options
+    Show,
+}
+
+/// Profile operations
+#[derive(Subcommand, Debug)]
+pub enum ProfilesAction {
+    /// List the configured profiles with their description or roots
+    List,
+}
+
+/// Inventory database operations
+#[derive(Subcommand, Debug)]
+pub enum DbAction {
+    /// Scan and record all results, keeping the history of earlier syncs;
+    /// filters such as --filter and --tag do not apply
+    Sync,
+
+    /// Run a read-only SQL query, e.g. `SELECT path FROM current_projects`
+    Query {
+        /// The SQL statement
+        sql: String,
+    },
+
+    /// Print the path of the database
+    Path,
+}
+
+/// Tag operations
+#[derive(Subcommand, Debug)]
+pub enum TagAction {
+    /// Add tags to a repository
+    Add {
+        /// Repository path (e.g. .) or remote identity (e.g. github.com/acme/tool)
+        project: String,
+
+        /// Tags to add
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+
+    /// Remove tags from a repository
+    Remove {
+        /// Repository path (e.g. .) or remote identity (e.g. github.com/acme/tool)
+        project: String,
+
+        /// Tags to remove
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+
+    /// List all tags with their number of repositories, or the tags of one repository
+    List {
+        /// Repository path or remote identity
+        project: Option<String>,
+    },
+}
+
+/// Shells supported by `init`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InitShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Formats for `export`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExportKind {
+    /// Pretty-printed JSON document
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Comma-separated values
+    Csv,
+    /// Tab-separated values
+    Tsv,
+    /// Workspace manifest (paths, remotes, default branches, tags) for `restore`
+    Manifest,
+    /// myrepos configuration (.mrconfig)
+    Mrconfig,
+    /// vcstool repositories file (.repos)
+    Vcstool,
+    /// Google repo manifest (default.xml)
+    Repo,
+    /// VS Code workspace (.code-workspace); updates an existing file
+    VscodeWorkspace,
+    /// JetBrains project with a module per repository, into the --output directory
+    Jetbrains,
+}
+
+impl ExportKind {
+    /// The output format, or `None` for manifests and editor workspaces
+    pub fn format(self) -> Option<OutputFormat> {
+        match self {
+            ExportKind::Json => Some(OutputFormat::Json),
+            ExportKind::Ndjson => Some(OutputFormat::Ndjson),
+            ExportKind::Csv => Some(OutputFormat::Csv),
+            ExportKind::Tsv => Some(OutputFormat::Tsv),
+            _ => None,
+        }
+    }
+
+    /// The manifest format, or `None` for project lists and editor workspaces
+    pub fn manifest_format(self) -> Option<ManifestFormat> {
+        match self {
+            ExportKind::Manifest => Some(ManifestFormat::Json),
+            ExportKind::Mrconfig => Some(ManifestFormat::Mrconfig),
+            ExportKind::Vcstool => Some(ManifestFormat::Vcstool),
+            ExportKind::Repo => Some(ManifestFormat::Repo),
+            _ => None,
+        }
+    }
+}
+
+/// Checks for `audit`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CheckArg {
+    /// No user.email configured
+    MissingIdentity,
+    /// No remotes
+    NoRemote,
+    /// Remotes using http:// or git://
+    InsecureRemote,
+    /// HEAD detached from any branch
+    DetachedHead,
+    /// Cloned more than once
+    Duplicate,
+}
+
+impl From<CheckArg> for AuditCheck {
+    fn from(check: CheckArg) -> Self {
+        match check {
+            CheckArg::MissingIdentity => AuditCheck::MissingIdentity,
+            CheckArg::NoRemote => AuditCheck::NoRemote,
+            CheckArg::InsecureRemote => AuditCheck::InsecureRemote,
+            CheckArg::DetachedHead => AuditCheck::DetachedHead,
+            CheckArg::Duplicate => AuditCheck::Duplicate,
+        }
+    }
+}
+
+/// Document formats for reports
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ReportKind {
+    /// Markdown document
+    Markdown,
+    /// Standalone HTML page
+    Html,
+}
+
+impl From<ReportKind> for ReportFormat {
+    fn from(kind: ReportKind) -> Self {
+        match kind {
+            ReportKind::Markdown => ReportFormat::Markdown,
+            ReportKind::Html => ReportFormat::Html,
+        }
+    }
+}
+
+/// Sorting profiles for organizing results
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SortProfile {
+    /// Sort alphabetically by repository name
+    Name,
+    /// Sort alphabetically by full path
+    Path,
+    /// Sort by last scanned time (newest first)
+    Recent,
+    /// Group by hosting service (GitHub, GitLab, etc.)
+    Service,
+}
+
+/// Output formats for results
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum FormatArg {
+    /// Human-readable table
+    Table,
+    /// Pretty-printed JSON with a schema version (a bare array with --columns)
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Comma-separated values
+    Csv,
+    /// Tab-separated values
+    Tsv,
+}
+
+impl From<FormatArg> for OutputFormat {
+    fn from(format: FormatArg) -> Self {
+        match format {
+            FormatArg::Table => OutputFormat::Table,
+            FormatArg::Json => OutputFormat::Json,
+            FormatArg::Ndjson => OutputFormat::Ndjson,
+            FormatArg::Csv => OutputFormat::Csv,
+            FormatArg::Tsv => OutputFormat::Tsv,
+        }
+    }
+}
+
+impl From<SortProfile> for SortSpec {
+    fn from(profile: SortProfile) -> Self {
+        let preset = match profile {
+            SortProfile::Name => SortPreset::Name,
+            SortProfile::Path => SortPreset::Path,
+            SortProfile::Recent => SortPreset::Recent,
+            SortProfile::Service => SortPreset::Service,
+        };
+        preset.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_definition() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+        for sub in Cli::command().get_subcommands_mut() {
+            sub.clone().debug_assert();
+        }
+    }
+
+    #[test]
+    fn test_template_options_conflict() {
+        use clap::Parser;
+        assert!(Cli::try_parse_from(["projects-cli", "-t", "{{name}}"]).is_ok());
+        assert!(
+            Cli::try_parse_from(["projects-cli", "-t", "{{name}}", "--template-file", "x.j2"])
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,1155 @@
+//! Human-readable output for the terminal.
+//!
+//! Tables, trees and detail views, with all labels taken from the
+//! [`Localizer`]. Machine-readable formats are written by
+//! [`git_projects_core::output`].
+
+use anyhow::Result;
+use git_projects_core::{
+    l10n::Localizer, AuditCheck, AuditFinding, Change, ChangeKind, ConfigScope, DuplicateGroup,
+    ExecReport, ExecResult, ExecStatus, GitProject, ManifestCheck, PathStyle, ProjectDetails,
+    ProjectGroup, QueryResult, RepoStatus, RestoreResult, RestoreStatus, SyncSummary,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Outputs projects as a formatted table to stdout
+pub fn output_table(
+    projects: &[GitProject],
+    path_style: PathStyle,
+    localizer: &Localizer,
+) -> Result<()> {
+    if projects.is_empty() {
+        println!(
+            "{}",
+            clean_fluent_string(&localizer.get("scan-no-results", None))
+        );
+        return Ok(());
+    }
+
+    // Calculate column widths
+    let name_width = projects
+        .iter()
+        .map(|p| p.name.len())
+        .max()
+        .unwrap_or(10)
+        .max(localizer.get("header-name", None).len());
+
+    let path_width = projects
+        .iter()
+        .map(|p| p.display_path(path_style).len())
+        .max()
+        .unwrap_or(20)
+        .max(localizer.get("header-path", None).len())
+        .min(60); // Cap at 60 chars for readability
+
+    let remote_width = 30;
+    let config_width = 35;
+
+    let forks: Vec<String> = projects.iter().map(|p| format_fork(p, localizer)).collect();
+    let fork_width = forks
+        .iter()
+        .map(|f| f.chars().count())
+        .max()
+        .unwrap_or(10)
+        .max(localizer.get("header-fork", None).len())
+        .min(30);
+
+    // Print header
+    println!(
+        "{:<name_width$}  {:<path_width$}  {:<remote_width$}  {:<fork_width$}  {:<config_width$}  {}  {}",
+        localizer.get("header-name", None),
+        localizer.get("header-path", None),
+        localizer.get("header-remotes", None),
+        localizer.get("header-fork", None),
+        localizer.get("header-config", None),
+        localizer.get("header-submodule", None),
+        localizer.get("header-has-submodules", None),
+        name_width = name_width,
+        path_width = path_width,
+        remote_width = remote_width,
+        fork_width = fork_width,
+        config_width = config_width,
+    );
+
+    // Print separator
+    println!(
+        "{}",
+        "=".repeat(name_width + path_width + remote_width + fork_width + config_width + 22)
+    );
+
+    // Print each project
+    for (project, fork) in projects.iter().zip(&forks) {
+        let name = truncate(&project.name, name_width);
+        let path = truncate(&project.display_path(path_style), path_width);
+        let remote = format_remotes(project, localizer);
+        let config = format_config(project, localizer);
+        let is_submodule = if project.is_submodule {
+            localizer.get("submodule-yes", None)
+        } else {
+            localizer.get("submodule-no", None)
+        };
+        let has_submodules = if project.has_submodules {
+            localizer.get("submodule-yes", None)
+        } else {
+            localizer.get("submodule-no", None)
+        };
+
+        println!(
+            "{:<name_width$}  {:<path_width$}  {:<remote_width$}  {:<fork_width$}  {:<config_width$}  {:<3}  {}",
+            name,
+            path,
+            truncate(&remote, remote_width),
+            truncate(fork, fork_width),
+            truncate(&config, config_width),
+            is_submodule,
+            has_submodules,
+            name_width = name_width,
+            path_width = path_width,
+            remote_width = remote_width,
+            fork_width = fork_width,
+            config_width = config_width,
+        );
+    }
+
+    // Print summary
+    println!();
+    let count = projects.len().to_string();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get("scan-complete", Some(&[("count", &count)])))
+    );
+    Ok(())
+}
+
+/// Outputs grouped projects as a tree with per-group counts
+pub fn output_tree(
+    groups: &[ProjectGroup],
+    total: usize,
+    collapsed: bool,
+    path_style: PathStyle,
+    localizer: &Localizer,
+) {
+    if total == 0 {
+        println!(
+            "{}",
+            clean_fluent_string(&localizer.get("scan-no-results", None))
+        );
+        return;
+    }
+
+    for group in groups {
+        println!("{}", format_group_header(group, collapsed, localizer));
+        print_group_children(group, "", collapsed, path_style, localizer);
+    }
+
+    println!();
+    let count = total.to_string();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get("scan-complete", Some(&[("count", &count)])))
+    );
+}
+
+/// Formats a group header, e.g. "▾ github (12)"
+fn format_group_header(group: &ProjectGroup, collapsed: bool, localizer: &Localizer) -> String {
+    let label = match &group.value {
+        Some(value) => value.clone(),
+        None => clean_fluent_string(&localizer.get("group-none", None)),
+    };
+    let marker = if collapsed { '▸' } else { '▾' };
+    format!("{} {} ({})", marker, label, group.count)
+}
+
+/// Prints the subgroups and projects of a group, indented with box-drawing characters
+fn print_group_children(
+    group: &ProjectGroup,
+    prefix: &str,
+    collapsed: bool,
+    path_style: PathStyle,
+    localizer: &Localizer,
+) {
+    let projects: &[GitProject] = if collapsed { &[] } else { &group.projects };
+    let total = group.groups.len() + projects.len();
+    let name_width = projects
+        .iter()
+        .map(|p| p.name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    for (index, subgroup) in group.groups.iter().enumerate() {
+        let last = index + 1 == total;
+        println!(
+            "{}{}{}",
+            prefix,
+            if last { "└─" } else { "├─" },
+            format_group_header(subgroup, collapsed, localizer)
+        );
+        let child_prefix = format!("{}{}", prefix, if last { "  " } else { "│ " });
+        print_group_children(subgroup, &child_prefix, collapsed, path_style, localizer);
+    }
+
+    for (index, project) in projects.iter().enumerate() {
+        let last = group.groups.len() + index + 1 == total;
+        println!(
+            "{}{}{:<name_width$}  {}",
+            prefix,
+            if last { "└── " } else { "├── " },
+            project.name,
+            project.display_path(path_style),
+            name_width = name_width,
+        );
+    }
+}
+
+/// Outputs duplicate clone groups, one block per remote identity
+pub fn output_duplicates(groups: &[DuplicateGroup], path_style: PathStyle, localizer: &Localizer) {
+    if groups.is_empty() {
+        println!(
+            "{}",
+            clean_fluent_string(&localizer.get("duplicates-none", None))
+        );
+        return;
+    }
+
+    for group in groups {
+        let identity = group.identity.to_string();
+        let count = group.projects.len().to_string();
+        let status = if group.heads_diverge() {
+            localizer.get("duplicates-heads-diverge", None)
+        } else {
+            localizer.get("duplicates-heads-match", None)
+        };
+        println!(
+            "{} [{}]",
+            clean_fluent_string(&localizer.get(
+                "duplicates-group",
+                Some(&[("identity", identity.as_str()), ("count", &count)])
+            )),
+            clean_fluent_string(&status)
+        );
+
+        let path_width = group
+            .projects
+            .iter()
+            .map(|p| p.display_path(path_style).chars().count())
+            .max()
+            .unwrap_or(20)
+            .min(60);
+
+        for project in &group.projects {
+            println!(
+                "  {:<path_width$}  {}",
+                truncate(&project.display_path(path_style), path_width),
+                format_head(project, localizer),
+                path_width = path_width,
+            );
+        }
+        println!();
+    }
+
+    let count = groups.len().to_string();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get("duplicates-summary", Some(&[("count", &count)])))
+    );
+}
+
+/// Outputs all details of one repository as labeled lines
+pub fn output_show(details: &ProjectDetails, path_style: PathStyle, localizer: &Localizer) {
+    let rows = show_rows(details, path_style, localizer);
+    let label_width = rows
+        .iter()
+        .map(|(label, _)| label.chars().count() + 1)
+        .max()
+        .unwrap_or(0);
+    for (label, values) in rows {
+        for (index, value) in values.iter().enumerate() {
+            let label = if index == 0 {
+                format!("{}:", label)
+            } else {
+                String::new()
+            };
+            println!(
+                "{:<label_width$}  {}",
+                label,
+                value,
+                label_width = label_width
+            );
+        }
+    }
+}
+
+/// Builds the labeled lines of `show`, one or more values per label
+pub fn show_rows(
+    details: &ProjectDetails,
+    path_style: PathStyle,
+    localizer: &Localizer,
+) -> Vec<(String, Vec<String>)> {
+    let project = &details.project;
+    let label = |key: &str| clean_fluent_string(&localizer.get(key, None));
+    let yes_no = |value: bool| {
+        label(if value {
+            "submodule-yes"
+        } else {
+            "submodule-no"
+        })
+    };
+    let scope = |scope: ConfigScope| match scope {
+        ConfigScope::Local => label("config-local"),
+        ConfigScope::Global => label("config-global"),
+        ConfigScope::System => label("config-system"),
+    };
+
+    let mut remotes = Vec::new();
+    for remote in &details.remotes {
+        match (&remote.fetch_url, &remote.push_url) {
+            (Some(fetch), Some(push)) if fetch != push => {
+                remotes.push(format!(
+                    "{}  {} {}",
+                    remote.name,
+                    fetch,
+                    label("remote-fetch")
+                ));
+                remotes.push(format!(
+                    "{}  {} {}",
+                    remote.name,
+                    push,
+                    label("remote-push")
+                ));
+            }
+            (Some(url), _) | (None, Some(url)) => {
+                remotes.push(format!("{}  {}", remote.name, url));
+            }
+            (None, None) => remotes.push(remote.name.clone()),
+        }
+    }
+    if remotes.is_empty() {
+        remotes.push(label("remote-none"));
+    }
+
+    let mut identity = Vec::new();
+    for (key, entry) in [
+        ("user.name", &details.identity.name),
+        ("user.email", &details.identity.email),
+    ] {
+        if let Some(entry) = entry {
+            let mut line = format!("{}  {} [{}]", key, entry.value, scope(entry.scope));
+            if let Some(file) = &entry.file {
+                line.push_str(&format!("  {}", file.display()));
+            }
+            identity.push(line);
+        }
+    }
+    if identity.is_empty() {
+        identity.push(label("config-none"));
+    }
+
+    let mut branches: Vec<String> = details
+        .branches
+        .iter()
+        .map(|branch| {
+            let marker = if branch.is_head { "*" } else { " " };
+            let commit: String = branch
+                .commit
+                .as_deref()
+                .map(|c| c.chars().take(7).collect())
+                .unwrap_or_default();
+            let mut line = format!("{} {}  {}", marker, branch.name, commit);
+            if let Some(upstream) = &branch.upstream {
+                line.push_str(&format!("  {}", upstream));
+            }
+            line
+        })
+        .collect();
+    if branches.is_empty() {
+        branches.push(label("branches-none"));
+    }
+
+    let mut submodules: Vec<String> = details
+        .submodules
+        .iter()
+        .map(|submodule| match &submodule.url {
+            Some(url) => format!("{}  {}", submodule.path.display(), url),
+            None => submodule.path.display().to_string(),
+        })
+        .collect();
+    if submodules.is_empty() {
+        submodules.push(yes_no(false));
+    }
+
+    let state = label(if details.status.dirty {
+        "state-modified"
+    } else {
+        "state-clean"
+    });
+    let mut rows = vec![
+        (label("header-name"), vec![project.name.clone()]),
+        (label("header-path"), vec![project.display_path(path_style)]),
+        (
+            label("header-branch"),
+            vec![format_head(project, localizer)],
+        ),
+        (label("header-state"), vec![state]),
+        (
+            label("header-upstream"),
+            vec![format_upstream(&details.status, localizer)],
+        ),
+        (label("header-remotes"), remotes),
+        (label("header-fork"), vec![format_fork(project, localizer)]),
+        (label("header-identity"), identity),
+        (label("header-branches"), branches),
+        (label("header-submodules"), submodules),
+        (
+            label("header-submodule"),
+            vec![yes_no(project.is_submodule)],
+        ),
+    ];
+    if !project.tags.is_empty() {
+        rows.push((label("header-tags"), vec![project.tags.join(", ")]));
+    }
+    if let Some(notes) = &project.notes {
+        rows.push((
+            label("header-notes"),
+            notes.lines().map(str::to_string).collect(),
+        ));
+    }
+    rows
+}
+
+/// Outputs the working tree status of repositories as a table
+pub fn output_status(
+    statuses: &[(&GitProject, RepoStatus)],
+    path_style: PathStyle,
+    localizer: &Localizer,
+) {
+    if statuses.is_empty() {
+        println!(
+            "{}",
+            clean_fluent_string(&localizer.get("scan-no-results", None))
+        );
+        return;
+    }
+
+    let rows: Vec<[String; 5]> = statuses
+        .iter()
+        .map(|(project, status)| {
+            let state = if status.dirty {
+                localizer.get("state-modified", None)
+            } else {
+                localizer.get("state-clean", None)
+            };
+            [
+                project.name.clone(),
+                format_head(project, localizer),
+                clean_fluent_string(&state),
+                format_upstream(status, localizer),
+                project.display_path(path_style),
+            ]
+        })
+        .collect();
+
+    let headers = [
+        "header-name",
+        "header-branch",
+        "header-state",
+        "header-upstream",
+        "header-path",
+    ]
+    .map(|key| clean_fluent_string(&localizer.get(key, None)));
+    let mut widths = headers.clone().map(|header| header.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count()).min(60);
+        }
+    }
+
+    let print_row = |cells: &[String; 5]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", truncate(cell, width), width = width))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    };
+    print_row(&headers);
+    println!("{}", "=".repeat(widths.iter().sum::<usize>() + 8));
+    for row in &rows {
+        print_row(row);
+    }
+
+    println!();
+    let count = statuses.len().to_string();
+    let dirty = statuses
+        .iter()
+        .filter(|(_, status)| status.dirty)
+        .count()
+        .to_string();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get(
+            "status-summary",
+            Some(&[("count", count.as_str()), ("dirty", dirty.as_str())])
+        ))
+    );
+}
+
+/// Formats the upstream with commits ahead and behind, e.g. "origin/main ↑2 ↓1"
+fn format_upstream(status: &RepoStatus, localizer: &Localizer) -> String {
+    let Some(upstream) = &status.upstream else {
+        return clean_fluent_string(&localizer.get("upstream-none", None));
+    };
+    match (status.ahead, status.behind) {
+        (Some(ahead), Some(behind)) => {
+            let mut result = upstream.clone();
+            if ahead > 0 {
+                result.push_str(&format!(" ↑{}", ahead));
+            }
+            if behind > 0 {
+                result.push_str(&format!(" ↓{}", behind));
+            }
+            result
+        }
+        _ => format!(
+            "{} {}",
+            upstream,
+            clean_fluent_string(&localizer.get("upstream-not-fetched", None))
+        ),
+    }
+}
+
+/// Outputs audit findings, one block per check
+pub fn output_audit(
+    findings: &[AuditFinding],
+    projects: &[GitProject],
+    path_style: PathStyle,
+    localizer: &Localizer,
+) {
+    if findings.is_empty() {
+        println!(
+            "{}",
+            clean_fluent_string(&localizer.get("audit-none", None))
+        );
+        return;
+    }
+
+    let paths: HashMap<&Path, String> = projects
+        .iter()
+        .map(|p| (p.path.as_path(), p.display_path(path_style)))
+        .collect();
+
+    for check in AuditCheck::ALL {
+        let matching: Vec<&AuditFinding> = findings.iter().filter(|f| f.check == *check).collect();
+        if matching.is_empty() {
+            continue;
+        }
+        let title = clean_fluent_string(&localizer.get(&format!("audit-{}", check.name()), None));
+        println!("{} ({})", title, matching.len());
+
+        let name_width = matching
+            .iter()
+            .map(|f| f.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        for finding in matching {
+            let path = paths
+                .get(finding.path.as_path())
+                .cloned()
+                .unwrap_or_else(|| finding.path.display().to_string());
+            let line = match &finding.detail {
+                Some(detail) => format!(
+                    "  {:<name_width$}  {}  {}",
+                    finding.name,
+                    path,
+                    detail,
+                    name_width = name_width
+                ),
+                None => format!(
+                    "  {:<name_width$}  {}",
+                    finding.name,
+                    path,
+                    name_width = name_width
+                ),
+            };
+            println!("{}", line);
+        }
+        println!();
+    }
+
+    let count = findings.len().to_string();
+    let affected = findings
+        .iter()
+        .map(|f| &f.path)
+        .collect::<std::collections::HashSet<_>>()
+        .len()
+        .to_string();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get(
+            "audit-summary",
+            Some(&[("count", count.as_str()), ("projects", affected.as_str())])
+        ))
+    );
+}
+
+/// Outputs the repositories `exec --dry-run` would run the command in
+pub fn output_exec_plan(
+    projects: &[GitProject],
+    command: &[String],
+    path_style: PathStyle,
+    localizer: &Localizer,
+) {
+    let command = command.join(" ");
+    let count = projects.len().to_string();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get(
+            "exec-dry-run",
+            Some(&[("command", command.as_str()), ("count", count.as_str())])
+        ))
+    );
+
+    let name_width = projects
+        .iter()
+        .map(|p| p.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for project in projects {
+        println!(
+            "  {:<name_width$}  {}",
+            project.name,
+            project.display_path(path_style),
+            name_width = name_width
+        );
+    }
+}
+
+/// Outputs the failed commands of an `exec` run and the counts to stderr,
+/// after the output of the commands
+pub fn output_exec_summary(report: &ExecReport, localizer: &Localizer) {
+    let failed: Vec<&ExecResult> = report
+        .results
+        .iter()
+        .filter(|r| matches!(r.status, ExecStatus::Failed | ExecStatus::Error))
+        .collect();
+    let name_width = failed
+        .iter()
+        .map(|r| r.name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    eprintln!();
+    for result in &failed {
+        let reason = match (&result.error, result.exit_code) {
+            (Some(error), _) => error.clone(),
+            (None, Some(code)) => {
+                let code = code.to_string();
+                clean_fluent_string(
+                    &localizer.get("exec-exit-code", Some(&[("code", code.as_str())])),
+                )
+            }
+            (None, None) => clean_fluent_string(&localizer.get("exec-signal", None)),
+        };
+        eprintln!(
+            "  {:<name_width$}  {}",
+            result.name,
+            reason,
+            name_width = name_width
+        );
+    }
+
+    let summary = &report.summary;
+    let count = report.results.len().to_string();
+    let succeeded = summary.succeeded.to_string();
+    let failed = summary.failed.to_string();
+    let skipped = summary.skipped.to_string();
+    eprintln!(
+        "{}",
+        clean_fluent_string(&localizer.get(
+            "exec-summary",
+            Some(&[
+                ("count", count.as_str()),
+                ("succeeded", succeeded.as_str()),
+                ("failed", failed.as_str()),
+                ("skipped", skipped.as_str()),
+            ])
+        ))
+    );
+}
+
+/// Outputs what `restore` did with one repository, as soon as it is done
+pub fn output_restore_result(
+    path: &Path,
+    result: &RestoreResult,
+    dry_run: bool,
+    localizer: &Localizer,
+) {
+    let remotes = result.added_remotes.join(", ");
+    let message = match result.status {
+        RestoreStatus::Cloned if dry_run => localizer.get("restore-would-clone", None),
+        RestoreStatus::Cloned => localizer.get("restore-cloned", None),
+        RestoreStatus::Updated => localizer.get(
+            if dry_run {
+                "restore-would-update"
+            } else {
+                "restore-updated"
+            },
+            Some(&[("remotes", remotes.as_str())]),
+        ),
+        RestoreStatus::Unchanged => localizer.get("restore-unchanged", None),
+        RestoreStatus::Conflict if result.conflicts.is_empty() => {
+            localizer.get("restore-not-repository", None)
+        }
+        RestoreStatus::Conflict => result
+            .conflicts
+            .iter()
+            .map(|conflict| {
+                clean_fluent_string(&localizer.get(
+                    "restore-conflict",
+                    Some(&[
+                        ("remote", conflict.remote.as_str()),
+                        ("actual", conflict.actual.as_str()),
+                        ("expected", conflict.expected.as_str()),
+                    ]),
+                ))
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+        RestoreStatus::Failed => {
+            let error = result.error.as_deref().unwrap_or_default();
+            localizer.get("restore-failed", Some(&[("error", error)]))
+        }
+    };
+    println!("{}: {}", path.display(), clean_fluent_string(&message));
+}
+
+/// Outputs the counts of a `restore` by status
+pub fn output_restore_summary(results: &[RestoreResult], localizer: &Localizer) {
+    let count = |status| {
+        results
+            .iter()
+            .filter(|r| r.status == status)
+            .count()
+            .to_string()
+    };
+    let cloned = count(RestoreStatus::Cloned);
+    let updated = count(RestoreStatus::Updated);
+    let unchanged = count(RestoreStatus::Unchanged);
+    let conflicts = count(RestoreStatus::Conflict);
+    let failed = count(RestoreStatus::Failed);
+    println!();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get(
+            "restore-summary",
+            Some(&[
+                ("cloned", cloned.as_str()),
+                ("updated", updated.as_str()),
+                ("unchanged", unchanged.as_str()),
+                ("conflicts", conflicts.as_str()),
+                ("failed", failed.as_str()),
+            ])
+        ))
+    );
+}
+
+/// Outputs the differences between a manifest and the scanned repositories
+pub fn output_manifest_check(result: &ManifestCheck, localizer: &Localizer) {
+    if result.is_clean() {
+        println!(
+            "{}",
+            clean_fluent_string(&localizer.get("check-clean", None))
+        );
+        return;
+    }
+
+    let missing = clean_fluent_string(&localizer.get("check-missing", None));
+    for path in &result.missing {
+        println!("{}: {}", path.display(), missing);
+    }
+    let extra = clean_fluent_string(&localizer.get("check-extra", None));
+    for path in &result.extra {
+        println!("{}: {}", path.display(), extra);
+    }
+    for mismatch in &result.mismatched {
+        let expected = mismatch.expected.join(", ");
+        let actual = mismatch.actual.join(", ");
+        let message = localizer.get(
+            "check-mismatched",
+            Some(&[("expected", expected.as_str()), ("actual", actual.as_str())]),
+        );
+        println!(
+            "{}: {}",
+            mismatch.path.display(),
+            clean_fluent_string(&message)
+        );
+    }
+
+    let missing = result.missing.len().to_string();
+    let extra = result.extra.len().to_string();
+    let mismatched = result.mismatched.len().to_string();
+    println!();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get(
+            "check-summary",
+            Some(&[
+                ("missing", missing.as_str()),
+                ("extra", extra.as_str()),
+                ("mismatched", mismatched.as_str()),
+            ])
+        ))
+    );
+}
+
+/// Outputs the changes between two scans as a table of paths and changes
+pub fn output_diff(changes: &[Change], localizer: &Localizer) {
+    if changes.is_empty() {
+        println!("{}", clean_fluent_string(&localizer.get("diff-none", None)));
+        return;
+    }
+
+    let rows: Vec<(String, String)> = changes
+        .iter()
+        .map(|change| {
+            (
+                change.path.display().to_string(),
+                format_change(&change.kind, localizer),
+            )
+        })
+        .collect();
+    let path_header = localizer.get("header-path", None);
+    let path_width = rows
+        .iter()
+        .map(|(path, _)| path.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(path_header.chars().count());
+
+    println!(
+        "{:<path_width$}  {}",
+        path_header,
+        localizer.get("header-change", None),
+        path_width = path_width
+    );
+
+    let change_width = rows
+        .iter()
+        .map(|(_, change)| change.chars().count())
+        .max()
+        .unwrap_or(0);
+    println!("{}", "=".repeat(path_width + 2 + change_width));
+    for (path, change) in &rows {
+        println!("{:<path_width$}  {}", path, change, path_width = path_width);
+    }
+
+    let count = changes.len().to_string();
+    let projects = changes
+        .iter()
+        .map(|c| &c.path)
+        .collect::<std::collections::HashSet<_>>()
+        .len()
+        .to_string();
+    println!();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get(
+            "diff-summary",
+            Some(&[("count", count.as_str()), ("projects", projects.as_str())])
+        ))
+    );
+}
+
+/// Describes a change between two scans
+fn format_change(kind: &ChangeKind, localizer: &Localizer) -> String {
+    let none = || clean_fluent_string(&localizer.get("diff-none-value", None));
+    let short = |commit: &Option<String>| match commit {
+        Some(commit) => commit.chars().take(7).collect(),
+        None => none(),
+    };
+    let message = match kind {
+        ChangeKind::Added => localizer.get("diff-added", None),
+        ChangeKind::Removed => localizer.get("diff-removed", None),
+        ChangeKind::Moved { from } => {
+            let from = from.display().to_string();
+            localizer.get("diff-moved", Some(&[("from", from.as_str())]))
+        }
+        ChangeKind::RemoteAdded { name, url } => localizer.get(
+            "diff-remote-added",
+            Some(&[("name", name.as_str()), ("url", url.as_str())]),
+        ),
+        ChangeKind::RemoteRemoved { name, url } => localizer.get(
+            "diff-remote-removed",
+            Some(&[("name", name.as_str()), ("url", url.as_str())]),
+        ),
+        ChangeKind::RemoteChanged { name, old, new } => localizer.get(
+            "diff-remote-changed",
+            Some(&[
+                ("name", name.as_str()),
+                ("old", old.as_str()),
+                ("new", new.as_str()),
+            ]),
+        ),
+        ChangeKind::IdentityChanged { old, new } => {
+            let old = old.clone().unwrap_or_else(none);
+            let new = new.clone().unwrap_or_else(none);
+            localizer.get(
+                "diff-identity-changed",
+                Some(&[("old", old.as_str()), ("new", new.as_str())]),
+            )
+        }
+        ChangeKind::BranchChanged { old, new } => {
+            let detached = || clean_fluent_string(&localizer.get("head-detached", None));
+            let old = old.clone().unwrap_or_else(detached);
+            let new = new.clone().unwrap_or_else(detached);
+            localizer.get(
+                "diff-branch-changed",
+                Some(&[("old", old.as_str()), ("new", new.as_str())]),
+            )
+        }
+        ChangeKind::HeadMoved { branch, old, new } => {
+            let branch = branch
+                .clone()
+                .unwrap_or_else(|| clean_fluent_string(&localizer.get("head-detached", None)));
+            let (old, new) = (short(old), short(new));
+            localizer.get(
+                "diff-head-moved",
+                Some(&[
+                    ("branch", branch.as_str()),
+                    ("old", old.as_str()),
+                    ("new", new.as_str()),
+                ]),
+            )
+        }
+    };
+    clean_fluent_string(&message)
+}
+
+/// Outputs what `db sync` recorded
+pub fn output_sync_summary(summary: &SyncSummary, localizer: &Localizer) {
+    let scan = summary.scan_id.to_string();
+    let count = summary.projects.to_string();
+    let added = summary.added.len().to_string();
+    let removed = summary.removed.len().to_string();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get(
+            "db-synced",
+            Some(&[
+                ("scan", scan.as_str()),
+                ("count", count.as_str()),
+                ("added", added.as_str()),
+                ("removed", removed.as_str()),
+            ])
+        ))
+    );
+}
+
+/// Outputs the rows of `db query` as a table; NULL is left empty
+pub fn output_query_result(result: &QueryResult, localizer: &Localizer) {
+    let cells: Vec<Vec<String>> = result
+        .rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|value| match value {
+                    serde_json::Value::Null => String::new(),
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = result
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([column.chars().count()])
+                .max()
+                .unwrap_or(0)
+                .min(60)
+        })
+        .collect();
+
+    let line = |values: &[String]| {
+        values
+            .iter()
+            .zip(&widths)
+            .map(|(value, &width)| format!("{:<width$}", truncate(value, width), width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    println!("{}", line(&result.columns));
+    println!(
+        "{}",
+        "=".repeat(widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1))
+    );
+    for row in &cells {
+        println!("{}", line(row));
+    }
+
+    let count = cells.len().to_string();
+    println!();
+    println!(
+        "{}",
+        clean_fluent_string(&localizer.get("db-rows", Some(&[("count", count.as_str())])))
+    );
+}
+
+/// Formats HEAD (branch and abbreviated commit) for display
+pub fn format_head(project: &GitProject, localizer: &Localizer) -> String {
+    let Some(head) = &project.head else {
+        return clean_fluent_string(&localizer.get("head-unknown", None));
+    };
+
+    let branch = match &head.branch {
+        Some(branch) => branch.clone(),
+        None => clean_fluent_string(&localizer.get("head-detached", None)),
+    };
+    let commit = match &head.commit {
+        Some(commit) => commit.chars().take(7).collect(),
+        None => clean_fluent_string(&localizer.get("head-unborn", None)),
+    };
+
+    format!("{} {}", branch, commit)
+}
+
+/// Formats remote information for display
+fn format_remotes(project: &GitProject, localizer: &Localizer) -> String {
+    if project.remotes.is_empty() {
+        return clean_fluent_string(&localizer.get("remote-none", None));
+    }
+
+    let first = &project.remotes[0];
+    let mut result = String::new();
+
+    // Add service if available
+    if let Some(service) = &first.service {
+        result.push_str(service);
+        if let Some(account) = &first.account {
+            result.push('/');
+            result.push_str(account);
+        }
+    } else {
+        // Fallback to remote name
+        result.push_str(&first.name);
+    }
+
+    // Add count if multiple remotes
+    if project.remotes.len() > 1 {
+        let count = project.remotes.len().to_string();
+        let remote_count =
+            clean_fluent_string(&localizer.get("remote-count", Some(&[("count", &count)])));
+        result.push_str(&format!(" (+{})", remote_count));
+    }
+
+    result
+}
+
+/// Formats the fork relationship for display (e.g., "fork of acme/tool")
+fn format_fork(project: &GitProject, localizer: &Localizer) -> String {
+    match project.fork_relation() {
+        Some(relation) => {
+            let upstream = relation.upstream_identity.slug();
+            clean_fluent_string(&localizer.get("fork-of", Some(&[("upstream", &upstream)])))
+        }
+        None => clean_fluent_string(&localizer.get("fork-none", None)),
+    }
+}
+
+/// Formats Git config for display
+fn format_config(project: &GitProject, localizer: &Localizer) -> String {
+    match &project.config {
+        Some(config) => {
+            let scope = match config.scope {
+                ConfigScope::Local => clean_fluent_string(&localizer.get("config-local", None)),
+                ConfigScope::Global => clean_fluent_string(&localizer.get("config-global", None)),
+                ConfigScope::System => clean_fluent_string(&localizer.get("config-system", None)),
+            };
+
+            match (&config.user_name, &config.user_email) {
+                (Some(name), Some(email)) => {
+                    format!("{} <{}> [{}]", name, email, scope)
+                }
+                (Some(name), None) => {
+                    format!("{} [{}]", name, scope)
+                }
+                (None, Some(email)) => {
+                    format!("<{}> [{}]", email, scope)
+                }
+                (None, None) => {
+                    format!("[{}]", scope)
+                }
+            }
+        }
+        None => clean_fluent_string(&localizer.get("config-none", None)),
+    }
+}
+
+/// Removes Unicode control characters that Fluent might add
+pub fn clean_fluent_string(s: &str) -> String {
+    s.chars()
+        .filter(|c| {
+            !matches!(
+                *c,
+                '\u{2068}' |  // FIRST STRONG ISOLATE
+            '\u{2069}' |  // POP DIRECTIONAL ISOLATE
+            '\u{202A}' |  // LEFT-TO-RIGHT EMBEDDING
+            '\u{202B}' |  // RIGHT-TO-LEFT EMBEDDING
+            '\u{202C}' |  // POP DIRECTIONAL FORMATTING
+            '\u{202D}' |  // LEFT-TO-RIGHT OVERRIDE
+            '\u{202E}' // RIGHT-TO-LEFT OVERRIDE
+            )
+        })
+        .collect()
+}
+
+/// Truncates a string to a maximum width, adding "..." if truncated
+/// Unicode-safe version that respects character boundaries
+fn truncate(s: &str, max_width: usize) -> String {
+    let char_count = s.chars().count();
+
+    if char_count <= max_width {
+        s.to_string()
+    } else if max_width <= 3 {
+        "...".to_string()
+    } else {
+        // Use char indices instead of byte indices
+        s.chars().take(max_width - 3).collect::<String>() + "..."
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("hello", 10), "hello");
+        assert_eq!(truncate("hello world", 8), "hello...");
+        assert_eq!(truncate("hi", 2), "hi");
+
+        // Test with Unicode
+        assert_eq!(truncate("café", 10), "café");
+        assert_eq!(truncate("hello 世界", 10), "hello 世界");
+
+        // truncate doesn't clean control characters - that's done by clean_fluent_string
+        // Just test that it doesn't panic on them
+        let result = truncate("test\u{2068}123\u{2069}", 10);
+        assert_eq!(result.chars().count(), 9); // 4 + 3 + 2 = 9 chars total
+    }
+
+    #[test]
+    fn test_clean_fluent_string() {
+        // Test that control characters are removed
+        assert_eq!(clean_fluent_string("test\u{2068}123\u{2069}"), "test123");
+        assert_eq!(clean_fluent_string("hello"), "hello");
+        assert_eq!(clean_fluent_string("\u{2068}wrapped\u{2069}"), "wrapped");
+    }
+}
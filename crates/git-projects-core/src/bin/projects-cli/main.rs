@@ -0,0 +1,1560 @@
+//! Command-line interface for Git Projects Scanner.
+//!
+//! This binary provides a user-friendly CLI for scanning and cataloging
+//! Git repositories on the local filesystem.
+
+mod cli;
+mod display;
+mod shell;
+mod tui;
+
+use anyhow::{Context, Result};
+use clap::{CommandFactory, Parser};
+use cli::{
+    Cli, Command, ConfigAction, DbAction, ExportKind, ProfilesAction, SortProfile, TagAction,
+};
+use git_projects_core::{
+    audit,
+    diff::{diff, load_snapshot},
+    exec::{exec, ExecOptions, ExecReport, ExecResult, Stream},
+    extract_status, find_duplicates, find_project, group_projects,
+    l10n::Localizer,
+    manifest::{check as check_manifest, restore_entry},
+    output, project_details, render_report,
+    server::Server,
+    workspace::{vscode_workspace, write_jetbrains},
+    AuditCheck, Column, Config, DefaultScanner, Filter, FrecencyStore, GitProject, GroupKey,
+    Inventory, Manifest, MetadataStore, OutputFormat, PathStyle, ProjectCache, ProjectScanner,
+    RemoteIdentity, RepoStatus, ReportOptions, RestoreOptions, RestoreStatus, ScanConfig,
+    SortDirection, SortSpec, Template,
+};
+use std::collections::BTreeSet;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    // Without a subcommand, scan and list like `projects-cli scan`
+    let command = cli.command.as_ref().unwrap_or(&Command::Scan);
+
+    match command {
+        Command::Schema => return output_json(&git_projects_core::schema::output_schema()),
+        Command::Init { shell, cmd } => {
+            anyhow::ensure!(
+                shell::is_valid_name(cmd),
+                "'{}' is not a valid function name",
+                cmd
+            );
+            print!(
+                "{}",
+                shell::init_script(*shell, cmd, env!("CARGO_BIN_NAME"))
+            );
+            return Ok(());
+        }
+        Command::Completions { shell } => {
+            clap_complete::generate(
+                *shell,
+                &mut Cli::command(),
+                env!("CARGO_BIN_NAME"),
+                &mut std::io::stdout(),
+            );
+            return Ok(());
+        }
+        Command::Config {
+            action: ConfigAction::Path,
+        } => {
+            let path = match &cli.config {
+                Some(path) => path.clone(),
+                None => Config::default_path()
+                    .context("Could not determine the configuration directory")?,
+            };
+            println!("{}", path.display());
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // Settings from configuration files; command-line options take precedence
+    let global = load_global_config(&cli)?;
+    if let Command::Profiles {
+        action: ProfilesAction::List,
+    } = command
+    {
+        list_profiles(&global);
+        return Ok(());
+    }
+    let config = load_config(&cli, global)?;
+
+    // Initialize localizer
+    let localizer = if let Some(locale) = cli.locale.as_ref().or(config.locale.as_ref()) {
+        Localizer::new(locale).with_context(|| format!("Failed to load locale: {}", locale))?
+    } else {
+        Localizer::from_system()
+            .unwrap_or_else(|_| Localizer::new("en").expect("Failed to load default locale"))
+    };
+
+    // Tags and notes are edited without scanning
+    let metadata_path =
+        MetadataStore::default_path().context("Could not determine the configuration directory")?;
+    match command {
+        Command::Tag { action } => return run_tag_command(action, &metadata_path, &localizer),
+        Command::Note {
+            project,
+            text,
+            clear,
+        } => return run_note_command(project, text.as_deref(), *clear, &metadata_path),
+        _ => {}
+    }
+
+    // Parse the filter and sort keys before scanning so errors are reported immediately
+    let filter = cli.filter.as_deref().map(parse_filter).transpose()?;
+    let sort = build_sort_spec(&cli, &config)?.with_locale(&localizer.locale());
+    let group_keys = cli
+        .group_by
+        .as_deref()
+        .map(GroupKey::parse_list)
+        .transpose()?;
+    let format = output_format(&cli, &config, group_keys.is_some());
+    let path_style = if cli.relative {
+        PathStyle::Relative
+    } else if cli.tilde {
+        PathStyle::Tilde
+    } else {
+        PathStyle::Absolute
+    };
+    let columns = cli.columns.as_deref().map(Column::parse_list).transpose()?;
+    check_output_options(&cli, format, columns.is_some(), group_keys.is_some())?;
+    let template = match (&cli.template, &cli.template_file) {
+        (Some(source), _) => Some(Template::new(unescape(source))?),
+        (None, Some(path)) => Some(Template::from_file(path)?),
+        (None, None) => None,
+    };
+
+    // Build scan configuration
+    let scan_config = build_scan_config(&cli, &config);
+    let announce = format == OutputFormat::Table && cli.verbose;
+
+    match command {
+        Command::Config {
+            action: ConfigAction::Show,
+        } => {
+            print!(
+                "{}",
+                effective_config(&config, &scan_config, &sort, format)?
+            );
+            return Ok(());
+        }
+        Command::Pick {
+            query,
+            list,
+            rescan,
+        } => {
+            let mut projects = cached_projects(&config, &scan_config, &metadata_path, *rescan)?;
+            filter_projects(
+                &mut projects,
+                cli.forks,
+                &cli.tags,
+                filter.iter().chain(&config.filter),
+            );
+            if *list {
+                let names: BTreeSet<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+                for name in names {
+                    println!("{}", name);
+                }
+                return Ok(());
+            }
+            sort.sort(&mut projects);
+            let picked = run_pick(
+                projects,
+                query.as_deref(),
+                &sort,
+                path_style,
+                &localizer,
+                |path| scan_single(path, &config, &scan_config, &metadata_path),
+            )?;
+            match picked {
+                Some(path) => println!("{}", path.display()),
+                // Cancelled; the shell function must not change directories
+                None => std::process::exit(1),
+            }
+            return Ok(());
+        }
+        Command::Restore {
+            manifest,
+            into,
+            dry_run,
+            allow_outside_root,
+        } => {
+            let root = match into {
+                Some(dir) => dir.clone(),
+                None => scan_config
+                    .root_paths
+                    .first()
+                    .cloned()
+                    .context("No scan root to restore into; use --into")?,
+            };
+            let restore = Restore {
+                manifest,
+                root: &root,
+                options: RestoreOptions {
+                    dry_run: *dry_run,
+                    allow_outside_root: *allow_outside_root,
+                },
+                metadata_path: &metadata_path,
+            };
+            if !run_restore(&restore, format, &localizer)? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Db {
+            db,
+            action: DbAction::Path,
+        } => {
+            println!("{}", inventory_path(db.as_deref())?.display());
+            return Ok(());
+        }
+        Command::Db {
+            db,
+            action: DbAction::Query { sql },
+        } => {
+            let path = inventory_path(db.as_deref())?;
+            let inventory = Inventory::open_read_only(&path)
+                .with_context(|| format!("Failed to open {}", path.display()))?;
+            let result = inventory.query(sql)?;
+            if format == OutputFormat::Json {
+                let rows: Vec<serde_json::Map<String, serde_json::Value>> = result
+                    .rows
+                    .into_iter()
+                    .map(|row| result.columns.iter().cloned().zip(row).collect())
+                    .collect();
+                output_json(&rows)?;
+            } else {
+                display::output_query_result(&result, &localizer);
+            }
+            return Ok(());
+        }
+        Command::Diff { old, new } => {
+            let changes = diff(&load_snapshot(old)?, &load_snapshot(new)?);
+            if format == OutputFormat::Json {
+                output_json(&changes)?;
+            } else {
+                display::output_diff(&changes, &localizer);
+            }
+            if !changes.is_empty() {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Show { project } => {
+            let project = resolve_project(project, &config, &scan_config, &metadata_path)?;
+            let details = project_details(project)?;
+            if format == OutputFormat::Json {
+                output_json(&details)?;
+            } else {
+                display::output_show(&details, path_style, &localizer);
+            }
+            return Ok(());
+        }
+        Command::Serve {
+            socket,
+            http,
+            interval,
+        } => {
+            let (config, scan_config, metadata_path) =
+                (config.clone(), scan_config.clone(), metadata_path.clone());
+            let (forks, tags, filter) = (cli.forks, cli.tags.clone(), filter.clone());
+            let server = Server::new(move || {
+                let mut projects = scan_projects(&config, &scan_config, &metadata_path)
+                    .map_err(|e| git_projects_core::Error::Other(format!("{:#}", e)))?;
+                filter_projects(
+                    &mut projects,
+                    forks,
+                    &tags,
+                    filter.iter().chain(&config.filter),
+                );
+                Ok(projects)
+            });
+            let serve = Serve {
+                socket: socket.as_deref(),
+                http: *http,
+                interval: *interval,
+            };
+            return run_serve(&server, &serve, &localizer);
+        }
+        _ => {}
+    }
+
+    if announce {
+        announce_scan(&config, &scan_config, &localizer);
+    }
+    let scan_configs = root_scan_configs(&config, &scan_config);
+    let mut projects = scan_roots(&scan_configs, announce)?;
+    save_cache(&scan_configs, &projects);
+    annotate(&config, &metadata_path, &mut projects)?;
+
+    // The inventory records the whole scan; synced after filtering, the
+    // repositories filtered out would be marked as removed
+    if let Command::Db { db, .. } = command {
+        let path = inventory_path(db.as_deref())?;
+        let mut inventory =
+            Inventory::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let summary = inventory.sync(&projects, &scan_config)?;
+        if format == OutputFormat::Json {
+            output_json(&summary)?;
+        } else {
+            display::output_sync_summary(&summary, &localizer);
+        }
+        return Ok(());
+    }
+    filter_projects(
+        &mut projects,
+        cli.forks,
+        &cli.tags,
+        filter.iter().chain(&config.filter),
+    );
+
+    // Sort the results
+    sort.sort(&mut projects);
+
+    match command {
+        Command::Report {
+            kind,
+            title,
+            output,
+        } => {
+            let options = ReportOptions {
+                title: title.clone(),
+                group_by: group_keys.unwrap_or_else(|| vec![GroupKey::Service]),
+                generated_at: Some(chrono::Utc::now()),
+                path_style,
+            };
+            let report = render_report(&projects, (*kind).into(), &options, &localizer);
+            match output {
+                Some(path) => std::fs::write(path, report)
+                    .with_context(|| format!("Failed to write report to {}", path.display()))?,
+                None => print!("{}", report),
+            }
+            return Ok(());
+        }
+        Command::Duplicates => {
+            let groups = find_duplicates(&projects);
+            if format == OutputFormat::Json {
+                output_json(&groups)?;
+            } else {
+                display::output_duplicates(&groups, path_style, &localizer);
+            }
+            return Ok(());
+        }
+        Command::Status { dirty } => {
+            return run_status(&projects, *dirty, format, path_style, &localizer);
+        }
+        Command::Audit { checks } => {
+            let checks: Vec<AuditCheck> = checks.iter().map(|&check| check.into()).collect();
+            let mut findings = audit(&projects);
+            if !checks.is_empty() {
+                findings.retain(|finding| checks.contains(&finding.check));
+            }
+            if format == OutputFormat::Json {
+                output_json(&findings)?;
+            } else {
+                display::output_audit(&findings, &projects, path_style, &localizer);
+            }
+            return Ok(());
+        }
+        Command::Exec {
+            jobs,
+            fail_fast,
+            dry_run,
+            report,
+            command,
+        } => {
+            let options = ExecOptions {
+                jobs: jobs.map_or(ExecOptions::default().jobs, |jobs| jobs.get()),
+                fail_fast: *fail_fast,
+            };
+            let run = ExecRun {
+                command,
+                options: &options,
+                dry_run: *dry_run,
+                report: report.as_deref(),
+            };
+            if !run_exec(&projects, &run, format, path_style, &localizer)? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Check { manifest } => {
+            if !run_check(manifest, &projects, format, &localizer)? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Tui => {
+            anyhow::ensure!(
+                std::io::stdin().is_terminal() && std::io::stderr().is_terminal(),
+                "tui needs an interactive terminal"
+            );
+            return tui::run(projects, &sort, path_style, &localizer, |path| {
+                scan_single(path, &config, &scan_config, &metadata_path)
+            });
+        }
+        Command::Export { kind, output } => {
+            return match kind.format() {
+                Some(format) => {
+                    run_export(&projects, format, columns.as_deref(), output.as_deref())
+                }
+                None => match kind {
+                    ExportKind::VscodeWorkspace => run_export_vscode(&projects, output.as_deref()),
+                    ExportKind::Jetbrains => {
+                        let dir = output
+                            .as_deref()
+                            .context("export jetbrains needs --output DIR")?;
+                        write_jetbrains(&projects, dir)
+                            .with_context(|| format!("Failed to write {}", dir.display()))
+                    }
+                    _ => run_export_manifest(&projects, *kind, output.as_deref()),
+                },
+            };
+        }
+        _ => {}
+    }
+
+    // Show completion message
+    if announce {
+        let count = projects.len().to_string();
+        eprintln!(
+            "{}",
+            display::clean_fluent_string(
+                &localizer.get("scan-complete", Some(&[("count", &count)]))
+            )
+        );
+    }
+
+    // Output results
+    if let Some(keys) = &group_keys {
+        let groups = group_projects(&projects, keys);
+        if format == OutputFormat::Json {
+            output_json(&groups)?;
+        } else {
+            display::output_tree(
+                &groups,
+                projects.len(),
+                cli.collapsed,
+                path_style,
+                &localizer,
+            );
+        }
+    } else if let Some(template) = &template {
+        let rendered = if cli.template_document {
+            template.render_document(&projects)?
+        } else {
+            template.render_each(&projects)?
+        };
+        print!("{}", rendered);
+    } else if format == OutputFormat::Table {
+        display::output_table(&projects, path_style, &localizer)?;
+    } else {
+        output_projects(&projects, format, columns.as_deref())?;
+    }
+
+    Ok(())
+}
+
+/// Scans the roots and applies the configuration and stored tags and notes
+fn scan_projects(
+    config: &Config,
+    scan_config: &ScanConfig,
+    metadata_path: &Path,
+) -> Result<Vec<GitProject>> {
+    let mut projects = scan_roots(&root_scan_configs(config, scan_config), false)?;
+    annotate(config, metadata_path, &mut projects)?;
+    Ok(projects)
+}
+
+/// Prints the configuration files read and the roots about to be scanned
+fn announce_scan(config: &Config, scan_config: &ScanConfig, localizer: &Localizer) {
+    for source in &config.sources {
+        let path_str = source.display().to_string();
+        eprintln!(
+            "{}",
+            display::clean_fluent_string(
+                &localizer.get("config-loaded", Some(&[("path", path_str.as_str())]))
+            )
+        );
+    }
+    eprintln!(
+        "{}",
+        display::clean_fluent_string(&localizer.get("scan-started", None))
+    );
+    for root in &scan_config.root_paths {
+        let path_str = root.display().to_string();
+        eprintln!(
+            "{}",
+            display::clean_fluent_string(
+                &localizer.get("scan-started-path", Some(&[("path", path_str.as_str())]))
+            )
+        );
+    }
+}
+
+/// Returns the settings for each root, from its own configuration file
+fn root_scan_configs(config: &Config, scan_config: &ScanConfig) -> Vec<ScanConfig> {
+    scan_config
+        .root_paths
+        .iter()
+        .map(|root| config.scan_config_for(root, scan_config))
+        .collect()
+}
+
+/// Scans with each of `scan_configs`
+fn scan_roots(scan_configs: &[ScanConfig], verbose: bool) -> Result<Vec<GitProject>> {
+    let scanner = DefaultScanner::new().with_verbose(verbose);
+    let mut projects = Vec::new();
+    for scan_config in scan_configs {
+        projects.extend(
+            scanner
+                .scan(scan_config)
+                .context("Failed to scan for Git repositories")?,
+        );
+    }
+    Ok(projects)
+}
+
+/// Applies the configuration and the stored tags and notes to scan results
+fn annotate(config: &Config, metadata_path: &Path, projects: &mut Vec<GitProject>) -> Result<()> {
+    // Apply host mappings, tags and ignore flags from the configuration
+    config.apply(projects);
+    let metadata = MetadataStore::load(metadata_path).with_context(|| {
+        format!(
+            "Failed to load tags and notes from {}",
+            metadata_path.display()
+        )
+    })?;
+    metadata.apply(projects);
+    Ok(())
+}
+
+/// Applies `--forks`, `--tag`, `--filter` and the configured filter
+///
+/// Keeps forks only if `forks` is set, and projects with all of `tags` that
+/// match all of `filters`.
+fn filter_projects<'a>(
+    projects: &mut Vec<GitProject>,
+    forks: bool,
+    tags: &[String],
+    filters: impl IntoIterator<Item = &'a Filter>,
+) {
+    if forks {
+        projects.retain(|p| p.fork_relation().is_some());
+    }
+    if !tags.is_empty() {
+        projects.retain(|p| tags.iter().all(|tag| p.tags.contains(tag)));
+    }
+    for filter in filters {
+        projects.retain(|p| filter.matches(p));
+    }
+}
+
+/// Saves the results of a full scan for `pick`, before the configuration
+/// and metadata are applied
+///
+/// The cache only speeds things up, so failures are ignored.
+fn save_cache(scan_configs: &[ScanConfig], projects: &[GitProject]) {
+    if let Some(path) = ProjectCache::default_path() {
+        let _ = ProjectCache::new(scan_configs, projects.to_vec()).save(&path);
+    }
+}
+
+/// Returns the results of the last scan with the same settings, or scans
+///
+/// An unreadable cache is treated like a missing one.
+fn cached_projects(
+    config: &Config,
+    scan_config: &ScanConfig,
+    metadata_path: &Path,
+    rescan: bool,
+) -> Result<Vec<GitProject>> {
+    let scan_configs = root_scan_configs(config, scan_config);
+    let cached = ProjectCache::default_path()
+        .filter(|_| !rescan)
+        .and_then(|path| ProjectCache::load(&path).ok().flatten())
+        .filter(|cache| cache.matches(&scan_configs));
+    let mut projects = match cached {
+        Some(cache) => cache.projects,
+        None => {
+            let projects = scan_roots(&scan_configs, false)?;
+            save_cache(&scan_configs, &projects);
+            projects
+        }
+    };
+    // Tags, notes and rules may have changed since the scan
+    annotate(config, metadata_path, &mut projects)?;
+    Ok(projects)
+}
+
+/// Runs `pick`: chooses a repository by query or interactively and records
+/// the visit
+///
+/// Returns `None` if the user cancels the interactive picker.
+fn run_pick(
+    projects: Vec<GitProject>,
+    query: Option<&str>,
+    sort: &SortSpec,
+    path_style: PathStyle,
+    localizer: &Localizer,
+    rescan: impl Fn(&Path) -> Result<GitProject>,
+) -> Result<Option<PathBuf>> {
+    let store_path =
+        FrecencyStore::default_path().context("Could not determine the data directory")?;
+    let mut store = FrecencyStore::load(&store_path)
+        .with_context(|| format!("Failed to load frecency from {}", store_path.display()))?;
+    let now = chrono::Utc::now();
+
+    let path = match query {
+        Some(query) => match store.rank(query, &projects, now).first() {
+            Some(project) => project.path.clone(),
+            None => return Err(git_projects_core::Error::project_not_found(query).into()),
+        },
+        None => {
+            anyhow::ensure!(
+                std::io::stdin().is_terminal() && std::io::stderr().is_terminal(),
+                "pick needs a query or an interactive terminal"
+            );
+            let boost = projects
+                .iter()
+                .map(|p| (p.path.clone(), store.boost(&p.path, now)))
+                .collect();
+            match tui::pick(projects, boost, sort, path_style, localizer, rescan)? {
+                Some(path) => path,
+                None => return Ok(None),
+            }
+        }
+    };
+
+    store.record(&path, now);
+    store
+        .save(&store_path)
+        .with_context(|| format!("Failed to save frecency to {}", store_path.display()))?;
+    Ok(Some(path))
+}
+
+/// Resolves `show`'s argument to a repository
+///
+/// An existing path is analyzed directly (from any directory inside the
+/// repository); anything else is matched against the names in the scan
+/// results, exactly or else fuzzily.
+fn resolve_project(
+    query: &str,
+    config: &Config,
+    scan_config: &ScanConfig,
+    metadata_path: &Path,
+) -> Result<GitProject> {
+    let path = Path::new(query);
+    if path.exists() {
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", path.display()))?;
+        let Some(repo) = path.ancestors().find(|dir| dir.join(".git").exists()) else {
+            anyhow::bail!("Not inside a Git repository: {}", path.display());
+        };
+        return scan_single(repo, config, scan_config, metadata_path);
+    }
+
+    let projects = scan_projects(config, scan_config, metadata_path)?;
+    Ok(find_project(&projects, query)?.clone())
+}
+
+/// Analyzes a single repository with the settings of a full scan
+fn scan_single(
+    repo: &Path,
+    config: &Config,
+    scan_config: &ScanConfig,
+    metadata_path: &Path,
+) -> Result<GitProject> {
+    let single = ScanConfig {
+        root_paths: vec![repo.to_path_buf()],
+        max_depth: Some(0),
+        ..scan_config.clone()
+    };
+    let mut projects = scan_roots(&[single], false)?;
+    annotate(config, metadata_path, &mut projects)?;
+    projects
+        .into_iter()
+        .next()
+        .with_context(|| format!("Not a Git repository: {}", repo.display()))
+}
+
+/// A line of `status --json`
+#[derive(serde::Serialize)]
+struct StatusEntry<'a> {
+    name: &'a str,
+    path: &'a Path,
+    #[serde(flatten)]
+    status: &'a RepoStatus,
+}
+
+/// Runs `status`: reads the working tree status of each repository
+///
+/// Repositories whose status cannot be read are reported on stderr and skipped.
+fn run_status(
+    projects: &[GitProject],
+    dirty_only: bool,
+    format: OutputFormat,
+    path_style: PathStyle,
+    localizer: &Localizer,
+) -> Result<()> {
+    let mut statuses = Vec::new();
+    for project in projects {
+        match extract_status(&project.path) {
+            Ok(status) if dirty_only && !status.dirty => {}
+            Ok(status) => statuses.push((project, status)),
+            Err(e) => {
+                let message = e.to_string();
+                eprintln!(
+                    "{}",
+                    display::clean_fluent_string(
+                        &localizer.get("verbose-warning", Some(&[("message", message.as_str())]))
+                    )
+                );
+            }
+        }
+    }
+
+    if format == OutputFormat::Json {
+        let entries: Vec<StatusEntry> = statuses
+            .iter()
+            .map(|(project, status)| StatusEntry {
+                name: &project.name,
+                path: &project.path,
+                status,
+            })
+            .collect();
+        output_json(&entries)
+    } else {
+        display::output_status(&statuses, path_style, localizer);
+        Ok(())
+    }
+}
+
+/// What `exec` runs, and how
+struct ExecRun<'a> {
+    command: &'a [String],
+    options: &'a ExecOptions,
+    dry_run: bool,
+    report: Option<&'a Path>,
+}
+
+/// Runs `exec`: runs the command in each repository and summarizes the results
+///
+/// Command output is prefixed with the repository name. With JSON output the
+/// report goes to stdout, so the command output goes to stderr. Returns
+/// whether the command succeeded everywhere.
+fn run_exec(
+    projects: &[GitProject],
+    run: &ExecRun,
+    format: OutputFormat,
+    path_style: PathStyle,
+    localizer: &Localizer,
+) -> Result<bool> {
+    let json = format == OutputFormat::Json;
+    let results = if run.dry_run {
+        if !json {
+            display::output_exec_plan(projects, run.command, path_style, localizer);
+        }
+        projects.iter().map(ExecResult::skipped).collect()
+    } else {
+        let width = projects
+            .iter()
+            .map(|p| p.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        exec(
+            projects,
+            run.command,
+            run.options,
+            |project, stream, line| {
+                let prefix = format!("[{}]", project.name);
+                let line = format!("{:<w$} {}", prefix, line, w = width + 2);
+                if stream == Stream::Stderr || json {
+                    eprintln!("{}", line);
+                } else {
+                    println!("{}", line);
+                }
+            },
+        )?
+    };
+
+    let report = ExecReport::new(run.command, results);
+    if let Some(path) = run.report {
+        let source =
+            serde_json::to_string_pretty(&report).context("Failed to serialize results to JSON")?;
+        std::fs::write(path, source + "\n")
+            .with_context(|| format!("Failed to write report to {}", path.display()))?;
+    }
+    if json {
+        output_json(&report)?;
+    } else if !run.dry_run {
+        display::output_exec_summary(&report, localizer);
+    }
+    Ok(report.summary.failed == 0)
+}
+
+/// Runs `export`: writes the projects to a file or stdout
+fn run_export(
+    projects: &[GitProject],
+    format: OutputFormat,
+    columns: Option<&[Column]>,
+    output: Option<&Path>,
+) -> Result<()> {
+    let Some(path) = output else {
+        return output_projects(projects, format, columns);
+    };
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut writer = std::io::BufWriter::new(file);
+    write_projects(&mut writer, projects, format, columns)
+        .and_then(|()| Ok(writer.flush()?))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Runs `export manifest` and the other manifest formats: writes the layout
+/// of the projects for `restore` or another tool
+fn run_export_manifest(
+    projects: &[GitProject],
+    kind: ExportKind,
+    output: Option<&Path>,
+) -> Result<()> {
+    let format = kind.manifest_format().context("Not a manifest format")?;
+    let rendered = format.render(&Manifest::from_projects(projects)?)?;
+    match output {
+        Some(path) => std::fs::write(path, rendered)
+            .with_context(|| format!("Failed to write {}", path.display())),
+        None => {
+            print!("{}", rendered);
+            Ok(())
+        }
+    }
+}
+
+/// Runs `export vscode-workspace`: writes a workspace with a folder per
+/// project, keeping the settings and folders of an existing file
+fn run_export_vscode(projects: &[GitProject], output: Option<&Path>) -> Result<()> {
+    let Some(path) = output else {
+        print!("{}", vscode_workspace(projects, None, None)?);
+        return Ok(());
+    };
+    let existing = match std::fs::read_to_string(path) {
+        Ok(existing) => Some(existing),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    // Folder paths are relative to the directory of the file
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let base = std::fs::canonicalize(dir).ok();
+    let rendered = vscode_workspace(projects, base.as_deref(), existing.as_deref())
+        .with_context(|| format!("Failed to update {}", path.display()))?;
+    std::fs::write(path, rendered).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Options of `serve`
+struct Serve<'a> {
+    socket: Option<&'a Path>,
+    http: Option<u16>,
+    interval: u64,
+}
+
+/// Runs `serve`: scans, then answers clients until interrupted
+fn run_serve(server: &Server, serve: &Serve, localizer: &Localizer) -> Result<()> {
+    server
+        .rescan()
+        .context("Failed to scan for Git repositories")?;
+    let count = server.projects().len().to_string();
+    eprintln!(
+        "{}",
+        display::clean_fluent_string(
+            &localizer.get("serve-scanned", Some(&[("count", count.as_str())]))
+        )
+    );
+    if serve.interval > 0 {
+        server.watch(std::time::Duration::from_secs(serve.interval));
+    }
+
+    let http = match serve.http {
+        Some(port) => {
+            let listener = std::net::TcpListener::bind(("127.0.0.1", port))
+                .with_context(|| format!("Failed to listen on port {}", port))?;
+            let address = listener.local_addr()?.to_string();
+            eprintln!(
+                "{}",
+                display::clean_fluent_string(
+                    &localizer.get("serve-http", Some(&[("address", address.as_str())]))
+                )
+            );
+            Some(listener)
+        }
+        None => None,
+    };
+
+    #[cfg(unix)]
+    {
+        let path = match serve.socket {
+            Some(path) => path.to_path_buf(),
+            None => Server::default_socket_path()
+                .context("Could not determine the runtime directory")?,
+        };
+        let listener = bind_socket(&path)?;
+        let path_str = path.display().to_string();
+        eprintln!(
+            "{}",
+            display::clean_fluent_string(
+                &localizer.get("serve-socket", Some(&[("path", path_str.as_str())]))
+            )
+        );
+        if let Some(http) = http {
+            let server = server.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = server.serve_http(http) {
+                    eprintln!("HTTP server stopped: {}", e);
+                }
+            });
+        }
+        server.serve_unix(listener)?;
+    }
+    #[cfg(not(unix))]
+    {
+        anyhow::ensure!(serve.socket.is_none(), "--socket needs Unix domain sockets");
+        let http = http.context("Serving needs --http on this platform")?;
+        server.serve_http(http)?;
+    }
+    Ok(())
+}
+
+/// Binds the socket of `serve`, replacing the socket of a daemon that is gone
+#[cfg(unix)]
+fn bind_socket(path: &Path) -> Result<std::os::unix::net::UnixListener> {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    if path.exists() {
+        anyhow::ensure!(
+            UnixStream::connect(path).is_err(),
+            "Another daemon is already listening on {}",
+            path.display()
+        );
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("Failed to listen on {}", path.display()))?;
+    // Only the owner may talk to the daemon
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// Returns the path of the inventory database: `--db`, or the default
+fn inventory_path(db: Option<&Path>) -> Result<PathBuf> {
+    match db {
+        Some(path) => Ok(path.to_path_buf()),
+        None => Inventory::default_path().context("Could not determine the data directory"),
+    }
+}
+
+/// Runs `check`: compares a manifest with the scanned projects
+///
+/// Returns whether they agree.
+fn run_check(
+    manifest_path: &Path,
+    projects: &[GitProject],
+    format: OutputFormat,
+    localizer: &Localizer,
+) -> Result<bool> {
+    let manifest = Manifest::load(manifest_path)
+        .with_context(|| format!("Failed to read manifest {}", manifest_path.display()))?;
+    let result = check_manifest(&manifest, projects);
+    if format == OutputFormat::Json {
+        output_json(&result)?;
+    } else {
+        display::output_manifest_check(&result, localizer);
+    }
+    Ok(result.is_clean())
+}
+
+/// What `restore` restores, and where
+struct Restore<'a> {
+    manifest: &'a Path,
+    root: &'a Path,
+    options: RestoreOptions,
+    metadata_path: &'a Path,
+}
+
+/// Runs `restore`: clones missing repositories of a manifest and adds remotes
+///
+/// The tags in the manifest are added to the tags of the restored
+/// repositories. Returns whether there were no conflicts or failures.
+fn run_restore(restore: &Restore, format: OutputFormat, localizer: &Localizer) -> Result<bool> {
+    let manifest = Manifest::load(restore.manifest)
+        .with_context(|| format!("Failed to read manifest {}", restore.manifest.display()))?;
+    let json = format == OutputFormat::Json;
+    let dry_run = restore.options.dry_run;
+    let mut store = MetadataStore::load(restore.metadata_path).with_context(|| {
+        format!(
+            "Failed to load tags and notes from {}",
+            restore.metadata_path.display()
+        )
+    })?;
+
+    let mut tagged = false;
+    let mut results = Vec::new();
+    for entry in &manifest.projects {
+        let result = restore_entry(entry, restore.root, &restore.options);
+        if !json {
+            display::output_restore_result(&entry.path, &result, dry_run, localizer);
+        }
+        let restored = matches!(
+            result.status,
+            RestoreStatus::Cloned | RestoreStatus::Updated | RestoreStatus::Unchanged
+        );
+        if restored && !dry_run && !entry.tags.is_empty() {
+            if let Ok(key) = MetadataStore::key_for_path(&result.path) {
+                tagged |= store.add_tags(&key, &entry.tags) > 0;
+            }
+        }
+        results.push(result);
+    }
+
+    if tagged {
+        store.save(restore.metadata_path).with_context(|| {
+            format!("Failed to save tags to {}", restore.metadata_path.display())
+        })?;
+    }
+    if json {
+        output_json(&results)?;
+    } else if !dry_run {
+        display::output_restore_summary(&results, localizer);
+    }
+    Ok(results
+        .iter()
+        .all(|r| !matches!(r.status, RestoreStatus::Conflict | RestoreStatus::Failed)))
+}
+
+/// Renders the effective settings as TOML, in the format of the configuration file
+fn effective_config(
+    config: &Config,
+    scan_config: &ScanConfig,
+    sort: &SortSpec,
+    format: OutputFormat,
+) -> Result<String> {
+    use toml::{Table, Value};
+
+    let strings = |values: Vec<String>| Value::Array(values.into_iter().map(Value::from).collect());
+
+    let mut scan = Table::new();
+    scan.insert(
+        "roots".into(),
+        strings(
+            scan_config
+                .root_paths
+                .iter()
+                .map(|root| root.display().to_string())
+                .collect(),
+        ),
+    );
+    if let Some(depth) = scan_config.max_depth {
+        scan.insert("depth".into(), Value::Integer(depth as i64));
+    }
+    scan.insert(
+        "follow_symlinks".into(),
+        Value::Boolean(scan_config.follow_symlinks),
+    );
+    scan.insert(
+        "include_submodules".into(),
+        Value::Boolean(scan_config.include_submodules),
+    );
+    scan.insert(
+        "ignore".into(),
+        strings(scan_config.ignore_patterns.clone()),
+    );
+
+    let mut output = Table::new();
+    let keys: Vec<&str> = sort.keys().iter().map(|(key, _)| key.name()).collect();
+    let orders: Vec<&str> = sort
+        .keys()
+        .iter()
+        .map(|(_, direction)| match direction {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        })
+        .collect();
+    output.insert("sort".into(), Value::from(keys.join(",")));
+    output.insert("order".into(), Value::from(orders.join(",")));
+    output.insert("format".into(), Value::from(format.to_string()));
+    output.insert("locale".into(), Value::from(sort.locale()));
+
+    let mut document = Table::new();
+    document.insert("scan".into(), Value::Table(scan));
+    document.insert("output".into(), Value::Table(output));
+    if !config.hosts.is_empty() {
+        let hosts = config
+            .hosts
+            .iter()
+            .map(|(host, service)| (host.clone(), Value::from(service.as_str())))
+            .collect();
+        document.insert("hosts".into(), Value::Table(hosts));
+    }
+
+    let mut result = String::new();
+    for source in &config.sources {
+        result.push_str(&format!("# {}\n", source.display()));
+    }
+    if let Some(filter) = &config.filter {
+        result.push_str(&format!("# filter: {}\n", filter.source()));
+    }
+    if !config.projects.is_empty() {
+        result.push_str(&format!("# {} project rules\n", config.projects.len()));
+    }
+    result.push_str(&toml::to_string(&document).context("Failed to format the settings")?);
+    Ok(result)
+}
+
+/// Loads the global configuration file (or --config)
+fn load_global_config(cli: &Cli) -> Result<Config> {
+    if cli.no_config {
+        Ok(Config::default())
+    } else if let Some(path) = &cli.config {
+        Config::load(path)
+            .with_context(|| format!("Failed to load configuration file {}", path.display()))
+    } else {
+        Config::load_default().context("Failed to load configuration file")
+    }
+}
+
+/// Applies the selected profile and determines the scan roots
+///
+/// Merges the profile given with --profile over the global file, then the
+/// .git-projects.toml of each root. The roots of the returned configuration
+/// are the ones to scan.
+fn load_config(cli: &Cli, global: Config) -> Result<Config> {
+    let global = match &cli.profile {
+        Some(name) => global.with_profile(name)?,
+        None => global,
+    };
+
+    // Determine root paths: command line, configuration, home directory
+    let root_paths = if !cli.roots.is_empty() {
+        cli.roots.clone()
+    } else if !global.roots.is_empty() {
+        global.roots.clone()
+    } else {
+        vec![dirs::home_dir().context("Could not determine home directory")?]
+    };
+
+    // Validate that all root paths exist
+    for path in &root_paths {
+        if !path.exists() {
+            anyhow::bail!("Path does not exist: {}", path.display());
+        }
+        if !path.is_dir() {
+            anyhow::bail!("Path is not a directory: {}", path.display());
+        }
+    }
+
+    let mut config = if cli.no_config {
+        global
+    } else {
+        global
+            .with_roots(&root_paths)
+            .context("Failed to load configuration file")?
+    };
+    config.roots = root_paths;
+
+    // Command-line options take precedence over root files, too
+    for root_config in config.root_configs.values_mut() {
+        if cli.max_depth.is_some() {
+            root_config.max_depth = None;
+        }
+        if cli.no_symlinks {
+            root_config.follow_symlinks = None;
+        }
+        if cli.no_submodules {
+            root_config.include_submodules = None;
+        }
+    }
+    Ok(config)
+}
+
+/// Builds a ScanConfig from the configuration, overridden by CLI arguments
+fn build_scan_config(cli: &Cli, config: &Config) -> ScanConfig {
+    let mut scan_config = ScanConfig::from(config);
+    if let Some(depth) = cli.max_depth {
+        scan_config.max_depth = Some(depth);
+    }
+    // Unlike the library default, the CLI follows symlinks unless told otherwise
+    scan_config.follow_symlinks = !cli.no_symlinks && config.follow_symlinks.unwrap_or(true);
+    scan_config.include_submodules = !cli.no_submodules && scan_config.include_submodules;
+    scan_config
+}
+
+/// Runs `profiles list`
+fn list_profiles(config: &Config) {
+    for (name, profile) in &config.profiles {
+        let roots: Vec<String> = profile
+            .config
+            .roots
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect();
+        let details: Vec<String> = profile
+            .description
+            .iter()
+            .cloned()
+            .chain((!roots.is_empty()).then(|| roots.join(", ")))
+            .collect();
+        if details.is_empty() {
+            println!("{}", name);
+        } else {
+            println!("{}: {}", name, details.join(" - "));
+        }
+    }
+}
+
+/// Runs `tag add`, `tag remove` and `tag list`
+fn run_tag_command(action: &TagAction, path: &Path, localizer: &Localizer) -> Result<()> {
+    let mut store = MetadataStore::load(path)
+        .with_context(|| format!("Failed to load tags and notes from {}", path.display()))?;
+    let key = match action {
+        TagAction::Add { project, tags } => {
+            let key = metadata_key(project)?;
+            store.add_tags(&key, tags);
+            key
+        }
+        TagAction::Remove { project, tags } => {
+            let key = metadata_key(project)?;
+            store.remove_tags(&key, tags);
+            key
+        }
+        TagAction::List { project: None } => {
+            for (tag, count) in store.tag_counts() {
+                println!("{} ({})", tag, count);
+            }
+            return Ok(());
+        }
+        TagAction::List {
+            project: Some(project),
+        } => metadata_key(project)?,
+    };
+
+    if matches!(action, TagAction::Add { .. } | TagAction::Remove { .. }) {
+        store
+            .save(path)
+            .with_context(|| format!("Failed to save tags to {}", path.display()))?;
+    }
+    let tags = store.get(&key).map(|meta| meta.tags.join(", "));
+    match tags.filter(|tags| !tags.is_empty()) {
+        Some(tags) => println!("{}: {}", key, tags),
+        None => println!(
+            "{}: {}",
+            key,
+            display::clean_fluent_string(&localizer.get("tags-none", None))
+        ),
+    }
+    Ok(())
+}
+
+/// Runs `note`: shows the note, or sets or clears it
+fn run_note_command(project: &str, text: Option<&str>, clear: bool, path: &Path) -> Result<()> {
+    let mut store = MetadataStore::load(path)
+        .with_context(|| format!("Failed to load tags and notes from {}", path.display()))?;
+    let key = metadata_key(project)?;
+    if text.is_none() && !clear {
+        if let Some(notes) = store.get(&key).and_then(|meta| meta.notes.as_deref()) {
+            println!("{}", notes);
+        }
+        return Ok(());
+    }
+    store.set_notes(&key, text.map(str::to_string));
+    store
+        .save(path)
+        .with_context(|| format!("Failed to save notes to {}", path.display()))
+}
+
+/// Resolves a repository path or remote identity to its metadata key
+fn metadata_key(project: &str) -> Result<String> {
+    let path = Path::new(project);
+    if path.exists() {
+        return Ok(MetadataStore::key_for_path(path)?);
+    }
+    if let Some(identity) = RemoteIdentity::parse(project) {
+        return Ok(identity.to_string());
+    }
+    if path.is_absolute() {
+        // A repository that no longer exists, e.g. to clean up its tags
+        return Ok(project.to_string());
+    }
+    if project.contains('/') {
+        return Ok(project.to_lowercase());
+    }
+    anyhow::bail!(
+        "'{}' is neither a repository path nor a remote identity (e.g. github.com/acme/tool)",
+        project
+    )
+}
+
+/// Parses a filter expression, pointing at the error column on failure
+fn parse_filter(expr: &str) -> Result<Filter> {
+    Filter::parse(expr).map_err(|e| match e {
+        git_projects_core::Error::InvalidFilter { column, .. } => {
+            anyhow::anyhow!("{}\n  {}\n  {}^", e, expr, " ".repeat(column - 1))
+        }
+        other => other.into(),
+    })
+}
+
+/// Builds the sort specification from --sort-by/--order or the --sort preset,
+/// falling back to the configured sort order
+fn build_sort_spec(cli: &Cli, config: &Config) -> Result<SortSpec> {
+    match (&cli.sort_by, cli.sort, &config.sort) {
+        (Some(keys), _, _) => Ok(SortSpec::parse(keys, cli.order.as_deref())?),
+        (None, Some(profile), _) => Ok(profile.into()),
+        (None, None, Some(sort)) => Ok(sort.clone()),
+        (None, None, None) => Ok(SortProfile::Name.into()),
+    }
+}
+
+/// Determines the output format from --format, or --json as a shorthand
+///
+/// The configured format only applies to plain project lists, since reports,
+/// templates, groups and duplicates support fewer formats.
+fn output_format(cli: &Cli, config: &Config, grouped: bool) -> OutputFormat {
+    let plain_list = matches!(cli.command, None | Some(Command::Scan))
+        && !grouped
+        && cli.template.is_none()
+        && cli.template_file.is_none();
+    match cli.format {
+        Some(format) => format.into(),
+        None if cli.json => OutputFormat::Json,
+        None if plain_list => config.format.unwrap_or_default(),
+        None => OutputFormat::Table,
+    }
+}
+
+/// Rejects output options that cannot be combined
+fn check_output_options(
+    cli: &Cli,
+    format: OutputFormat,
+    has_columns: bool,
+    grouped: bool,
+) -> Result<()> {
+    let has_template = cli.template.is_some() || cli.template_file.is_some();
+    if let Some(Command::Export { kind, .. }) = cli.command {
+        if cli.format.is_some() || cli.json {
+            anyhow::bail!("export takes the format as an argument, e.g. `export csv`");
+        }
+        if grouped || has_template {
+            anyhow::bail!("--group-by and --template are not supported for export");
+        }
+        if has_columns && kind.format().is_none() {
+            anyhow::bail!("--columns is not supported for manifests and workspaces");
+        }
+        return Ok(());
+    }
+    let interactive_command = match cli.command {
+        Some(Command::Tui) => Some("tui"),
+        Some(Command::Pick { .. }) => Some("pick"),
+        _ => None,
+    };
+    if let Some(name) = interactive_command {
+        if format != OutputFormat::Table || grouped || has_columns || has_template {
+            anyhow::bail!(
+                "--format, --group-by, --columns and --template are not supported for {}",
+                name
+            );
+        }
+    }
+    let detail_command = match cli.command {
+        Some(Command::Show { .. }) => Some("show"),
+        Some(Command::Status { .. }) => Some("status"),
+        Some(Command::Audit { .. }) => Some("audit"),
+        Some(Command::Exec { .. }) => Some("exec"),
+        Some(Command::Restore { .. }) => Some("restore"),
+        Some(Command::Check { .. }) => Some("check"),
+        Some(Command::Diff { .. }) => Some("diff"),
+        Some(Command::Db { .. }) => Some("db"),
+        Some(Command::Serve { .. }) => Some("serve"),
+        _ => None,
+    };
+    if let Some(name) = detail_command {
+        if grouped || has_columns || has_template {
+            anyhow::bail!(
+                "--group-by, --columns and --template are not supported for {}",
+                name
+            );
+        }
+        if !matches!(format, OutputFormat::Table | OutputFormat::Json) {
+            anyhow::bail!(
+                "--format {} is not supported for {} (use table or json)",
+                format,
+                name
+            );
+        }
+    }
+    if has_columns && format == OutputFormat::Table {
+        anyhow::bail!("--columns requires --format csv, tsv, ndjson or json");
+    }
+    if cli.template_document && !has_template {
+        anyhow::bail!("--template-document requires --template or --template-file");
+    }
+    if matches!(cli.command, Some(Command::Report { .. }))
+        && (has_columns || has_template || format != OutputFormat::Table)
+    {
+        anyhow::bail!("--format, --columns and --template are not supported for reports");
+    }
+    let structured = grouped || matches!(cli.command, Some(Command::Duplicates));
+    if structured && has_template {
+        anyhow::bail!("--template is not supported for grouped or duplicate output");
+    }
+    if structured && !matches!(format, OutputFormat::Table | OutputFormat::Json) {
+        anyhow::bail!(
+            "--format {} is not supported for grouped or duplicate output (use table or json)",
+            format
+        );
+    }
+    if structured && has_columns {
+        anyhow::bail!("--columns is not supported for grouped or duplicate output");
+    }
+    Ok(())
+}
+
+/// Outputs projects in a machine-readable format to stdout
+fn output_projects(
+    projects: &[GitProject],
+    format: OutputFormat,
+    columns: Option<&[Column]>,
+) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    let result =
+        write_projects(&mut stdout, projects, format, columns).and_then(|()| Ok(stdout.flush()?));
+
+    match result {
+        // The reader went away (e.g. `| head`), which is not an error
+        Err(e) if is_broken_pipe(&e) => Ok(()),
+        other => other,
+    }
+}
+
+/// Writes projects in a machine-readable format
+fn write_projects(
+    out: &mut impl Write,
+    projects: &[GitProject],
+    format: OutputFormat,
+    columns: Option<&[Column]>,
+) -> Result<()> {
+    let table_columns = columns.unwrap_or(Column::DEFAULT);
+    match format {
+        OutputFormat::Json => output::write_json(out, projects, columns),
+        OutputFormat::Ndjson => output::write_ndjson(out, projects, columns),
+        OutputFormat::Csv => output::write_csv(out, projects, table_columns),
+        OutputFormat::Tsv => output::write_tsv(out, projects, table_columns),
+        OutputFormat::Table => unreachable!("tables are rendered by output_table"),
+    }
+    .map_err(anyhow::Error::from)
+}
+
+/// Checks whether an error was caused by writing to a closed pipe
+fn is_broken_pipe(error: &anyhow::Error) -> bool {
+    let io_error = match error.downcast_ref::<git_projects_core::Error>() {
+        Some(git_projects_core::Error::Io(e)) => Some(e),
+        _ => error.downcast_ref::<std::io::Error>(),
+    };
+    io_error.is_some_and(|e| e.kind() == std::io::ErrorKind::BrokenPipe)
+}
+
+/// Outputs a serializable value (projects, duplicate groups) as JSON to stdout
+fn output_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<()> {
+    let json =
+        serde_json::to_string_pretty(value).context("Failed to serialize results to JSON")?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Expands `\t`, `\n` and `\\` in a template given on the command line
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape(r"{{name}}\t{{path}}\n"), "{{name}}\t{{path}}\n");
+        assert_eq!(unescape(r"a\\tb\x\"), r"a\tb\x\");
+    }
+
+    #[test]
+    fn test_sort_by_name() {
+        let mut projects = vec![
+            create_test_project("zebra"),
+            create_test_project("alpha"),
+            create_test_project("beta"),
+        ];
+
+        SortSpec::from(SortProfile::Name).sort(&mut projects);
+
+        assert_eq!(projects[0].name, "alpha");
+        assert_eq!(projects[1].name, "beta");
+        assert_eq!(projects[2].name, "zebra");
+    }
+
+    #[test]
+    fn test_sort_by_path() {
+        let mut projects = vec![
+            create_test_project_with_path("project", "/z/path"),
+            create_test_project_with_path("project", "/a/path"),
+            create_test_project_with_path("project", "/m/path"),
+        ];
+
+        SortSpec::from(SortProfile::Path).sort(&mut projects);
+
+        assert_eq!(projects[0].path, PathBuf::from("/a/path"));
+        assert_eq!(projects[1].path, PathBuf::from("/m/path"));
+        assert_eq!(projects[2].path, PathBuf::from("/z/path"));
+    }
+
+    fn create_test_project(name: &str) -> GitProject {
+        GitProject::test(format!("/test/{}", name))
+    }
+
+    fn create_test_project_with_path(name: &str, path: &str) -> GitProject {
+        GitProject {
+            name: name.to_string(),
+            ..GitProject::test(path)
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Shell integration.
+//!
+//! `init` prints a function that changes to the repository chosen by
+//! `pick`, and completes its argument with the names of the repositories
+//! found by the last scan.
+
+use crate::cli::InitShell;
+
+/// Returns the functions for `shell`, naming the jump function `cmd`
+pub fn init_script(shell: InitShell, cmd: &str, exe: &str) -> String {
+    let template = match shell {
+        InitShell::Bash => BASH,
+        InitShell::Zsh => ZSH,
+        InitShell::Fish => FISH,
+    };
+    template.replace("{cmd}", cmd).replace("{exe}", exe)
+}
+
+/// Whether `cmd` can be used as a function name in all supported shells
+pub fn is_valid_name(cmd: &str) -> bool {
+    let mut chars = cmd.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+const BASH: &str = r#"{cmd}() {
+    local dir
+    dir="$(command {exe} pick "$@")" && [ -n "$dir" ] && cd -- "$dir"
+}
+
+_{cmd}_complete() {
+    local IFS=$'\n'
+    COMPREPLY=($(compgen -W "$(command {exe} pick --list 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}"))
+}
+complete -F _{cmd}_complete {cmd}
+"#;
+
+const ZSH: &str = r#"{cmd}() {
+    local dir
+    dir="$(command {exe} pick "$@")" && [ -n "$dir" ] && cd -- "$dir"
+}
+
+_{cmd}() {
+    local -a names
+    names=("${(@f)$(command {exe} pick --list 2>/dev/null)}")
+    compadd -a names
+}
+(( $+functions[compdef] )) && compdef _{cmd} {cmd}
+"#;
+
+const FISH: &str = r#"function {cmd} --description 'Jump to a Git repository'
+    set -l dir (command {exe} pick $argv); or return
+    test -n "$dir"; and cd -- $dir
+end
+
+complete --command {cmd} --no-files --arguments '(command {exe} pick --list 2>/dev/null)'
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_script() {
+        let script = init_script(InitShell::Bash, "j", "projects-cli");
+        assert!(script.starts_with("j() {"));
+        assert!(script.contains("complete -F _j_complete j"));
+        assert!(!script.contains("{cmd}") && !script.contains("{exe}"));
+
+        for shell in [InitShell::Zsh, InitShell::Fish] {
+            let script = init_script(shell, "p", "projects-cli");
+            assert!(script.contains("command projects-cli pick --list"));
+        }
+    }
+
+    #[test]
+    fn test_valid_names() {
+        assert!(is_valid_name("p"));
+        assert!(is_valid_name("go-to_repo2"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("2p"));
+        assert!(!is_valid_name("p; rm"));
+    }
+}
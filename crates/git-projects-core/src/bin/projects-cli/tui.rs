@@ -0,0 +1,757 @@
+//! Full-screen terminal UI over the scan results.
+//!
+//! [`run`] shows the projects in a table with an incremental fuzzy search
+//! and a detail pane for the selected project. The state lives in [`App`],
+//! which turns key presses into [`Action`]s; [`run`] carries out those that
+//! need the terminal or the scanner, such as opening a shell. Details are
+//! read by a [`DetailsReader`] thread, so that the status walk of a large
+//! repository does not hold up drawing.
+//!
+//! [`pick`] runs the same browser to choose a project. Enter then returns
+//! its path instead of opening a shell, and projects are ranked by
+//! frecency as well as by name. The UI is drawn on stderr, so stdout stays
+//! free for the picked path, as in `cd "$(projects-cli pick)"`.
+
+use crate::display::{self, clean_fluent_string};
+use anyhow::Result;
+use crossterm::clipboard::CopyToClipboard;
+use crossterm::cursor::Show;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use git_projects_core::{
+    filter::Field, fuzzy, l10n::Localizer, project_details, sort, GitProject, PathStyle,
+    ProjectDetails, SortDirection, SortKey, SortSpec,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::{Frame, Terminal};
+use std::collections::HashMap;
+use std::io::Stderr;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+/// How long the event loop waits for a key before checking for details
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The details of a project, or the error reading them
+type Details = std::result::Result<ProjectDetails, String>;
+
+/// A column of the project table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TuiColumn {
+    Name,
+    Branch,
+    Service,
+    Account,
+    Path,
+}
+
+impl TuiColumn {
+    const ALL: [TuiColumn; 5] = [
+        TuiColumn::Name,
+        TuiColumn::Branch,
+        TuiColumn::Service,
+        TuiColumn::Account,
+        TuiColumn::Path,
+    ];
+
+    fn header_key(self) -> &'static str {
+        match self {
+            TuiColumn::Name => "header-name",
+            TuiColumn::Branch => "header-branch",
+            TuiColumn::Service => "header-service",
+            TuiColumn::Account => "header-account",
+            TuiColumn::Path => "header-path",
+        }
+    }
+
+    fn field(self) -> Field {
+        match self {
+            TuiColumn::Name => Field::Name,
+            TuiColumn::Branch => Field::Branch,
+            TuiColumn::Service => Field::Service,
+            TuiColumn::Account => Field::Account,
+            TuiColumn::Path => Field::Path,
+        }
+    }
+
+    fn from_field(field: Field) -> Option<Self> {
+        Self::ALL.into_iter().find(|column| column.field() == field)
+    }
+
+    fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&c| c == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// The text shown in this column for a project
+    fn value(self, project: &GitProject, path_style: PathStyle) -> String {
+        let remote = project.primary_remote();
+        match self {
+            TuiColumn::Name => project.name.clone(),
+            TuiColumn::Branch => project
+                .head
+                .as_ref()
+                .and_then(|head| {
+                    head.branch
+                        .clone()
+                        .or_else(|| head.commit.as_ref().map(|c| c.chars().take(7).collect()))
+                })
+                .unwrap_or_default(),
+            TuiColumn::Service => remote.and_then(|r| r.service.clone()).unwrap_or_default(),
+            TuiColumn::Account => remote.and_then(|r| r.account.clone()).unwrap_or_default(),
+            TuiColumn::Path => project.display_path(path_style),
+        }
+    }
+}
+
+/// What the event loop should do after a key press
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Quit,
+    CopyPath(PathBuf),
+    OpenShell(PathBuf),
+    Reanalyze(PathBuf),
+    Select(PathBuf),
+}
+
+/// The terminal the UI is drawn on
+type Tui = Terminal<CrosstermBackend<Stderr>>;
+
+/// The state of the browser
+struct App {
+    projects: Vec<GitProject>,
+    /// Indices into `projects`, in display order
+    visible: Vec<usize>,
+    query: String,
+    column: TuiColumn,
+    direction: SortDirection,
+    locale: String,
+    table: TableState,
+    /// Details read so far, or the error reading them
+    details: HashMap<PathBuf, Details>,
+    reader: DetailsReader,
+    /// The project whose details are being read
+    requested: Option<PathBuf>,
+    /// Feedback on the last action, shown instead of the key help
+    message: Option<String>,
+    /// Number of table rows on screen, for paging
+    page: usize,
+    /// Whether Enter selects a project instead of opening a shell
+    picking: bool,
+    /// Frecency boost added to the match score, by path
+    boost: HashMap<PathBuf, i64>,
+}
+
+impl App {
+    /// Creates the browser state, starting with the first key of `sort`
+    fn new(projects: Vec<GitProject>, sort: &SortSpec) -> Self {
+        let (column, direction) = match sort.keys().first() {
+            Some(&(SortKey::Field(field), direction)) => match TuiColumn::from_field(field) {
+                Some(column) => (column, direction),
+                None => (TuiColumn::Name, SortDirection::Asc),
+            },
+            _ => (TuiColumn::Name, SortDirection::Asc),
+        };
+        let mut app = App {
+            projects,
+            visible: Vec::new(),
+            query: String::new(),
+            column,
+            direction,
+            locale: sort.locale().to_string(),
+            table: TableState::default(),
+            details: HashMap::new(),
+            reader: DetailsReader::spawn(),
+            requested: None,
+            message: None,
+            page: 10,
+            picking: false,
+            boost: HashMap::new(),
+        };
+        app.refresh(false);
+        app
+    }
+
+    /// Turns the browser into a picker that ranks projects by `boost` too
+    fn picker(mut self, boost: HashMap<PathBuf, i64>) -> Self {
+        self.picking = true;
+        self.boost = boost;
+        self.refresh(false);
+        self
+    }
+
+    /// Recomputes the visible rows after the query or sort order changed
+    ///
+    /// Best matches (including any frecency boost) come first; the sort
+    /// column orders equally good ones.
+    /// With `keep_selection`, the selected project stays selected if it is
+    /// still visible, otherwise the first row is selected.
+    fn refresh(&mut self, keep_selection: bool) {
+        let selected = self
+            .selected()
+            .filter(|_| keep_selection)
+            .map(|p| p.path.clone());
+
+        let spec = SortSpec::new(vec![(SortKey::Field(self.column.field()), self.direction)])
+            .then(SortKey::Field(Field::Name), SortDirection::Asc)
+            .then(SortKey::Field(Field::Path), SortDirection::Asc)
+            .with_locale(&self.locale);
+        let comparator = spec.comparator();
+        let mut scored: Vec<(i64, usize, sort::SortValues)> = self
+            .projects
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| {
+                let boost = self.boost.get(&p.path).copied().unwrap_or(0);
+                fuzzy::score(&self.query, &p.name).map(|s| (s + boost, i, comparator.values(p)))
+            })
+            .collect();
+        scored.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then_with(|| comparator.compare_values(&a.2, &b.2))
+        });
+        self.visible = scored.into_iter().map(|(_, i, _)| i).collect();
+
+        let index = selected
+            .and_then(|path| {
+                self.visible
+                    .iter()
+                    .position(|&i| self.projects[i].path == path)
+            })
+            .or(if self.visible.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+        self.table.select(index);
+    }
+
+    fn selected(&self) -> Option<&GitProject> {
+        let index = *self.visible.get(self.table.selected()?)?;
+        Some(&self.projects[index])
+    }
+
+    /// Starts reading the details of the selected project, unless they
+    /// were read already
+    fn request_details(&mut self) {
+        let Some(project) = self.selected() else {
+            return;
+        };
+        if !self.details.contains_key(&project.path)
+            && self.requested.as_ref() != Some(&project.path)
+        {
+            let project = project.clone();
+            self.requested = Some(project.path.clone());
+            self.reader.request(project);
+        }
+    }
+
+    /// Returns the details of the selected project, or `None` while they
+    /// are read
+    fn selected_details(&self) -> Option<&Details> {
+        self.details.get(&self.selected()?.path)
+    }
+
+    /// Stores the details read since the last call; returns whether there
+    /// were any
+    fn receive_details(&mut self) -> bool {
+        let mut received = false;
+        while let Ok((path, details)) = self.reader.results.try_recv() {
+            if self.requested.as_ref() == Some(&path) {
+                self.requested = None;
+            }
+            self.details.insert(path, details);
+            received = true;
+        }
+        received
+    }
+
+    /// Replaces a project with a fresh analysis of it
+    fn replace(&mut self, project: GitProject) {
+        self.details.remove(&project.path);
+        match self.projects.iter().position(|p| p.path == project.path) {
+            Some(index) => self.projects[index] = project,
+            None => self.projects.push(project),
+        }
+        self.refresh(true);
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let Some(last) = self.visible.len().checked_sub(1) else {
+            return;
+        };
+        let current = self.table.selected().unwrap_or(0);
+        self.table
+            .select(Some(current.saturating_add_signed(delta).min(last)));
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        self.message = None;
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let selected_path = || self.selected().map(|p| p.path.clone());
+        match key.code {
+            KeyCode::Char('c') if ctrl => return Some(Action::Quit),
+            KeyCode::Char('y') if ctrl => return selected_path().map(Action::CopyPath),
+            KeyCode::Char('r') if ctrl => return selected_path().map(Action::Reanalyze),
+            KeyCode::Enter if self.picking => return selected_path().map(Action::Select),
+            KeyCode::Enter => return selected_path().map(Action::OpenShell),
+            KeyCode::Char('u') if ctrl => {
+                self.query.clear();
+                self.refresh(false);
+            }
+            KeyCode::Esc if self.query.is_empty() => return Some(Action::Quit),
+            KeyCode::Esc => {
+                self.query.clear();
+                self.refresh(false);
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.refresh(false);
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refresh(false);
+            }
+            KeyCode::Tab => {
+                self.column = self.column.next();
+                self.refresh(true);
+            }
+            KeyCode::BackTab => {
+                self.direction = match self.direction {
+                    SortDirection::Asc => SortDirection::Desc,
+                    SortDirection::Desc => SortDirection::Asc,
+                };
+                self.refresh(true);
+            }
+            KeyCode::Up => self.move_by(-1),
+            KeyCode::Down => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-(self.page as isize)),
+            KeyCode::PageDown => self.move_by(self.page as isize),
+            KeyCode::Home => self.move_by(isize::MIN),
+            KeyCode::End => self.move_by(isize::MAX),
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Runs the browser until the user quits
+///
+/// `rescan` analyzes a single repository again, for re-analyzing an entry.
+pub fn run(
+    projects: Vec<GitProject>,
+    sort: &SortSpec,
+    path_style: PathStyle,
+    localizer: &Localizer,
+    rescan: impl Fn(&Path) -> Result<GitProject>,
+) -> Result<()> {
+    let mut app = App::new(projects, sort);
+    session(&mut app, path_style, localizer, &rescan)?;
+    Ok(())
+}
+
+/// Reads project details on a background thread
+///
+/// Requests made while one is being read are skipped but the last, so
+/// scrolling through the list only reads the project it stops at.
+struct DetailsReader {
+    requests: Sender<GitProject>,
+    results: Receiver<(PathBuf, Details)>,
+}
+
+impl DetailsReader {
+    fn spawn() -> Self {
+        let (requests, pending) = mpsc::channel::<GitProject>();
+        let (sender, results) = mpsc::channel();
+        std::thread::spawn(move || {
+            while let Ok(project) = pending.recv() {
+                let project = pending.try_iter().last().unwrap_or(project);
+                let path = project.path.clone();
+                let details = project_details(project).map_err(|e| e.to_string());
+                if sender.send((path, details)).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { requests, results }
+    }
+
+    fn request(&self, project: GitProject) {
+        // The thread only stops when the reader is dropped
+        let _ = self.requests.send(project);
+    }
+}
+
+/// Runs the browser as a picker and returns the chosen project's path
+///
+/// Returns `None` if the user quits without choosing.
+pub fn pick(
+    projects: Vec<GitProject>,
+    boost: HashMap<PathBuf, i64>,
+    sort: &SortSpec,
+    path_style: PathStyle,
+    localizer: &Localizer,
+    rescan: impl Fn(&Path) -> Result<GitProject>,
+) -> Result<Option<PathBuf>> {
+    let mut app = App::new(projects, sort).picker(boost);
+    session(&mut app, path_style, localizer, &rescan)
+}
+
+/// Sets up the terminal, runs the event loop and restores the terminal,
+/// also on panics
+fn session(
+    app: &mut App,
+    path_style: PathStyle,
+    localizer: &Localizer,
+    rescan: &dyn Fn(&Path) -> Result<GitProject>,
+) -> Result<Option<PathBuf>> {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        leave_terminal();
+        hook(info);
+    }));
+
+    let result = enter_terminal()
+        .and_then(|mut terminal| event_loop(&mut terminal, app, path_style, localizer, rescan));
+    leave_terminal();
+    // Back to the default hook, which the one above wraps
+    let _ = std::panic::take_hook();
+    result
+}
+
+/// Switches stderr to the alternate screen in raw mode
+fn enter_terminal() -> Result<Tui> {
+    enable_raw_mode()?;
+    crossterm::execute!(std::io::stderr(), EnterAlternateScreen)?;
+    Ok(Terminal::new(CrosstermBackend::new(std::io::stderr()))?)
+}
+
+/// Undoes [`enter_terminal`]; errors are ignored as there is nothing left to do
+fn leave_terminal() {
+    let _ = disable_raw_mode();
+    let _ = crossterm::execute!(std::io::stderr(), LeaveAlternateScreen, Show);
+}
+
+fn event_loop(
+    terminal: &mut Tui,
+    app: &mut App,
+    path_style: PathStyle,
+    localizer: &Localizer,
+    rescan: &dyn Fn(&Path) -> Result<GitProject>,
+) -> Result<Option<PathBuf>> {
+    let message =
+        |key: &str, args: &[(&str, &str)]| clean_fluent_string(&localizer.get(key, Some(args)));
+
+    let mut redraw = true;
+    loop {
+        redraw |= app.receive_details();
+        if redraw {
+            terminal.draw(|frame| draw(frame, app, path_style, localizer))?;
+        }
+        redraw = event::poll(POLL_INTERVAL)?;
+        if !redraw {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match app.handle_key(key) {
+            None => {}
+            Some(Action::Quit) => return Ok(None),
+            Some(Action::Select(path)) => return Ok(Some(path)),
+            Some(Action::CopyPath(path)) => {
+                let path = path.display().to_string();
+                crossterm::execute!(
+                    std::io::stderr(),
+                    CopyToClipboard::to_clipboard_from(path.as_str())
+                )?;
+                app.message = Some(message("tui-copied", &[("path", &path)]));
+            }
+            Some(Action::OpenShell(path)) => {
+                leave_terminal();
+                let path_str = path.display().to_string();
+                eprintln!("{}", message("tui-shell", &[("path", &path_str)]));
+                let status = open_shell(&path);
+                *terminal = enter_terminal()?;
+                if let Err(e) = status {
+                    let error = e.to_string();
+                    app.message = Some(message("tui-shell-failed", &[("error", &error)]));
+                }
+            }
+            Some(Action::Reanalyze(path)) => match rescan(&path) {
+                Ok(project) => {
+                    let name = project.name.clone();
+                    app.replace(project);
+                    app.message = Some(message("tui-reanalyzed", &[("name", &name)]));
+                }
+                Err(e) => {
+                    let path = path.display().to_string();
+                    let error = format!("{:#}", e);
+                    app.message = Some(message(
+                        "tui-reanalyze-failed",
+                        &[("path", &path), ("error", &error)],
+                    ));
+                }
+            },
+        }
+    }
+}
+
+/// Runs the user's shell in `path` and waits for it to exit
+fn open_shell(path: &Path) -> std::io::Result<()> {
+    let shell = std::env::var_os("SHELL")
+        .or_else(|| std::env::var_os("COMSPEC"))
+        .unwrap_or_else(|| if cfg!(windows) { "cmd" } else { "sh" }.into());
+    std::process::Command::new(shell)
+        .current_dir(path)
+        .status()
+        .map(|_| ())
+}
+
+fn draw(frame: &mut Frame, app: &mut App, path_style: PathStyle, localizer: &Localizer) {
+    let label = |key: &str| clean_fluent_string(&localizer.get(key, None));
+    let bold = Style::new().add_modifier(Modifier::BOLD);
+    let dim = Style::new().add_modifier(Modifier::DIM);
+
+    let [search_area, body, status_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [list_area, detail_area] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(body);
+
+    // Search line with the number of matches
+    let shown = app.visible.len().to_string();
+    let total = app.projects.len().to_string();
+    let count = clean_fluent_string(
+        &localizer.get("tui-count", Some(&[("shown", &shown), ("total", &total)])),
+    );
+    let prompt = format!("{}: ", label("tui-search"));
+    let cursor = (prompt.chars().count() + app.query.chars().count()) as u16;
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(prompt, bold),
+            Span::raw(app.query.clone()),
+            Span::raw("  "),
+            Span::styled(count, dim),
+        ])),
+        search_area,
+    );
+    frame.set_cursor_position((search_area.x + cursor, search_area.y));
+
+    // Project table, with an arrow on the sort column
+    let header = Row::new(TuiColumn::ALL.map(|column| {
+        let mut text = label(column.header_key());
+        if column == app.column {
+            text.push_str(match app.direction {
+                SortDirection::Asc => " ▲",
+                SortDirection::Desc => " ▼",
+            });
+        }
+        Cell::from(text)
+    }))
+    .style(bold);
+    let rows = app.visible.iter().map(|&index| {
+        let project = &app.projects[index];
+        Row::new(TuiColumn::ALL.map(|column| column.value(project, path_style)))
+    });
+    let widths = [
+        Constraint::Percentage(22),
+        Constraint::Percentage(14),
+        Constraint::Percentage(12),
+        Constraint::Percentage(14),
+        Constraint::Fill(1),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::bordered())
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(table, list_area, &mut app.table);
+    // Borders and header
+    app.page = usize::from(list_area.height.saturating_sub(3)).max(1);
+
+    // Detail pane, with the same lines as `show`
+    app.request_details();
+    let lines = match app.selected_details() {
+        Some(Ok(details)) => {
+            let rows = display::show_rows(details, path_style, localizer);
+            let width = rows
+                .iter()
+                .map(|(label, _)| label.chars().count() + 1)
+                .max()
+                .unwrap_or(0);
+            let mut lines = Vec::new();
+            for (label, values) in rows {
+                for (index, value) in values.into_iter().enumerate() {
+                    let label = if index == 0 {
+                        format!("{}:", label)
+                    } else {
+                        String::new()
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("{:<width$}  ", label, width = width), bold),
+                        Span::raw(value),
+                    ]));
+                }
+            }
+            lines
+        }
+        Some(Err(error)) => vec![Line::raw(error.clone())],
+        None if app.requested.is_some() => vec![Line::styled(label("tui-details-loading"), dim)],
+        None => Vec::new(),
+    };
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::bordered().title(label("tui-details")))
+            .wrap(Wrap { trim: false }),
+        detail_area,
+    );
+
+    let help = if app.picking {
+        "tui-help-pick"
+    } else {
+        "tui-help"
+    };
+    let status = app.message.clone().unwrap_or_else(|| label(help));
+    frame.render_widget(Paragraph::new(status).style(dim), status_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(path: &str) -> GitProject {
+        GitProject::test(path)
+    }
+
+    fn app() -> App {
+        let projects = vec![
+            project("/src/tool"),
+            project("/src/git-projects-core"),
+            project("/src/git-projects"),
+        ];
+        App::new(projects, &SortSpec::parse("name", None).unwrap())
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn names(app: &App) -> Vec<&str> {
+        app.visible
+            .iter()
+            .map(|&i| app.projects[i].name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_search_and_sort() {
+        let mut app = app();
+        assert_eq!(names(&app), ["git-projects", "git-projects-core", "tool"]);
+
+        app.handle_key(key(KeyCode::BackTab));
+        assert_eq!(names(&app), ["tool", "git-projects-core", "git-projects"]);
+
+        // Best matches first, whatever the sort order
+        for c in "gpc".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(names(&app), ["git-projects-core", "git-projects"]);
+        app.handle_key(key(KeyCode::Char('o')));
+        assert_eq!(names(&app), ["git-projects-core"]);
+        app.handle_key(key(KeyCode::Backspace));
+        assert_eq!(names(&app).len(), 2);
+
+        // Esc clears the query before quitting
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), None);
+        assert_eq!(names(&app).len(), 3);
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), Some(Action::Quit));
+    }
+
+    #[test]
+    fn test_selection_and_actions() {
+        let mut app = app();
+        app.handle_key(key(KeyCode::Down));
+        let core = PathBuf::from("/src/git-projects-core");
+        assert_eq!(app.selected().unwrap().path, core);
+
+        // The selection follows the project when the order changes
+        app.handle_key(key(KeyCode::BackTab));
+        assert_eq!(app.selected().unwrap().path, core);
+        app.handle_key(key(KeyCode::End));
+        assert_eq!(app.selected().unwrap().name, "git-projects");
+        app.handle_key(key(KeyCode::Home));
+        assert_eq!(app.selected().unwrap().name, "tool");
+
+        assert_eq!(
+            app.handle_key(key(KeyCode::Enter)),
+            Some(Action::OpenShell(PathBuf::from("/src/tool")))
+        );
+        assert_eq!(
+            app.handle_key(ctrl('y')),
+            Some(Action::CopyPath(PathBuf::from("/src/tool")))
+        );
+        assert_eq!(
+            app.handle_key(ctrl('r')),
+            Some(Action::Reanalyze(PathBuf::from("/src/tool")))
+        );
+
+        let mut renamed = project("/src/tool");
+        renamed.name = "a-tool".to_string();
+        app.replace(renamed);
+        assert_eq!(app.selected().unwrap().name, "a-tool");
+        assert_eq!(app.projects.len(), 3);
+
+        // Nothing to act on without matches
+        app.handle_key(key(KeyCode::Char('z')));
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), None);
+    }
+
+    #[test]
+    fn test_details_are_read_in_background() {
+        let mut app = app();
+        app.request_details();
+        assert_eq!(app.requested, Some(PathBuf::from("/src/git-projects")));
+        assert!(app.selected_details().is_none());
+
+        // Not a repository, so reading fails, but off the render path
+        while app.selected_details().is_none() {
+            app.receive_details();
+            std::thread::sleep(POLL_INTERVAL);
+        }
+        assert!(app.selected_details().unwrap().is_err());
+        assert_eq!(app.requested, None);
+
+        // Details are read once
+        app.request_details();
+        assert_eq!(app.requested, None);
+    }
+
+    #[test]
+    fn test_picker() {
+        let boost = HashMap::from([(PathBuf::from("/src/tool"), 40)]);
+        let mut app = app().picker(boost);
+        assert_eq!(names(&app), ["tool", "git-projects", "git-projects-core"]);
+        assert_eq!(
+            app.handle_key(key(KeyCode::Enter)),
+            Some(Action::Select(PathBuf::from("/src/tool")))
+        );
+    }
+}
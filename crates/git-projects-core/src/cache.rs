@@ -0,0 +1,135 @@
+//! Cache of the last scan.
+//!
+//! Scanning a large tree takes a moment, too long for shell completion or
+//! for jumping to a project. A [`ProjectCache`] keeps the results of the last
+//! full scan in the cache directory, `~/.cache/git-projects/projects.json`,
+//! together with the [`ScanConfig`]s that produced them, so they are only
+//! reused for the same roots and settings.
+//!
+//! The projects are cached as the scanner found them. Host mappings, rules,
+//! tags and notes change more often than the repositories, so they are
+//! applied again after loading.
+
+use crate::error::Result;
+use crate::models::{GitProject, ScanConfig};
+use crate::schema::SCHEMA_VERSION;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Name of the cache file in the cache directory.
+pub const CACHE_FILE: &str = "projects.json";
+
+/// The projects found by a scan.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectCache {
+    /// The [schema version](crate::SCHEMA_VERSION) of the projects; caches
+    /// written by other versions are ignored.
+    pub schema_version: String,
+
+    /// When the scan ran.
+    pub created: DateTime<Utc>,
+
+    /// The settings of the scan, one per root.
+    pub scan_configs: Vec<ScanConfig>,
+
+    /// The projects found, before the configuration and metadata are
+    /// applied.
+    pub projects: Vec<GitProject>,
+}
+
+impl ProjectCache {
+    /// Creates a cache of the projects found with `scan_configs`.
+    pub fn new(scan_configs: &[ScanConfig], projects: Vec<GitProject>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION.to_string(),
+            created: Utc::now(),
+            scan_configs: scan_configs.to_vec(),
+            projects,
+        }
+    }
+
+    /// Returns the path of the cache file in the cache directory.
+    ///
+    /// `$XDG_CACHE_HOME/git-projects`, or `~/.cache/git-projects`.
+    pub fn default_path() -> Option<PathBuf> {
+        crate::config::cache_dir().map(|dir| dir.join(CACHE_FILE))
+    }
+
+    /// Loads the cache from a file.
+    ///
+    /// Returns `None` if the file does not exist or was written by another
+    /// schema version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`](crate::Error::Io) if the file cannot be read, or
+    /// [`Error::Json`](crate::Error::Json) if it is malformed.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let source = std::fs::read_to_string(path)?;
+        let version: serde_json::Value = serde_json::from_str(&source)?;
+        if version.get("schema_version").and_then(|v| v.as_str()) != Some(SCHEMA_VERSION) {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&source)?))
+    }
+
+    /// Writes the cache to a file, replacing it atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`](crate::Error::Io) if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let source = serde_json::to_string(self)?;
+        crate::config::write_atomic(path, &source)?;
+        Ok(())
+    }
+
+    /// Whether the cache was produced with the same settings as
+    /// `scan_configs`.
+    pub fn matches(&self, scan_configs: &[ScanConfig]) -> bool {
+        self.scan_configs == scan_configs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_configs(root: &str) -> Vec<ScanConfig> {
+        vec![ScanConfig {
+            root_paths: vec![PathBuf::from(root)],
+            ..ScanConfig::default()
+        }]
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join(CACHE_FILE);
+        assert_eq!(ProjectCache::load(&path).unwrap(), None);
+
+        let cache = ProjectCache::new(&scan_configs("/src"), vec![]);
+        cache.save(&path).unwrap();
+        let loaded = ProjectCache::load(&path).unwrap().unwrap();
+        assert_eq!(loaded, cache);
+        assert!(loaded.matches(&scan_configs("/src")));
+        assert!(!loaded.matches(&scan_configs("/tmp")));
+    }
+
+    #[test]
+    fn test_other_schema_version_is_ignored() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join(CACHE_FILE);
+        let mut cache = ProjectCache::new(&scan_configs("/src"), vec![]);
+        cache.schema_version = "0.1.0".to_string();
+        cache.save(&path).unwrap();
+        assert_eq!(ProjectCache::load(&path).unwrap(), None);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(ProjectCache::load(&path).is_err());
+    }
+}
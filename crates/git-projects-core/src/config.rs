@@ -0,0 +1,1055 @@
+//! Configuration files.
+//!
+//! Persistent settings are read from TOML files:
+//!
+//! - the global file, `~/.config/git-projects/config.toml` (or
+//!   `$XDG_CONFIG_HOME/git-projects/config.toml`), see [`Config::default_path`]
+//! - an optional [`ROOT_CONFIG_FILE`] (`.git-projects.toml`) in each scan root
+//!
+//! The scan settings of a root file apply to that root only, over those of
+//! the global file and the selected [profile](Profile), if any; its other
+//! settings are merged over them. [`Config`] only holds what the files set;
+//! command-line options take precedence over it, and anything set nowhere
+//! falls back to the defaults of [`ScanConfig`].
+//!
+//! # Format
+//!
+//! All sections and keys are optional:
+//!
+//! ```toml
+//! [scan]
+//! roots = ["~/src", "~/work"]   # global file only
+//! depth = 4
+//! follow_symlinks = false
+//! include_submodules = true
+//! ignore = ["node_modules", "**/vendor", "archive/*"]
+//!
+//! [output]
+//! sort = "service,account,name" # keys as for --sort-by
+//! order = "asc,asc,desc"        # directions as for --order
+//! format = "table"              # table, json, ndjson, csv or tsv
+//! locale = "de"
+//!
+//! # Service names for self-hosted platforms
+//! [hosts]
+//! "git.example.com" = "gitea"
+//!
+//! # Per-project settings, keyed by remote identity, name or path
+//! [projects."github.com/acme/tool"]
+//! tags = ["client-x", "oncall"]
+//!
+//! [projects."~/src/old-prototype"]
+//! ignore = true
+//!
+//! # Named profiles (global file only), see Config::with_profile
+//! [profiles.work]
+//! description = "Work repositories"
+//! roots = ["~/work"]
+//! depth = 4
+//! ignore = ["archive"]              # added to scan.ignore
+//! filter = 'service == "github"'    # as --filter
+//! sort = "name"                     # order and format as in [output]
+//! ```
+//!
+//! Ignore patterns follow [`ScanConfig::ignore_patterns`]; in a root file
+//! they are relative to that root only. Relative paths (in `roots` and
+//! project keys starting with `./`) are resolved against the directory of
+//! the file, and `~` is expanded to the home directory.
+//!
+//! Errors point at the offending value with file, line and column.
+
+use crate::error::{Error, Result};
+use crate::filter::Filter;
+use crate::models::{GitProject, ScanConfig};
+use crate::output::OutputFormat;
+use crate::sort::SortSpec;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use toml::Spanned;
+
+/// Name of the configuration file read from each scan root.
+pub const ROOT_CONFIG_FILE: &str = ".git-projects.toml";
+
+/// Settings read from one or more configuration files.
+///
+/// Unset values are `None` (or empty), so the caller can tell them apart from
+/// explicit settings when merging with command-line options.
+///
+/// # Example
+///
+/// ```
+/// use git_projects_core::{Config, ScanConfig};
+/// use std::path::Path;
+///
+/// let config = Config::parse(
+///     "[scan]\ndepth = 5\nignore = [\"node_modules\"]\n",
+///     Path::new("config.toml"),
+/// )?;
+/// let scan = ScanConfig::from(&config);
+/// assert_eq!(scan.max_depth, Some(5));
+/// assert_eq!(scan.ignore_patterns, ["node_modules"]);
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// Default root directories to scan.
+    pub roots: Vec<PathBuf>,
+
+    /// Maximum recursion depth.
+    pub max_depth: Option<usize>,
+
+    /// Whether to follow symbolic links.
+    pub follow_symlinks: Option<bool>,
+
+    /// Whether to include submodules.
+    pub include_submodules: Option<bool>,
+
+    /// Glob patterns for directories to skip.
+    pub ignore_patterns: Vec<String>,
+
+    /// Sort order of the results.
+    pub sort: Option<SortSpec>,
+
+    /// Output format for project lists.
+    pub format: Option<OutputFormat>,
+
+    /// Locale for messages and sorting.
+    pub locale: Option<String>,
+
+    /// Filter applied to the results.
+    ///
+    /// Only set by [profiles](Profile); combined with `--filter`.
+    pub filter: Option<Filter>,
+
+    /// Service names by host, for platforms not recognized from the URL.
+    pub hosts: BTreeMap<String, String>,
+
+    /// Per-project settings, ordered by file, then by key.
+    pub projects: Vec<ProjectRule>,
+
+    /// Named profiles, selectable with [`Config::with_profile`].
+    pub profiles: BTreeMap<String, Profile>,
+
+    /// The scan settings of the [`ROOT_CONFIG_FILE`] of each root that has
+    /// one, see [`Config::scan_config_for`].
+    pub root_configs: BTreeMap<PathBuf, Config>,
+
+    /// The files the settings were read from, in merge order.
+    pub sources: Vec<PathBuf>,
+}
+
+/// A named set of settings, merged over the configuration when selected.
+///
+/// Profiles bundle the scan settings, a filter, a sort order and an output
+/// format for one context, for example `projects-cli --profile work`.
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    /// Short description shown by `profiles list`.
+    pub description: Option<String>,
+
+    /// The settings of the profile.
+    ///
+    /// Only scan and output settings and [`Config::filter`] are set.
+    pub config: Config,
+}
+
+/// Settings for the projects matched by a [`ProjectSelector`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectRule {
+    /// The projects the rule applies to.
+    pub selector: ProjectSelector,
+
+    /// Tags to add to the matched projects.
+    pub tags: Vec<String>,
+
+    /// Whether to drop the matched projects from the results.
+    ///
+    /// `None` keeps the decision of earlier rules.
+    pub ignore: Option<bool>,
+}
+
+/// Selects projects for a [`ProjectRule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectSelector {
+    /// The project at this path.
+    Path(PathBuf),
+    /// Projects whose primary remote identity (`github.com/acme/tool`) or
+    /// name is this.
+    Name(String),
+}
+
+impl ProjectSelector {
+    /// Parses a `[projects."..."]` key.
+    ///
+    /// Keys starting with `/`, `~` or `.` are paths; relative paths are
+    /// resolved against `base`.
+    fn parse(key: &str, base: &Path) -> Self {
+        if key.starts_with(['/', '~', '.']) {
+            ProjectSelector::Path(expand_path(key, base))
+        } else {
+            ProjectSelector::Name(key.to_string())
+        }
+    }
+
+    /// Checks whether `project` is selected.
+    pub fn matches(&self, project: &GitProject) -> bool {
+        match self {
+            ProjectSelector::Path(path) => project.path == *path,
+            ProjectSelector::Name(name) => {
+                project.name == *name
+                    || project
+                        .identity()
+                        .is_some_and(|id| id.to_string().eq_ignore_ascii_case(name))
+            }
+        }
+    }
+}
+
+impl Config {
+    /// Returns the path of the global configuration file.
+    ///
+    /// This is `$XDG_CONFIG_HOME/git-projects/config.toml` if the variable is
+    /// set, and `~/.config/git-projects/config.toml` otherwise (on all
+    /// platforms). `None` if the home directory cannot be determined.
+    pub fn default_path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    /// Loads the global configuration file, if it exists.
+    ///
+    /// Returns an empty configuration if there is no file at
+    /// [`Config::default_path`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file exists but cannot be read, or
+    /// [`Error::Config`] if it is invalid.
+    pub fn load_default() -> Result<Self> {
+        match Self::default_path() {
+            Some(path) if path.is_file() => Self::load(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Loads a global configuration file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read, or [`Error::Config`]
+    /// if it is invalid.
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        Self::parse(&source, path)
+    }
+
+    /// Parses the contents of a global configuration file.
+    ///
+    /// `path` is used in error messages and to resolve relative paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] for syntax errors, unknown keys and invalid
+    /// values.
+    pub fn parse(source: &str, path: &Path) -> Result<Self> {
+        Self::parse_file(source, path, None)
+    }
+
+    /// Reads the [`ROOT_CONFIG_FILE`] of each root that has one.
+    ///
+    /// Its scan settings are kept in [`Config::root_configs`], for that root
+    /// only; everything else is merged over this configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if a root file cannot be read, or
+    /// [`Error::Config`] if it is invalid (for example, because it sets
+    /// `scan.roots`).
+    pub fn with_roots(mut self, roots: &[PathBuf]) -> Result<Self> {
+        for root in roots {
+            let path = root.join(ROOT_CONFIG_FILE);
+            if path.is_file() {
+                let source = std::fs::read_to_string(&path)?;
+                let mut file = Self::parse_file(&source, &path, Some(root))?;
+                let scan = Config {
+                    max_depth: file.max_depth.take(),
+                    follow_symlinks: file.follow_symlinks.take(),
+                    include_submodules: file.include_submodules.take(),
+                    ignore_patterns: std::mem::take(&mut file.ignore_patterns),
+                    ..Config::default()
+                };
+                self.merge(file);
+                self.root_configs.insert(root.clone(), scan);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns the profile called `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownProfile`] if there is no such profile.
+    pub fn profile(&self, name: &str) -> Result<&Profile> {
+        self.profiles.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            if known.is_empty() {
+                Error::unknown_profile(format!("{} (no profiles are defined)", name))
+            } else {
+                Error::unknown_profile(format!("{} (known: {})", name, known.join(", ")))
+            }
+        })
+    }
+
+    /// Merges the profile called `name` over this configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownProfile`] if there is no such profile.
+    pub fn with_profile(mut self, name: &str) -> Result<Self> {
+        let profile = self.profile(name)?.config.clone();
+        self.merge(profile);
+        Ok(self)
+    }
+
+    /// Merges `other` over this configuration.
+    ///
+    /// Values set in `other` replace those set here; ignore patterns, host
+    /// mappings, project rules and profiles are combined.
+    pub fn merge(&mut self, other: Config) {
+        if !other.roots.is_empty() {
+            self.roots = other.roots;
+        }
+        self.max_depth = other.max_depth.or(self.max_depth);
+        self.follow_symlinks = other.follow_symlinks.or(self.follow_symlinks);
+        self.include_submodules = other.include_submodules.or(self.include_submodules);
+        self.ignore_patterns.extend(other.ignore_patterns);
+        self.sort = other.sort.or(self.sort.take());
+        self.format = other.format.or(self.format);
+        self.locale = other.locale.or(self.locale.take());
+        self.filter = other.filter.or(self.filter.take());
+        self.hosts.extend(other.hosts);
+        self.projects.extend(other.projects);
+        self.profiles.extend(other.profiles);
+        self.root_configs.extend(other.root_configs);
+        self.sources.extend(other.sources);
+    }
+
+    /// Returns the settings for scanning `root`: `base`, with the scan
+    /// settings of the root's file over it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use git_projects_core::{Config, ScanConfig};
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let mut config = Config::default();
+    /// let overlay = Config { max_depth: Some(6), ..Config::default() };
+    /// config.root_configs.insert(PathBuf::from("/deep"), overlay);
+    ///
+    /// let base = ScanConfig::from(&config);
+    /// assert_eq!(config.scan_config_for(Path::new("/deep"), &base).max_depth, Some(6));
+    /// assert_eq!(config.scan_config_for(Path::new("/flat"), &base).max_depth, Some(3));
+    /// ```
+    pub fn scan_config_for(&self, root: &Path, base: &ScanConfig) -> ScanConfig {
+        let mut scan = ScanConfig {
+            root_paths: vec![root.to_path_buf()],
+            ..base.clone()
+        };
+        if let Some(overlay) = self.root_configs.get(root) {
+            scan.max_depth = overlay.max_depth.or(scan.max_depth);
+            scan.follow_symlinks = overlay.follow_symlinks.unwrap_or(scan.follow_symlinks);
+            scan.include_submodules = overlay
+                .include_submodules
+                .unwrap_or(scan.include_submodules);
+            scan.ignore_patterns
+                .extend(overlay.ignore_patterns.iter().cloned());
+        }
+        scan
+    }
+
+    /// Applies host mappings and project rules to scan results.
+    ///
+    /// Remotes on a mapped host get its service name, matched projects get
+    /// the tags of their rules, and ignored projects are removed.
+    pub fn apply(&self, projects: &mut Vec<GitProject>) {
+        projects.retain_mut(|project| {
+            for remote in &mut project.remotes {
+                let mapped = crate::identity::RemoteIdentity::parse(&remote.url)
+                    .and_then(|id| self.hosts.get(&id.host));
+                if let Some(service) = mapped {
+                    remote.service = Some(service.clone());
+                }
+            }
+
+            let rules: Vec<&ProjectRule> = self
+                .projects
+                .iter()
+                .filter(|rule| rule.selector.matches(project))
+                .collect();
+            let mut ignore = false;
+            for rule in rules {
+                for tag in &rule.tags {
+                    if !project.tags.contains(tag) {
+                        project.tags.push(tag.clone());
+                    }
+                }
+                ignore = rule.ignore.unwrap_or(ignore);
+            }
+            !ignore
+        });
+    }
+
+    /// Parses a configuration file; `root` is set for root files.
+    fn parse_file(source: &str, path: &Path, root: Option<&Path>) -> Result<Self> {
+        let error = |span: Option<Range<usize>>, message: &str| {
+            let (line, column) = location(source, span.map_or(0, |s| s.start));
+            Error::config(path, line, column, message)
+        };
+        let raw: RawConfig = toml::from_str(source).map_err(|e| error(e.span(), e.message()))?;
+        let base = root
+            .or_else(|| path.parent())
+            .unwrap_or_else(|| Path::new(""));
+
+        let roots = match (raw.scan.roots, root) {
+            (Some(roots), Some(_)) => {
+                return Err(error(
+                    Some(roots.span()),
+                    "scan.roots is only allowed in the global configuration file",
+                ))
+            }
+            (Some(roots), None) => roots
+                .into_inner()
+                .iter()
+                .map(|r| expand_path(r, base))
+                .collect(),
+            (None, _) => Vec::new(),
+        };
+
+        let ignore_patterns = parse_ignore(raw.scan.ignore, root, &error)?;
+        let sort = parse_sort(raw.output.sort, raw.output.order, "output.", &error)?;
+        let format = parse_format(raw.output.format, &error)?;
+
+        let hosts = raw
+            .hosts
+            .into_iter()
+            .map(|(host, service)| (host.to_lowercase(), service))
+            .collect();
+
+        let projects = raw
+            .projects
+            .into_iter()
+            .map(|(key, settings)| ProjectRule {
+                selector: ProjectSelector::parse(&key, base),
+                tags: settings.tags,
+                ignore: settings.ignore,
+            })
+            .collect();
+
+        let profiles = match (raw.profiles, root) {
+            (Some(profiles), Some(_)) => {
+                return Err(error(
+                    Some(profiles.span()),
+                    "profiles are only allowed in the global configuration file",
+                ))
+            }
+            (Some(profiles), None) => profiles
+                .into_inner()
+                .into_iter()
+                .map(|(name, profile)| {
+                    let profile = Self::parse_profile(profile, source, base, &error)?;
+                    Ok((name, profile))
+                })
+                .collect::<Result<_>>()?,
+            (None, _) => BTreeMap::new(),
+        };
+
+        Ok(Self {
+            roots,
+            max_depth: raw.scan.depth,
+            follow_symlinks: raw.scan.follow_symlinks,
+            include_submodules: raw.scan.include_submodules,
+            ignore_patterns,
+            sort,
+            format,
+            locale: raw.output.locale,
+            filter: None,
+            hosts,
+            projects,
+            profiles,
+            root_configs: BTreeMap::new(),
+            sources: vec![path.to_path_buf()],
+        })
+    }
+
+    /// Validates a `[profiles.<name>]` table.
+    fn parse_profile(
+        raw: RawProfile,
+        source: &str,
+        base: &Path,
+        error: &impl Fn(Option<Range<usize>>, &str) -> Error,
+    ) -> Result<Profile> {
+        let filter = raw
+            .filter
+            .map(|filter| {
+                Filter::parse(filter.get_ref()).map_err(|e| match e {
+                    Error::InvalidFilter { column, message } => {
+                        // Point into the string, after the opening quote
+                        let start = filter.span().start + 1;
+                        let offset = filter
+                            .get_ref()
+                            .char_indices()
+                            .nth(column - 1)
+                            .map_or(filter.get_ref().len(), |(i, _)| i);
+                        let at = (start + offset).min(source.len());
+                        error(Some(at..at), &message)
+                    }
+                    e => error(Some(filter.span()), &e.to_string()),
+                })
+            })
+            .transpose()?;
+
+        Ok(Profile {
+            description: raw.description,
+            config: Config {
+                roots: raw.roots.iter().map(|r| expand_path(r, base)).collect(),
+                max_depth: raw.depth,
+                follow_symlinks: raw.follow_symlinks,
+                include_submodules: raw.include_submodules,
+                ignore_patterns: parse_ignore(raw.ignore, None, error)?,
+                sort: parse_sort(raw.sort, raw.order, "", error)?,
+                format: parse_format(raw.format, error)?,
+                filter,
+                ..Config::default()
+            },
+        })
+    }
+}
+
+/// Validates ignore patterns, anchoring those of root files.
+fn parse_ignore(
+    patterns: Vec<Spanned<String>>,
+    root: Option<&Path>,
+    error: &impl Fn(Option<Range<usize>>, &str) -> Error,
+) -> Result<Vec<String>> {
+    patterns
+        .into_iter()
+        .map(|pattern| {
+            globset::Glob::new(pattern.get_ref())
+                .map_err(|e| error(Some(pattern.span()), &e.kind().to_string()))?;
+            Ok(match root {
+                Some(root) => anchor_pattern(pattern.get_ref(), root),
+                None => pattern.into_inner(),
+            })
+        })
+        .collect()
+}
+
+/// Validates sort keys and directions; `table` prefixes the key names in
+/// error messages.
+fn parse_sort(
+    keys: Option<Spanned<String>>,
+    order: Option<Spanned<String>>,
+    table: &str,
+    error: &impl Fn(Option<Range<usize>>, &str) -> Error,
+) -> Result<Option<SortSpec>> {
+    match (keys, order) {
+        (Some(keys), order) => {
+            SortSpec::parse(keys.get_ref(), order.as_ref().map(|o| o.get_ref().as_str()))
+                .map(Some)
+                .map_err(|e| {
+                    // Point at the order if the keys alone are fine
+                    let span = match &order {
+                        Some(order) if SortSpec::parse(keys.get_ref(), None).is_ok() => {
+                            order.span()
+                        }
+                        _ => keys.span(),
+                    };
+                    error(Some(span), &e.to_string())
+                })
+        }
+        (None, Some(order)) => Err(error(
+            Some(order.span()),
+            &format!("{}order requires {}sort", table, table),
+        )),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Validates an output format.
+fn parse_format(
+    format: Option<Spanned<String>>,
+    error: &impl Fn(Option<Range<usize>>, &str) -> Error,
+) -> Result<Option<OutputFormat>> {
+    format
+        .map(|format| {
+            format
+                .get_ref()
+                .parse::<OutputFormat>()
+                .map_err(|e| error(Some(format.span()), &e.to_string()))
+        })
+        .transpose()
+}
+
+impl From<&Config> for ScanConfig {
+    /// Builds a scan configuration from the file settings, using the
+    /// [defaults](ScanConfig::default) for anything not set.
+    fn from(config: &Config) -> Self {
+        let defaults = ScanConfig::default();
+        ScanConfig {
+            root_paths: if config.roots.is_empty() {
+                defaults.root_paths
+            } else {
+                config.roots.clone()
+            },
+            max_depth: config.max_depth.or(defaults.max_depth),
+            follow_symlinks: config.follow_symlinks.unwrap_or(defaults.follow_symlinks),
+            include_submodules: config
+                .include_submodules
+                .unwrap_or(defaults.include_submodules),
+            ignore_patterns: config.ignore_patterns.clone(),
+        }
+    }
+}
+
+/// The file format, before validation.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawConfig {
+    scan: RawScan,
+    output: RawOutput,
+    hosts: BTreeMap<String, String>,
+    projects: BTreeMap<String, RawProject>,
+    profiles: Option<Spanned<BTreeMap<String, RawProfile>>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawScan {
+    roots: Option<Spanned<Vec<String>>>,
+    depth: Option<usize>,
+    follow_symlinks: Option<bool>,
+    include_submodules: Option<bool>,
+    ignore: Vec<Spanned<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawOutput {
+    sort: Option<Spanned<String>>,
+    order: Option<Spanned<String>>,
+    format: Option<Spanned<String>>,
+    locale: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawProfile {
+    description: Option<String>,
+    roots: Vec<String>,
+    depth: Option<usize>,
+    follow_symlinks: Option<bool>,
+    include_submodules: Option<bool>,
+    ignore: Vec<Spanned<String>>,
+    filter: Option<Spanned<String>>,
+    sort: Option<Spanned<String>>,
+    order: Option<Spanned<String>>,
+    format: Option<Spanned<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawProject {
+    tags: Vec<String>,
+    ignore: Option<bool>,
+}
+
+/// Returns the directory of the global configuration files.
+///
+/// `$XDG_CONFIG_HOME/git-projects`, or `~/.config/git-projects`.
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// Returns the directory of state kept between runs, such as frecency.
+///
+/// `$XDG_DATA_HOME/git-projects`, or `~/.local/share/git-projects`.
+pub(crate) fn data_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+/// Returns the directory of files that can be recreated, such as the scan cache.
+///
+/// `$XDG_CACHE_HOME/git-projects`, or `~/.cache/git-projects`.
+pub(crate) fn cache_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CACHE_HOME", ".cache")
+}
+
+/// Returns `$variable/git-projects`, or `~/default/git-projects`.
+fn xdg_dir(variable: &str, default: &str) -> Option<PathBuf> {
+    let base = std::env::var_os(variable)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(default)))?;
+    Some(base.join("git-projects"))
+}
+
+/// Replaces a file atomically, creating its directory if needed, so a failed
+/// write never leaves a truncated file behind.
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)
+}
+
+/// Converts a byte offset into a 1-based line and column.
+pub(crate) fn location(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    (line, column)
+}
+
+/// Expands `~` and resolves relative paths against `base`.
+fn expand_path(path: &str, base: &Path) -> PathBuf {
+    let home = || dirs::home_dir().unwrap_or_default();
+    if path == "~" {
+        home()
+    } else if let Some(rest) = path.strip_prefix("~/") {
+        home().join(rest)
+    } else {
+        base.join(path.strip_prefix("./").unwrap_or(path))
+    }
+}
+
+/// Anchors an ignore pattern from a root file to that root.
+fn anchor_pattern(pattern: &str, root: &Path) -> String {
+    if pattern.starts_with('/') {
+        return pattern.to_string();
+    }
+    let root = globset::escape(&root.display().to_string());
+    let pattern = pattern.trim_end_matches('/');
+    if pattern.contains('/') {
+        format!("{}/{}", root.trim_end_matches('/'), pattern)
+    } else {
+        format!("{}/**/{}", root.trim_end_matches('/'), pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::{SortDirection, SortKey};
+
+    fn project(path: &str, url: Option<&str>) -> GitProject {
+        url.into_iter()
+            .fold(GitProject::test(path), GitProject::with_remote)
+    }
+
+    fn parse_error(source: &str) -> (usize, usize, String) {
+        match Config::parse(source, Path::new("/etc/gp.toml")) {
+            Err(Error::Config {
+                line,
+                column,
+                message,
+                ..
+            }) => (line, column, message),
+            other => panic!("expected a config error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_full_config() {
+        let config = Config::parse(
+            r#"
+[scan]
+roots = ["~/src", "work"]
+depth = 5
+follow_symlinks = false
+ignore = ["node_modules"]
+
+[output]
+sort = "service,name"
+order = "asc,desc"
+format = "csv"
+locale = "de"
+
+[hosts]
+"Git.Example.com" = "gitea"
+
+[projects."github.com/acme/tool"]
+tags = ["client-x"]
+
+[projects."./old"]
+ignore = true
+"#,
+            Path::new("/etc/gp/config.toml"),
+        )
+        .unwrap();
+
+        let home = dirs::home_dir().unwrap_or_default();
+        assert_eq!(
+            config.roots,
+            [home.join("src"), PathBuf::from("/etc/gp/work")]
+        );
+        assert_eq!(config.max_depth, Some(5));
+        assert_eq!(config.follow_symlinks, Some(false));
+        assert_eq!(config.include_submodules, None);
+        assert_eq!(
+            config.sort.unwrap().keys(),
+            [
+                (
+                    SortKey::Field(crate::filter::Field::Service),
+                    SortDirection::Asc
+                ),
+                (
+                    SortKey::Field(crate::filter::Field::Name),
+                    SortDirection::Desc
+                ),
+            ]
+        );
+        assert_eq!(config.format, Some(OutputFormat::Csv));
+        assert_eq!(config.locale.as_deref(), Some("de"));
+        assert_eq!(config.hosts["git.example.com"], "gitea");
+        assert_eq!(
+            config.projects[0].selector,
+            ProjectSelector::Path(PathBuf::from("/etc/gp/old"))
+        );
+        assert_eq!(config.projects[0].ignore, Some(true));
+        assert_eq!(
+            config.projects[1].selector,
+            ProjectSelector::Name("github.com/acme/tool".to_string())
+        );
+        assert_eq!(config.sources, [PathBuf::from("/etc/gp/config.toml")]);
+    }
+
+    #[test]
+    fn test_errors_point_at_the_value() {
+        let (line, column, message) = parse_error("[scan]\ndepth = \"deep\"\n");
+        assert_eq!((line, column), (2, 9));
+        assert!(message.contains("expected usize"), "{}", message);
+
+        let (line, column, message) = parse_error("[scan]\nmax_depth = 3\n");
+        assert_eq!((line, column), (2, 1));
+        assert!(message.contains("unknown field"), "{}", message);
+
+        let (line, column, message) = parse_error("[output]\nsort = \"name,nope\"\n");
+        assert_eq!((line, column), (2, 8));
+        assert!(message.contains("unknown sort key 'nope'"), "{}", message);
+
+        let (line, column, _) = parse_error("[output]\nsort = \"name\"\norder = \"up\"\n");
+        assert_eq!((line, column), (3, 9));
+
+        let (line, column, _) = parse_error("[scan]\nignore = [\"ok\", \"a{b\"]\n");
+        assert_eq!((line, column), (2, 17));
+
+        let (line, _, _) = parse_error("[output]\nformat = \"xml\"\n");
+        assert_eq!(line, 2);
+
+        let err = Config::parse("[scan\n", Path::new("/etc/gp.toml")).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Invalid configuration at /etc/gp.toml:1:"));
+    }
+
+    #[test]
+    fn test_root_files_apply_to_their_root() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let (a, b) = (temp.path().join("a"), temp.path().join("b"));
+        for (root, depth) in [(&a, 6), (&b, 1)] {
+            std::fs::create_dir(root).unwrap();
+            std::fs::write(
+                root.join(ROOT_CONFIG_FILE),
+                format!("[scan]\ndepth = {}\n[output]\nformat = \"json\"\n", depth),
+            )
+            .unwrap();
+        }
+
+        // The order of the roots does not matter
+        for roots in [[a.clone(), b.clone()], [b.clone(), a.clone()]] {
+            let config = Config::default().with_roots(&roots).unwrap();
+            let base = ScanConfig::from(&config);
+            assert_eq!(config.max_depth, None);
+            assert_eq!(config.format, Some(OutputFormat::Json));
+            assert_eq!(config.scan_config_for(&a, &base).max_depth, Some(6));
+            assert_eq!(config.scan_config_for(&b, &base).max_depth, Some(1));
+            assert_eq!(
+                config.scan_config_for(&b, &base).root_paths,
+                std::slice::from_ref(&b)
+            );
+        }
+    }
+
+    #[test]
+    fn test_root_files_are_merged_and_anchored() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        std::fs::write(
+            root.join(ROOT_CONFIG_FILE),
+            "[scan]\ndepth = 2\nignore = [\"build/*\", \"vendor\"]\n",
+        )
+        .unwrap();
+
+        let global = Config::parse(
+            "[scan]\ndepth = 4\nignore = [\"node_modules\"]\n",
+            Path::new("/g.toml"),
+        )
+        .unwrap();
+        let config = global.with_roots(std::slice::from_ref(&root)).unwrap();
+        assert_eq!(config.max_depth, Some(4));
+        let scan = config.scan_config_for(&root, &ScanConfig::from(&config));
+        assert_eq!(scan.max_depth, Some(2));
+        let escaped = globset::escape(&root.display().to_string());
+        assert_eq!(
+            scan.ignore_patterns,
+            [
+                "node_modules".to_string(),
+                format!("{}/build/*", escaped),
+                format!("{}/**/vendor", escaped),
+            ]
+        );
+        assert_eq!(config.sources.len(), 2);
+
+        std::fs::write(root.join(ROOT_CONFIG_FILE), "[scan]\nroots = [\"/\"]\n").unwrap();
+        let err = Config::default().with_roots(&[root]).unwrap_err();
+        assert!(matches!(err, Error::Config { line: 2, .. }));
+    }
+
+    #[test]
+    fn test_apply_hosts_tags_and_ignore() {
+        let config = Config::parse(
+            r#"
+[hosts]
+"git.example.com" = "gitea"
+
+[projects."github.com/acme/tool"]
+tags = ["client-x"]
+
+[projects.tool]
+tags = ["client-x", "oncall"]
+
+[projects."/w/old"]
+ignore = true
+"#,
+            Path::new("/etc/gp.toml"),
+        )
+        .unwrap();
+
+        let mut projects = vec![
+            project("/w/tool", Some("git@github.com:Acme/Tool.git")),
+            project("/w/old", None),
+            project(
+                "/w/internal",
+                Some("https://git.example.com/team/internal.git"),
+            ),
+        ];
+        config.apply(&mut projects);
+
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[0].tags, ["client-x", "oncall"]);
+        assert_eq!(projects[1].remotes[0].service.as_deref(), Some("gitea"));
+    }
+
+    #[test]
+    fn test_later_rules_override_ignore() {
+        let mut config =
+            Config::parse("[projects.a]\nignore = true\n", Path::new("/g.toml")).unwrap();
+        config
+            .merge(Config::parse("[projects.a]\nignore = false\n", Path::new("/r.toml")).unwrap());
+        let mut projects = vec![project("/w/a", None)];
+        config.apply(&mut projects);
+        assert_eq!(projects.len(), 1);
+    }
+
+    #[test]
+    fn test_scan_config_defaults() {
+        let scan = ScanConfig::from(&Config::default());
+        let defaults = ScanConfig::default();
+        assert_eq!(scan.root_paths, defaults.root_paths);
+        assert_eq!(scan.max_depth, defaults.max_depth);
+        assert!(scan.ignore_patterns.is_empty());
+    }
+
+    #[test]
+    fn test_profiles() {
+        let global = Config::parse(
+            r#"
+[scan]
+roots = ["/src"]
+depth = 3
+ignore = ["node_modules"]
+
+[profiles.work]
+description = "Work repositories"
+roots = ["/work"]
+depth = 4
+ignore = ["archive"]
+filter = 'service == "github"'
+sort = "name"
+order = "desc"
+format = "json"
+
+[profiles.oss]
+roots = ["/oss"]
+depth = 2
+include_submodules = false
+"#,
+            Path::new("/etc/gp.toml"),
+        )
+        .unwrap();
+        assert_eq!(global.profiles.keys().collect::<Vec<_>>(), ["oss", "work"]);
+        assert_eq!(global.roots, [PathBuf::from("/src")]);
+        assert!(global.filter.is_none());
+
+        let work = global.clone().with_profile("work").unwrap();
+        assert_eq!(work.roots, [PathBuf::from("/work")]);
+        assert_eq!(work.max_depth, Some(4));
+        assert_eq!(work.ignore_patterns, ["node_modules", "archive"]);
+        assert_eq!(
+            work.filter.as_ref().map(Filter::source),
+            Some(r#"service == "github""#)
+        );
+        assert_eq!(work.format, Some(OutputFormat::Json));
+        assert_eq!(
+            work.sort.unwrap().keys(),
+            [(
+                SortKey::Field(crate::filter::Field::Name),
+                SortDirection::Desc
+            )]
+        );
+
+        let oss = global.clone().with_profile("oss").unwrap();
+        assert_eq!(oss.max_depth, Some(2));
+        assert_eq!(oss.include_submodules, Some(false));
+        assert!(oss.filter.is_none());
+
+        match global.with_profile("home") {
+            Err(Error::UnknownProfile(message)) => {
+                assert_eq!(message, "home (known: oss, work)")
+            }
+            other => panic!("expected an unknown profile error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_profile_errors() {
+        // Filter errors point into the expression
+        assert_eq!(parse_error("[profiles.x]\nfilter = 'name == 1'\n").0, 2);
+        let (_, column, message) = parse_error("[profiles.x]\nfilter = 'nam == \"a\"'\n");
+        assert_eq!(column, 11);
+        assert!(message.contains("nam"), "{}", message);
+        assert_eq!(parse_error("[profiles.x]\nroot = [\"/\"]\n").0, 2);
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        std::fs::write(root.join(ROOT_CONFIG_FILE), "[profiles.x]\ndepth = 1\n").unwrap();
+        let err = Config::default().with_roots(&[root]).unwrap_err();
+        assert!(matches!(err, Error::Config { line: 1, .. }), "{}", err);
+    }
+}
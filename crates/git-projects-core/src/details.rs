@@ -0,0 +1,158 @@
+//! Full details of a single project.
+//!
+//! A scan only collects what is needed to list many repositories quickly.
+//! [`project_details`] reads the rest for one of them: all remotes with
+//! their push URLs, where the commit identity comes from, local branches,
+//! submodules and the working tree status. [`find_project`] resolves the
+//! name given on the command line to a scanned project.
+
+use crate::error::{Error, Result};
+use crate::fuzzy;
+use crate::git_analyzer::{
+    extract_branches, extract_identity, extract_remote_details, extract_status, extract_submodules,
+};
+use crate::models::{
+    BranchInfo, GitProject, IdentityDetails, RemoteDetails, RepoStatus, SubmoduleInfo,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Everything known about one project.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ProjectDetails {
+    /// The project as found by the scan, including tags and notes.
+    pub project: GitProject,
+
+    /// All remotes, with fetch and push URLs.
+    pub remotes: Vec<RemoteDetails>,
+
+    /// The commit identity and where it is configured.
+    pub identity: IdentityDetails,
+
+    /// Local branches, ordered by name.
+    pub branches: Vec<BranchInfo>,
+
+    /// Submodules declared in `.gitmodules`.
+    pub submodules: Vec<SubmoduleInfo>,
+
+    /// Working tree status and upstream divergence.
+    pub status: RepoStatus,
+}
+
+/// Reads the full details of a scanned project.
+///
+/// # Errors
+///
+/// Returns an error if the repository can no longer be opened or any of its
+/// details cannot be read.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::{project_details, DefaultScanner, ProjectScanner, ScanConfig};
+///
+/// let projects = DefaultScanner::new().scan(&ScanConfig::default())?;
+/// if let Some(project) = projects.into_iter().next() {
+///     let details = project_details(project)?;
+///     println!("{} branches", details.branches.len());
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn project_details(project: GitProject) -> Result<ProjectDetails> {
+    let path = project.path.as_path();
+    Ok(ProjectDetails {
+        remotes: extract_remote_details(path)?,
+        identity: extract_identity(path)?,
+        branches: extract_branches(path)?,
+        submodules: extract_submodules(path)?,
+        status: extract_status(path)?,
+        project,
+    })
+}
+
+/// Finds the project a user means by `query`.
+///
+/// A project whose name equals `query` (ignoring case) wins. Otherwise the
+/// name is [fuzzy matched](crate::fuzzy) and the best match wins.
+///
+/// # Errors
+///
+/// Returns [`Error::ProjectNotFound`] if nothing matches, and
+/// [`Error::AmbiguousProject`] if several projects match equally well.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::{find_project, DefaultScanner, ProjectScanner, ScanConfig};
+///
+/// let projects = DefaultScanner::new().scan(&ScanConfig::default())?;
+/// let project = find_project(&projects, "gpc")?;
+/// println!("{}", project.path.display());
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn find_project<'a>(projects: &'a [GitProject], query: &str) -> Result<&'a GitProject> {
+    let exact: Vec<&GitProject> = projects
+        .iter()
+        .filter(|p| p.name.to_lowercase() == query.to_lowercase())
+        .collect();
+    let candidates = if exact.is_empty() {
+        let ranked = fuzzy::rank(query, projects, |p| &p.name);
+        let best = ranked.first().map(|(score, _)| *score);
+        ranked
+            .into_iter()
+            .take_while(|(score, _)| Some(*score) == best)
+            .map(|(_, project)| project)
+            .collect()
+    } else {
+        exact
+    };
+
+    match candidates.as_slice() {
+        [] => Err(Error::project_not_found(query)),
+        [project] => Ok(project),
+        _ => Err(Error::ambiguous_project(
+            query,
+            candidates
+                .iter()
+                .map(|p| p.path.display().to_string())
+                .collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::Path;
+
+    #[test]
+    fn test_find_project() {
+        let projects = [
+            GitProject::test("/src/git-projects"),
+            GitProject::test("/src/git-projects-core"),
+            GitProject::test("/src/Tool"),
+        ];
+        let find = |query| find_project(&projects, query).map(|p| p.path.as_path());
+
+        // Exact names win over better fuzzy scores
+        assert_eq!(
+            find("git-projects").unwrap(),
+            Path::new("/src/git-projects")
+        );
+        assert_eq!(find("tool").unwrap(), Path::new("/src/Tool"));
+        assert_eq!(find("gpc").unwrap(), Path::new("/src/git-projects-core"));
+        assert!(matches!(find("zzz"), Err(Error::ProjectNotFound(_))));
+    }
+
+    #[test]
+    fn test_ambiguous_project() {
+        let projects = [GitProject::test("/src/tool"), GitProject::test("/tmp/tool")];
+        match find_project(&projects, "tool") {
+            Err(Error::AmbiguousProject { candidates, .. }) => {
+                assert_eq!(candidates, ["/src/tool", "/tmp/tool"])
+            }
+            other => panic!("expected an ambiguous match, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,449 @@
+//! Differences between two scans of the same machine.
+//!
+//! [`diff`] compares two snapshots, e.g. the JSON output of two runs, and
+//! lists what changed as [`Change`] records: projects added, removed or
+//! moved, remotes added, removed or pointed elsewhere, a different commit
+//! identity, another branch checked out or a branch that moved to another
+//! commit.
+//!
+//! Projects are matched by path first. Of the rest, a removed and an added
+//! project with the same [`RemoteIdentity`] are taken to be the same project
+//! moved to a new path, and are compared like the others.
+
+use crate::error::{Error, Result};
+use crate::identity::RemoteIdentity;
+use crate::models::GitProject;
+use crate::schema::{ProjectList, SCHEMA_VERSION};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A change between two snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Change {
+    /// The path of the project: the new one, or the old one if it was removed.
+    pub path: PathBuf,
+
+    /// What changed.
+    #[serde(flatten)]
+    pub kind: ChangeKind,
+}
+
+/// What changed about a project.
+///
+/// Serialized with a `change` tag, e.g.
+/// `{"change": "branch_changed", "old": "main", "new": "feature"}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// The project is new.
+    Added,
+
+    /// The project is gone.
+    Removed,
+
+    /// The project is a clone of the same repository at another path.
+    Moved {
+        /// The old path.
+        from: PathBuf,
+    },
+
+    /// A remote was added.
+    RemoteAdded {
+        /// The name of the remote.
+        name: String,
+        /// Its URL.
+        url: String,
+    },
+
+    /// A remote was removed.
+    RemoteRemoved {
+        /// The name of the remote.
+        name: String,
+        /// Its old URL.
+        url: String,
+    },
+
+    /// A remote points to another URL.
+    RemoteChanged {
+        /// The name of the remote.
+        name: String,
+        /// The old URL.
+        old: String,
+        /// The new URL.
+        new: String,
+    },
+
+    /// The commit identity (`user.name` and `user.email`) changed.
+    IdentityChanged {
+        /// The old identity, formatted as `Name <email>`.
+        old: Option<String>,
+        /// The new identity.
+        new: Option<String>,
+    },
+
+    /// Another branch is checked out; `None` for a detached `HEAD`.
+    BranchChanged {
+        /// The old branch.
+        old: Option<String>,
+        /// The new branch.
+        new: Option<String>,
+    },
+
+    /// The checked-out branch points to another commit.
+    HeadMoved {
+        /// The branch; `None` for a detached `HEAD`.
+        branch: Option<String>,
+        /// The old commit.
+        old: Option<String>,
+        /// The new commit.
+        new: Option<String>,
+    },
+}
+
+/// Compares two snapshots of the projects on a machine.
+///
+/// Changes are listed in the order of the new snapshot, with removed
+/// projects last.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::diff::{diff, load_snapshot};
+/// use std::path::Path;
+///
+/// let old = load_snapshot(Path::new("monday.json"))?;
+/// let new = load_snapshot(Path::new("friday.json"))?;
+/// for change in diff(&old, &new) {
+///     println!("{}: {:?}", change.path.display(), change.kind);
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn diff(old: &[GitProject], new: &[GitProject]) -> Vec<Change> {
+    let by_path: HashMap<&Path, usize> = old
+        .iter()
+        .enumerate()
+        .map(|(i, project)| (project.path.as_path(), i))
+        .collect();
+    let mut matched: Vec<Option<usize>> = new
+        .iter()
+        .map(|project| by_path.get(project.path.as_path()).copied())
+        .collect();
+    let mut old_matched = vec![false; old.len()];
+    for &i in matched.iter().flatten() {
+        old_matched[i] = true;
+    }
+
+    // Match the rest by remote identity, in order
+    let mut by_identity: HashMap<RemoteIdentity, Vec<usize>> = HashMap::new();
+    for (i, project) in old.iter().enumerate().rev() {
+        if !old_matched[i] {
+            if let Some(identity) = project.identity() {
+                by_identity.entry(identity).or_default().push(i);
+            }
+        }
+    }
+    for (project, matched) in new.iter().zip(&mut matched) {
+        if matched.is_none() {
+            *matched = project
+                .identity()
+                .and_then(|identity| by_identity.get_mut(&identity)?.pop());
+            if let Some(i) = *matched {
+                old_matched[i] = true;
+            }
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (project, matched) in new.iter().zip(matched) {
+        let mut change = |kind| {
+            changes.push(Change {
+                path: project.path.clone(),
+                kind,
+            })
+        };
+        let Some(i) = matched else {
+            change(ChangeKind::Added);
+            continue;
+        };
+        let before = &old[i];
+        if before.path != project.path {
+            change(ChangeKind::Moved {
+                from: before.path.clone(),
+            });
+        }
+        for kind in project_changes(before, project) {
+            change(kind);
+        }
+    }
+    for (project, _) in old.iter().zip(old_matched).filter(|(_, m)| !m) {
+        changes.push(Change {
+            path: project.path.clone(),
+            kind: ChangeKind::Removed,
+        });
+    }
+    changes
+}
+
+/// Compares the remotes, identity and `HEAD` of the same project
+fn project_changes(old: &GitProject, new: &GitProject) -> Vec<ChangeKind> {
+    let mut changes = Vec::new();
+    for remote in &new.remotes {
+        match old.remotes.iter().find(|r| r.name == remote.name) {
+            None => changes.push(ChangeKind::RemoteAdded {
+                name: remote.name.clone(),
+                url: remote.url.clone(),
+            }),
+            Some(before) if before.url != remote.url => changes.push(ChangeKind::RemoteChanged {
+                name: remote.name.clone(),
+                old: before.url.clone(),
+                new: remote.url.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for remote in &old.remotes {
+        if !new.remotes.iter().any(|r| r.name == remote.name) {
+            changes.push(ChangeKind::RemoteRemoved {
+                name: remote.name.clone(),
+                url: remote.url.clone(),
+            });
+        }
+    }
+
+    let (old_identity, new_identity) = (identity(old), identity(new));
+    if old_identity != new_identity {
+        changes.push(ChangeKind::IdentityChanged {
+            old: old_identity,
+            new: new_identity,
+        });
+    }
+
+    // Nothing to compare if HEAD could not be read
+    if let (Some(before), Some(head)) = (&old.head, &new.head) {
+        if before.branch != head.branch {
+            changes.push(ChangeKind::BranchChanged {
+                old: before.branch.clone(),
+                new: head.branch.clone(),
+            });
+        } else if before.commit != head.commit {
+            changes.push(ChangeKind::HeadMoved {
+                branch: head.branch.clone(),
+                old: before.commit.clone(),
+                new: head.commit.clone(),
+            });
+        }
+    }
+    changes
+}
+
+/// Formats the commit identity of a project as `Name <email>`
+fn identity(project: &GitProject) -> Option<String> {
+    let config = project.config.as_ref()?;
+    match (&config.user_name, &config.user_email) {
+        (Some(name), Some(email)) => Some(format!("{} <{}>", name, email)),
+        (Some(name), None) => Some(name.clone()),
+        (None, Some(email)) => Some(format!("<{}>", email)),
+        (None, None) => None,
+    }
+}
+
+/// Loads a snapshot: the JSON or NDJSON output of a scan.
+///
+/// Accepts the [`ProjectList`] envelope of `--json`, a plain array of
+/// projects and `--format ndjson`, all without `--columns`.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] if the file cannot be read, or
+/// [`Error::InvalidSnapshot`] if it is malformed or has another major schema
+/// version.
+pub fn load_snapshot(path: &Path) -> Result<Vec<GitProject>> {
+    let source = std::fs::read_to_string(path)?;
+    parse_snapshot(&source)
+        .map_err(|message| Error::invalid_snapshot(format!("{}: {}", path.display(), message)))
+}
+
+/// Parses a snapshot, see [`load_snapshot`]
+fn parse_snapshot(source: &str) -> std::result::Result<Vec<GitProject>, String> {
+    match serde_json::from_str::<serde_json::Value>(source) {
+        Ok(value) if value.get("schema_version").is_some() || value.get("projects").is_some() => {
+            let version = value
+                .get("schema_version")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let major = |version: &str| version.split('.').next().map(str::to_string);
+            if major(version) != major(SCHEMA_VERSION) {
+                return Err(format!(
+                    "schema version {} is not supported (expected {})",
+                    version, SCHEMA_VERSION
+                ));
+            }
+            let list: ProjectList = serde_json::from_value(value).map_err(|e| e.to_string())?;
+            Ok(list.projects)
+        }
+        // A single project, e.g. one line of NDJSON
+        Ok(value) if value.is_object() => serde_json::from_value(value)
+            .map(|project| vec![project])
+            .map_err(|e| e.to_string()),
+        Ok(value) => serde_json::from_value(value).map_err(|e| e.to_string()),
+        // One project per line
+        Err(_) => source
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|e| format!("line {}: {}", i + 1, e))
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigScope, GitConfig, HeadInfo};
+
+    fn project(path: &str, url: Option<&str>) -> GitProject {
+        let project = GitProject {
+            head: Some(HeadInfo {
+                branch: Some("main".to_string()),
+                commit: Some("a".repeat(40)),
+                upstream_remote: None,
+            }),
+            ..GitProject::test(path)
+        };
+        url.into_iter().fold(project, GitProject::with_remote)
+    }
+
+    fn kinds(changes: &[Change]) -> Vec<(&str, &ChangeKind)> {
+        changes
+            .iter()
+            .map(|c| (c.path.to_str().unwrap(), &c.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_added_removed_and_moved() {
+        let old = [
+            project("/src/tool", Some("git@github.com:acme/tool.git")),
+            project("/src/gone", None),
+            project("/tmp/lib", Some("https://github.com/acme/lib")),
+        ];
+        let new = [
+            project("/src/tool", Some("git@github.com:acme/tool.git")),
+            project("/src/lib", Some("git@github.com:acme/lib.git")),
+            project("/src/new", None),
+        ];
+        let changes = diff(&old, &new);
+        assert_eq!(
+            kinds(&changes),
+            [
+                (
+                    "/src/lib",
+                    &ChangeKind::Moved {
+                        from: PathBuf::from("/tmp/lib")
+                    }
+                ),
+                (
+                    "/src/lib",
+                    &ChangeKind::RemoteChanged {
+                        name: "origin".to_string(),
+                        old: "https://github.com/acme/lib".to_string(),
+                        new: "git@github.com:acme/lib.git".to_string(),
+                    }
+                ),
+                ("/src/new", &ChangeKind::Added),
+                ("/src/gone", &ChangeKind::Removed),
+            ]
+        );
+        assert!(diff(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_remotes_identity_and_head() {
+        let old = project("/src/tool", Some("https://github.com/acme/tool.git"));
+        let mut new = old.clone();
+        new.remotes[0].name = "upstream".to_string();
+        new.config = Some(GitConfig {
+            user_name: Some("Ada".to_string()),
+            user_email: Some("ada@example.com".to_string()),
+            scope: ConfigScope::Local,
+        });
+        new.head.as_mut().unwrap().commit = Some("b".repeat(40));
+
+        let changes = diff(std::slice::from_ref(&old), std::slice::from_ref(&new));
+        let kinds: Vec<&ChangeKind> = changes.iter().map(|c| &c.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &ChangeKind::RemoteAdded {
+                    name: "upstream".to_string(),
+                    url: "https://github.com/acme/tool.git".to_string(),
+                },
+                &ChangeKind::RemoteRemoved {
+                    name: "origin".to_string(),
+                    url: "https://github.com/acme/tool.git".to_string(),
+                },
+                &ChangeKind::IdentityChanged {
+                    old: None,
+                    new: Some("Ada <ada@example.com>".to_string()),
+                },
+                &ChangeKind::HeadMoved {
+                    branch: Some("main".to_string()),
+                    old: Some("a".repeat(40)),
+                    new: Some("b".repeat(40)),
+                },
+            ]
+        );
+
+        new = old.clone();
+        new.head.as_mut().unwrap().branch = None;
+        assert_eq!(
+            diff(std::slice::from_ref(&old), &[new])[0].kind,
+            ChangeKind::BranchChanged {
+                old: Some("main".to_string()),
+                new: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_change_json() {
+        let change = Change {
+            path: PathBuf::from("/src/tool"),
+            kind: ChangeKind::Moved {
+                from: PathBuf::from("/tmp/tool"),
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&change).unwrap(),
+            serde_json::json!({ "path": "/src/tool", "change": "moved", "from": "/tmp/tool" })
+        );
+    }
+
+    #[test]
+    fn test_parse_snapshot() {
+        let projects = vec![project("/src/tool", None), project("/src/lib", None)];
+        let envelope = serde_json::to_string(&ProjectList::new(projects.clone())).unwrap();
+        assert_eq!(parse_snapshot(&envelope).unwrap(), projects);
+
+        let array = serde_json::to_string(&projects).unwrap();
+        assert_eq!(parse_snapshot(&array).unwrap(), projects);
+
+        let ndjson: String = projects
+            .iter()
+            .map(|p| serde_json::to_string(p).unwrap() + "\n")
+            .collect();
+        assert_eq!(parse_snapshot(&ndjson).unwrap(), projects);
+
+        // NDJSON with a single project is a single JSON object
+        let single = serde_json::to_string(&projects[0]).unwrap() + "\n";
+        assert_eq!(parse_snapshot(&single).unwrap(), projects[..1]);
+        let single = serde_json::to_string_pretty(&projects[0]).unwrap();
+        assert_eq!(parse_snapshot(&single).unwrap(), projects[..1]);
+
+        assert!(parse_snapshot(r#"{"schema_version": "99.0.0", "projects": []}"#).is_err());
+        assert!(parse_snapshot("not json").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Detection of duplicate clones of the same remote repository.
+//!
+//! Projects are grouped by the [`RemoteIdentity`] of their primary remote, so
+//! `~/work/tool` (cloned via SSH) and `~/tmp/Tool` (cloned via HTTPS) end up
+//! in the same [`DuplicateGroup`].
+
+use crate::identity::RemoteIdentity;
+use crate::models::GitProject;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A set of local clones that share the same remote identity.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DuplicateGroup {
+    /// The normalized remote identity shared by all projects in this group.
+    pub identity: RemoteIdentity,
+
+    /// The clones of this remote, in the order they were passed in.
+    ///
+    /// Always contains at least two projects.
+    pub projects: Vec<GitProject>,
+}
+
+impl DuplicateGroup {
+    /// Returns the distinct `HEAD` commits of the clones in this group.
+    ///
+    /// Clones whose `HEAD` could not be resolved are ignored.
+    pub fn head_commits(&self) -> BTreeSet<&str> {
+        self.projects
+            .iter()
+            .filter_map(|p| p.head.as_ref()?.commit.as_deref())
+            .collect()
+    }
+
+    /// Whether the clones point to different `HEAD` commits.
+    pub fn heads_diverge(&self) -> bool {
+        self.head_commits().len() > 1
+    }
+}
+
+/// Groups projects that are clones of the same remote repository.
+///
+/// Only groups with two or more projects are returned; projects without a
+/// parseable remote are never considered duplicates. Groups are sorted by
+/// identity.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::{find_duplicates, DefaultScanner, ProjectScanner, ScanConfig};
+///
+/// let projects = DefaultScanner::new().scan(&ScanConfig::default())?;
+/// for group in find_duplicates(&projects) {
+///     println!("{} is cloned {} times", group.identity, group.projects.len());
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn find_duplicates(projects: &[GitProject]) -> Vec<DuplicateGroup> {
+    let mut groups: BTreeMap<RemoteIdentity, Vec<GitProject>> = BTreeMap::new();
+
+    for project in projects {
+        if let Some(identity) = project.identity() {
+            groups.entry(identity).or_default().push(project.clone());
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, projects)| projects.len() > 1)
+        .map(|(identity, projects)| DuplicateGroup { identity, projects })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::HeadInfo;
+
+    fn project(path: &str, url: Option<&str>, commit: Option<&str>) -> GitProject {
+        let project = GitProject {
+            head: Some(HeadInfo {
+                branch: Some("main".to_string()),
+                commit: commit.map(str::to_string),
+                upstream_remote: None,
+            }),
+            ..GitProject::test(path)
+        };
+        url.into_iter().fold(project, GitProject::with_remote)
+    }
+
+    #[test]
+    fn test_groups_equivalent_urls() {
+        let projects = vec![
+            project("/a/tool", Some("https://github.com/Acme/tool.git"), None),
+            project("/b/tool", Some("git@github.com:acme/tool"), None),
+            project("/c/other", Some("https://github.com/acme/other.git"), None),
+        ];
+
+        let groups = find_duplicates(&projects);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].identity.to_string(), "github.com/acme/tool");
+        assert_eq!(groups[0].projects.len(), 2);
+    }
+
+    #[test]
+    fn test_ignores_projects_without_remotes() {
+        let projects = vec![
+            project("/a/tool", None, None),
+            project("/b/tool", None, None),
+        ];
+        assert!(find_duplicates(&projects).is_empty());
+    }
+
+    #[test]
+    fn test_heads_diverge() {
+        let url = Some("https://github.com/acme/tool.git");
+        let same = find_duplicates(&[
+            project("/a/tool", url, Some("aaaa")),
+            project("/b/tool", url, Some("aaaa")),
+        ]);
+        assert!(!same[0].heads_diverge());
+
+        let diverging = find_duplicates(&[
+            project("/a/tool", url, Some("aaaa")),
+            project("/b/tool", url, Some("bbbb")),
+            project("/c/tool", url, None),
+        ]);
+        assert!(diverging[0].heads_diverge());
+        assert_eq!(diverging[0].head_commits().len(), 2);
+    }
+}
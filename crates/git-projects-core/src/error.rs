@@ -42,7 +42,7 @@ pub enum Error {
         path: PathBuf,
         /// The underlying gitoxide error.
         #[source]
-        source: gix::open::Error,
+        source: Box<gix::open::Error>,
     },
 
     /// Failed to discover a Git repository in the given path.
@@ -54,7 +54,7 @@ pub enum Error {
         path: PathBuf,
         /// The underlying gitoxide error.
         #[source]
-        source: gix::discover::Error,
+        source: Box<gix::discover::Error>,
     },
 
     /// Failed to read Git configuration.
@@ -66,7 +66,7 @@ pub enum Error {
         path: PathBuf,
         /// The underlying gitoxide config error.
         #[source]
-        source: gix::config::Error,
+        source: Box<gix::config::Error>,
     },
 
     /// Failed to access remote configuration.
@@ -80,6 +80,58 @@ pub enum Error {
         message: String,
     },
 
+    /// Failed to read the `HEAD` reference.
+    ///
+    /// This occurs when trying to determine the checked-out branch or commit.
+    #[error("Failed to read HEAD for {path}: {message}")]
+    GitHead {
+        /// The repository path where HEAD could not be read.
+        path: PathBuf,
+        /// A descriptive error message.
+        message: String,
+    },
+
+    /// Failed to determine the working tree status.
+    ///
+    /// This occurs when comparing the index, working tree and upstream.
+    #[error("Failed to read status for {path}: {message}")]
+    GitStatus {
+        /// The repository path whose status could not be read.
+        path: PathBuf,
+        /// A descriptive error message.
+        message: String,
+    },
+
+    /// Failed to read branches, submodules or other repository details.
+    #[error("Failed to read details for {path}: {message}")]
+    GitDetails {
+        /// The repository path whose details could not be read.
+        path: PathBuf,
+        /// A descriptive error message.
+        message: String,
+    },
+
+    /// Failed to clone a repository.
+    #[error("Failed to clone into {path}: {message}")]
+    GitClone {
+        /// The path the repository was cloned into.
+        path: PathBuf,
+        /// A descriptive error message.
+        message: String,
+    },
+
+    /// A manifest is malformed or uses an unsupported feature.
+    #[error("Invalid manifest: {0}")]
+    InvalidManifest(String),
+
+    /// An editor workspace file is malformed.
+    #[error("Invalid workspace file: {0}")]
+    InvalidWorkspace(String),
+
+    /// A snapshot of a scan is malformed or has an unsupported schema version.
+    #[error("Invalid snapshot: {0}")]
+    InvalidSnapshot(String),
+
     /// A required path does not exist.
     ///
     /// Used when a specified scan root or target path is invalid.
@@ -98,6 +150,81 @@ pub enum Error {
     #[error("Invalid URL format: {0}")]
     InvalidUrl(String),
 
+    /// A filter expression could not be parsed or type-checked.
+    ///
+    /// The column is 1-based and counts characters, not bytes.
+    #[error("Invalid filter at column {column}: {message}")]
+    InvalidFilter {
+        /// The column where the error was detected.
+        column: usize,
+        /// A descriptive error message.
+        message: String,
+    },
+
+    /// A sort specification could not be parsed.
+    ///
+    /// Occurs for unknown sort keys or directions in `--sort-by`/`--order`.
+    #[error("Invalid sort specification: {0}")]
+    InvalidSort(String),
+
+    /// A group specification could not be parsed.
+    ///
+    /// Occurs for unknown keys in `--group-by`.
+    #[error("Invalid group specification: {0}")]
+    InvalidGroup(String),
+
+    /// An output format or column selection could not be parsed.
+    ///
+    /// Occurs for unknown values in `--format` or `--columns`.
+    #[error("Invalid output specification: {0}")]
+    InvalidOutput(String),
+
+    /// A user-defined output template could not be compiled or rendered.
+    ///
+    /// The message includes the template name and line where available.
+    #[error("Template error: {0}")]
+    Template(String),
+
+    /// An ignore pattern is not a valid glob.
+    ///
+    /// Occurs for malformed entries in [`ScanConfig::ignore_patterns`](crate::ScanConfig::ignore_patterns).
+    #[error("Invalid ignore pattern: {0}")]
+    InvalidPattern(String),
+
+    /// A configuration file is malformed or contains an invalid value.
+    ///
+    /// The line and column are 1-based and point at the offending value.
+    #[error("Invalid configuration at {}:{line}:{column}: {message}", path.display())]
+    Config {
+        /// The configuration file.
+        path: PathBuf,
+        /// The line of the error.
+        line: usize,
+        /// The column of the error.
+        column: usize,
+        /// A descriptive error message.
+        message: String,
+    },
+
+    /// A profile was requested that is not defined in the configuration.
+    ///
+    /// The message names the known profiles.
+    #[error("Unknown profile: {0}")]
+    UnknownProfile(String),
+
+    /// No project matches a name given on the command line.
+    #[error("No repository matching '{0}' found")]
+    ProjectNotFound(String),
+
+    /// A name matches several projects equally well.
+    #[error("'{query}' matches several repositories: {}", candidates.join(", "))]
+    AmbiguousProject {
+        /// The name that was looked up.
+        query: String,
+        /// The paths of the matching projects.
+        candidates: Vec<String>,
+    },
+
     /// Localization system error.
     ///
     /// This covers errors in loading or using Fluent translation files.
@@ -108,6 +235,20 @@ pub enum Error {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// An error of the inventory database.
+    #[cfg(feature = "inventory")]
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    /// An error answered by a `projects-cli serve` daemon.
+    #[error("Server error {code}: {message}")]
+    Rpc {
+        /// The JSON-RPC error code
+        code: i64,
+        /// The message of the server
+        message: String,
+    },
+
     /// A generic error with a custom message.
     ///
     /// Used for miscellaneous errors that don't fit other categories.
@@ -132,7 +273,7 @@ impl Error {
     pub fn git_open(path: impl Into<PathBuf>, source: gix::open::Error) -> Self {
         Error::GitOpen {
             path: path.into(),
-            source,
+            source: Box::new(source),
         }
     }
 
@@ -140,7 +281,7 @@ impl Error {
     pub fn git_discover(path: impl Into<PathBuf>, source: gix::discover::Error) -> Self {
         Error::GitDiscover {
             path: path.into(),
-            source,
+            source: Box::new(source),
         }
     }
 
@@ -148,7 +289,7 @@ impl Error {
     pub fn git_config(path: impl Into<PathBuf>, source: gix::config::Error) -> Self {
         Error::GitConfig {
             path: path.into(),
-            source,
+            source: Box::new(source),
         }
     }
 
@@ -160,6 +301,53 @@ impl Error {
         }
     }
 
+    /// Creates a GitHead error.
+    pub fn git_head(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Error::GitHead {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates a GitDetails error.
+    pub fn git_details(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Error::GitDetails {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates a GitStatus error.
+    pub fn git_status(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Error::GitStatus {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates a GitClone error.
+    pub fn git_clone(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Error::GitClone {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates an InvalidManifest error.
+    pub fn invalid_manifest(message: impl Into<String>) -> Self {
+        Error::InvalidManifest(message.into())
+    }
+
+    /// Creates an InvalidWorkspace error.
+    pub fn invalid_workspace(message: impl Into<String>) -> Self {
+        Error::InvalidWorkspace(message.into())
+    }
+
+    /// Creates an InvalidSnapshot error.
+    pub fn invalid_snapshot(message: impl Into<String>) -> Self {
+        Error::InvalidSnapshot(message.into())
+    }
+
     /// Creates a PathNotFound error.
     pub fn path_not_found(path: impl Into<PathBuf>) -> Self {
         Error::PathNotFound(path.into())
@@ -175,6 +363,72 @@ impl Error {
         Error::InvalidUrl(url.into())
     }
 
+    /// Creates an InvalidFilter error.
+    pub fn invalid_filter(column: usize, message: impl Into<String>) -> Self {
+        Error::InvalidFilter {
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an InvalidSort error.
+    pub fn invalid_sort(message: impl Into<String>) -> Self {
+        Error::InvalidSort(message.into())
+    }
+
+    /// Creates an InvalidGroup error.
+    pub fn invalid_group(message: impl Into<String>) -> Self {
+        Error::InvalidGroup(message.into())
+    }
+
+    /// Creates an InvalidOutput error.
+    pub fn invalid_output(message: impl Into<String>) -> Self {
+        Error::InvalidOutput(message.into())
+    }
+
+    /// Creates a Template error.
+    pub fn template(message: impl Into<String>) -> Self {
+        Error::Template(message.into())
+    }
+
+    /// Creates an InvalidPattern error.
+    pub fn invalid_pattern(message: impl Into<String>) -> Self {
+        Error::InvalidPattern(message.into())
+    }
+
+    /// Creates an UnknownProfile error.
+    pub fn unknown_profile(message: impl Into<String>) -> Self {
+        Error::UnknownProfile(message.into())
+    }
+
+    /// Creates a ProjectNotFound error.
+    pub fn project_not_found(query: impl Into<String>) -> Self {
+        Error::ProjectNotFound(query.into())
+    }
+
+    /// Creates an AmbiguousProject error.
+    pub fn ambiguous_project(query: impl Into<String>, candidates: Vec<String>) -> Self {
+        Error::AmbiguousProject {
+            query: query.into(),
+            candidates,
+        }
+    }
+
+    /// Creates a Config error.
+    pub fn config(
+        path: impl Into<PathBuf>,
+        line: usize,
+        column: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        Error::Config {
+            path: path.into(),
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
     /// Creates an L10n error.
     pub fn l10n(message: impl Into<String>) -> Self {
         Error::L10n(message.into())
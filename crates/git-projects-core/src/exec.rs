@@ -0,0 +1,385 @@
+//! Running a command in many repositories.
+//!
+//! [`exec`] runs the same command (`git fetch`, `make lint`, ...) in each
+//! project, with at most [`ExecOptions::jobs`] running at once. Output is
+//! passed on line by line as it arrives, tagged with the project, and each
+//! run ends in an [`ExecResult`] with its exit code and duration.
+//!
+//! The command is started directly, not through a shell; use
+//! `sh -c '...'` for pipes and other shell syntax.
+
+use crate::error::{Error, Result};
+use crate::models::GitProject;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Settings for [`exec`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecOptions {
+    /// Maximum number of commands running at the same time (at least 1).
+    pub jobs: usize,
+
+    /// Start no further commands once one has failed.
+    ///
+    /// Commands already running are left to finish.
+    pub fail_fast: bool,
+}
+
+impl Default for ExecOptions {
+    /// One job per CPU, without fail-fast.
+    fn default() -> Self {
+        Self {
+            jobs: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            fail_fast: false,
+        }
+    }
+}
+
+/// The stream a line of output was written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    /// Standard output.
+    Stdout,
+    /// Standard error.
+    Stderr,
+}
+
+/// How a command ended.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExecStatus {
+    /// The command exited with code 0.
+    Success,
+    /// The command exited with another code or was killed by a signal.
+    Failed,
+    /// The command could not be started, e.g. because it does not exist.
+    Error,
+    /// The command was not run, because of fail-fast or a dry run.
+    Skipped,
+}
+
+/// The outcome of running the command in one project.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExecResult {
+    /// The name of the project.
+    pub name: String,
+
+    /// The path the command ran in.
+    pub path: PathBuf,
+
+    /// How the command ended.
+    pub status: ExecStatus,
+
+    /// The exit code, if the command exited normally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+
+    /// Wall-clock time from start to exit, in milliseconds.
+    pub duration_ms: u64,
+
+    /// Why the command could not be started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ExecResult {
+    /// A result for a project the command was not run in.
+    pub fn skipped(project: &GitProject) -> Self {
+        Self {
+            name: project.name.clone(),
+            path: project.path.clone(),
+            status: ExecStatus::Skipped,
+            exit_code: None,
+            duration_ms: 0,
+            error: None,
+        }
+    }
+}
+
+/// Counts of [`ExecResult`]s by status.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExecSummary {
+    /// Commands that exited with code 0.
+    pub succeeded: usize,
+    /// Commands that failed or could not be started.
+    pub failed: usize,
+    /// Commands that were not run.
+    pub skipped: usize,
+}
+
+impl ExecSummary {
+    /// Counts the results by status.
+    pub fn of(results: &[ExecResult]) -> Self {
+        let mut summary = Self::default();
+        for result in results {
+            match result.status {
+                ExecStatus::Success => summary.succeeded += 1,
+                ExecStatus::Failed | ExecStatus::Error => summary.failed += 1,
+                ExecStatus::Skipped => summary.skipped += 1,
+            }
+        }
+        summary
+    }
+}
+
+/// The machine-readable report of a run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExecReport {
+    /// The command and its arguments.
+    pub command: Vec<String>,
+
+    /// Counts by status.
+    pub summary: ExecSummary,
+
+    /// One result per project, in the order the projects were passed in.
+    pub results: Vec<ExecResult>,
+}
+
+impl ExecReport {
+    /// Creates a report from the results of running `command`.
+    pub fn new(command: &[String], results: Vec<ExecResult>) -> Self {
+        Self {
+            command: command.to_vec(),
+            summary: ExecSummary::of(&results),
+            results,
+        }
+    }
+}
+
+/// Runs `command` in the directory of each project.
+///
+/// `on_line` is called with each line of output as it arrives, from several
+/// threads at once; lines of one stream keep their order. Results are in
+/// the order of `projects`, whatever order the commands finish in.
+///
+/// # Errors
+///
+/// Returns [`Error::Other`] if `command` is empty. Commands that fail or
+/// cannot be started are reported in their results instead.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::exec::{exec, ExecOptions};
+/// use git_projects_core::{DefaultScanner, ProjectScanner, ScanConfig};
+///
+/// let projects = DefaultScanner::new().scan(&ScanConfig::default())?;
+/// let command = ["git".to_string(), "fetch".to_string()];
+/// let results = exec(&projects, &command, &ExecOptions::default(), |project, _, line| {
+///     println!("[{}] {}", project.name, line);
+/// })?;
+/// for result in results {
+///     println!("{}: {:?}", result.name, result.exit_code);
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn exec<F>(
+    projects: &[GitProject],
+    command: &[String],
+    options: &ExecOptions,
+    on_line: F,
+) -> Result<Vec<ExecResult>>
+where
+    F: Fn(&GitProject, Stream, &str) + Sync,
+{
+    let Some((program, args)) = command.split_first() else {
+        return Err(Error::other("No command given"));
+    };
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let results: Mutex<Vec<Option<ExecResult>>> = Mutex::new(vec![None; projects.len()]);
+
+    std::thread::scope(|scope| {
+        for _ in 0..options.jobs.clamp(1, projects.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(project) = projects.get(index) else {
+                    break;
+                };
+                let result = if stop.load(Ordering::SeqCst) {
+                    ExecResult::skipped(project)
+                } else {
+                    run_one(project, program, args, &on_line)
+                };
+                if options.fail_fast && result.status != ExecStatus::Success {
+                    stop.store(true, Ordering::SeqCst);
+                }
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    Ok(results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .zip(projects)
+        .map(|(result, project)| result.unwrap_or_else(|| ExecResult::skipped(project)))
+        .collect())
+}
+
+/// Runs the command in one project, forwarding its output
+fn run_one<F>(project: &GitProject, program: &str, args: &[String], on_line: &F) -> ExecResult
+where
+    F: Fn(&GitProject, Stream, &str) + Sync,
+{
+    let started = Instant::now();
+    let mut result = ExecResult {
+        status: ExecStatus::Error,
+        ..ExecResult::skipped(project)
+    };
+
+    let child = Command::new(program)
+        .args(args)
+        .current_dir(&project.path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            result.error = Some(format!("{}: {}", program, e));
+            return result;
+        }
+    };
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    std::thread::scope(|scope| {
+        if let Some(stderr) = stderr {
+            scope.spawn(|| forward_lines(stderr, |line| on_line(project, Stream::Stderr, line)));
+        }
+        if let Some(stdout) = stdout {
+            forward_lines(stdout, |line| on_line(project, Stream::Stdout, line));
+        }
+    });
+
+    match child.wait() {
+        Ok(status) => {
+            result.status = if status.success() {
+                ExecStatus::Success
+            } else {
+                ExecStatus::Failed
+            };
+            result.exit_code = status.code();
+        }
+        Err(e) => result.error = Some(e.to_string()),
+    }
+    result.duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    result
+}
+
+/// Calls `on_line` with each line read, without the line ending
+///
+/// Output that is not UTF-8 is converted lossily.
+fn forward_lines(reader: impl Read, on_line: impl Fn(&str)) {
+    let mut reader = BufReader::new(reader);
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        match reader.read_until(b'\n', &mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let line = String::from_utf8_lossy(&buffer);
+                on_line(line.trim_end_matches(['\n', '\r']));
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn project(path: &std::path::Path, name: &str) -> GitProject {
+        GitProject {
+            name: name.to_string(),
+            ..GitProject::test(path)
+        }
+    }
+
+    fn shell(script: &str) -> Vec<String> {
+        vec!["sh".to_string(), "-c".to_string(), script.to_string()]
+    }
+
+    #[test]
+    fn test_exec() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("code"), "3").unwrap();
+        let projects = [project(temp.path(), "a"), project(temp.path(), "b")];
+        let lines = Mutex::new(Vec::new());
+
+        let options = ExecOptions {
+            jobs: 2,
+            fail_fast: false,
+        };
+        let command = shell("echo out; echo err >&2; exit $(cat code)");
+        let results = exec(&projects, &command, &options, |project, stream, line| {
+            lines
+                .lock()
+                .unwrap()
+                .push((project.name.clone(), stream, line.to_string()));
+        })
+        .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "a");
+        assert_eq!(results[0].status, ExecStatus::Failed);
+        assert_eq!(results[0].exit_code, Some(3));
+        let mut lines = lines.into_inner().unwrap();
+        lines.sort_by(|a, b| (&a.0, &a.2).cmp(&(&b.0, &b.2)));
+        assert_eq!(
+            lines,
+            [
+                ("a".to_string(), Stream::Stderr, "err".to_string()),
+                ("a".to_string(), Stream::Stdout, "out".to_string()),
+                ("b".to_string(), Stream::Stderr, "err".to_string()),
+                ("b".to_string(), Stream::Stdout, "out".to_string()),
+            ]
+        );
+
+        let command = ["does-not-exist-projects-cli".to_string()];
+        let results = exec(&projects, &command, &options, |_, _, _| {}).unwrap();
+        assert_eq!(results[0].status, ExecStatus::Error);
+        assert!(results[0].error.is_some());
+        assert_eq!(
+            ExecSummary::of(&results),
+            ExecSummary {
+                succeeded: 0,
+                failed: 2,
+                skipped: 0
+            }
+        );
+        assert!(exec(&projects, &[], &options, |_, _, _| {}).is_err());
+    }
+
+    #[test]
+    fn test_fail_fast() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let projects = [
+            project(temp.path(), "a"),
+            project(temp.path(), "b"),
+            project(temp.path(), "c"),
+        ];
+        let options = ExecOptions {
+            jobs: 1,
+            fail_fast: true,
+        };
+        let results = exec(&projects, &shell("exit 1"), &options, |_, _, _| {}).unwrap();
+        let statuses: Vec<_> = results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [ExecStatus::Failed, ExecStatus::Skipped, ExecStatus::Skipped]
+        );
+
+        let results = exec(&projects, &shell("true"), &options, |_, _, _| {}).unwrap();
+        assert_eq!(ExecSummary::of(&results).succeeded, 3);
+    }
+}
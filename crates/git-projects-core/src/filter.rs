@@ -0,0 +1,886 @@
+//! A small expression language for filtering scan results.
+//!
+//! Filters are evaluated against [`GitProject`] fields, so every frontend
+//! (CLI, GUI, scripts) shares the same semantics.
+//!
+//! # Syntax
+//!
+//! ```text
+//! expr       := or
+//! or         := and ( "||" and )*
+//! and        := unary ( "&&" unary )*
+//! unary      := "!" unary | primary
+//! primary    := "(" expr ")" | comparison | field | "true" | "false"
+//! comparison := field op literal
+//! op         := "==" | "!=" | "~" | "!~" | "<" | "<=" | ">" | ">="
+//! literal    := "string" | integer | "true" | "false"
+//! ```
+//!
+//! - `==` / `!=` compare exactly; `~` / `!~` match a case-insensitive glob
+//!   pattern (`*` and `?` wildcards).
+//! - Fields derived from remotes (`service`, `account`, `remote`, ...) match
+//!   if **any** remote matches. `a != b` is always the negation of `a == b`.
+//! - `tag` matches if **any** of the project's tags matches, so `tag != x`
+//!   selects projects without the tag `x`.
+//! - Missing values (e.g., no `account`) never compare equal to anything.
+//!
+//! # Example
+//!
+//! ```
+//! use git_projects_core::Filter;
+//!
+//! let filter: Filter = r#"service == "github" && account ~ "acme*" || is_submodule"#
+//!     .parse()
+//!     .unwrap();
+//! ```
+
+use crate::error::{Error, Result};
+use crate::identity::RemoteIdentity;
+use crate::models::{ConfigScope, GitProject};
+use std::fmt;
+use std::str::FromStr;
+
+/// A parsed, type-checked filter expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    expr: Expr,
+    source: String,
+}
+
+impl Filter {
+    /// Parses and type-checks a filter expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidFilter`] with the (1-based) column of the
+    /// offending token if the expression is malformed, references an unknown
+    /// field, or compares a field with a value of the wrong type.
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        parser.expect_end()?;
+        Ok(Self {
+            expr,
+            source: source.to_string(),
+        })
+    }
+
+    /// Returns whether a project matches this filter.
+    pub fn matches(&self, project: &GitProject) -> bool {
+        self.expr.eval(project)
+    }
+
+    /// Returns the root of the expression tree.
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    /// Returns the original expression text.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl FromStr for Filter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// A node of the filter expression tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Logical OR of two expressions.
+    Or(Box<Expr>, Box<Expr>),
+    /// Logical AND of two expressions.
+    And(Box<Expr>, Box<Expr>),
+    /// Logical negation.
+    Not(Box<Expr>),
+    /// A boolean constant (`true` / `false`).
+    Const(bool),
+    /// A boolean field used as a predicate (e.g., `is_submodule`).
+    Flag(Field),
+    /// A comparison between a field and a literal value.
+    Compare {
+        /// The field on the left-hand side.
+        field: Field,
+        /// The comparison operator.
+        op: CompareOp,
+        /// The literal on the right-hand side.
+        value: Literal,
+    },
+}
+
+impl Expr {
+    fn eval(&self, project: &GitProject) -> bool {
+        match self {
+            Expr::Or(a, b) => a.eval(project) || b.eval(project),
+            Expr::And(a, b) => a.eval(project) && b.eval(project),
+            Expr::Not(e) => !e.eval(project),
+            Expr::Const(b) => *b,
+            Expr::Flag(field) => matches!(field.value(project), FieldValue::Bool(true)),
+            Expr::Compare { field, op, value } => compare(field.value(project), *op, value),
+        }
+    }
+}
+
+/// A comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `~` (case-insensitive glob match)
+    Match,
+    /// `!~`
+    NotMatch,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+}
+
+impl CompareOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+            CompareOp::Match => "~",
+            CompareOp::NotMatch => "!~",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+        }
+    }
+}
+
+/// A literal value on the right-hand side of a comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Literal {
+    /// A double-quoted string.
+    Str(String),
+    /// An integer.
+    Int(i64),
+    /// `true` or `false`.
+    Bool(bool),
+}
+
+/// The type of a filter field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    /// Zero or more strings (optional scalars and per-remote values).
+    Text,
+    /// A boolean flag.
+    Bool,
+    /// An integer.
+    Int,
+}
+
+/// A [`GitProject`] field that can be used in filter expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    /// Project name.
+    Name,
+    /// Absolute project path.
+    Path,
+    /// Hosting service of any remote (e.g., `"github"`).
+    Service,
+    /// Account of any remote.
+    Account,
+    /// URL of any remote.
+    Remote,
+    /// Name of any remote (e.g., `"origin"`).
+    RemoteName,
+    /// Normalized host of any remote (e.g., `"github.com"`).
+    Host,
+    /// Normalized identity of any remote (e.g., `"github.com/acme/tool"`).
+    Identity,
+    /// Number of configured remotes.
+    RemoteCount,
+    /// Whether any remote is configured.
+    HasRemotes,
+    /// Configured `user.name`.
+    UserName,
+    /// Configured `user.email`.
+    UserEmail,
+    /// Scope of the identity configuration (`"local"`, `"global"`, `"system"`).
+    Scope,
+    /// Checked-out branch.
+    Branch,
+    /// `HEAD` commit id.
+    Commit,
+    /// Whether the project is a submodule.
+    IsSubmodule,
+    /// Whether the project contains submodules.
+    HasSubmodules,
+    /// Whether the project is a fork of another remote.
+    IsFork,
+    /// `owner/name` of the upstream repository, if the project is a fork.
+    Upstream,
+    /// Any tag of the project (e.g., `"client-x"`).
+    Tag,
+    /// Note about the project.
+    Notes,
+}
+
+impl Field {
+    /// All fields, in documentation order.
+    pub const ALL: &'static [Field] = &[
+        Field::Name,
+        Field::Path,
+        Field::Service,
+        Field::Account,
+        Field::Remote,
+        Field::RemoteName,
+        Field::Host,
+        Field::Identity,
+        Field::RemoteCount,
+        Field::HasRemotes,
+        Field::UserName,
+        Field::UserEmail,
+        Field::Scope,
+        Field::Branch,
+        Field::Commit,
+        Field::IsSubmodule,
+        Field::HasSubmodules,
+        Field::IsFork,
+        Field::Upstream,
+        Field::Tag,
+        Field::Notes,
+    ];
+
+    /// Returns the name used for this field in filter expressions.
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::Name => "name",
+            Field::Path => "path",
+            Field::Service => "service",
+            Field::Account => "account",
+            Field::Remote => "remote",
+            Field::RemoteName => "remote_name",
+            Field::Host => "host",
+            Field::Identity => "identity",
+            Field::RemoteCount => "remote_count",
+            Field::HasRemotes => "has_remotes",
+            Field::UserName => "user_name",
+            Field::UserEmail => "user_email",
+            Field::Scope => "scope",
+            Field::Branch => "branch",
+            Field::Commit => "commit",
+            Field::IsSubmodule => "is_submodule",
+            Field::HasSubmodules => "has_submodules",
+            Field::IsFork => "is_fork",
+            Field::Upstream => "upstream",
+            Field::Tag => "tag",
+            Field::Notes => "notes",
+        }
+    }
+
+    /// Looks up a field by its expression name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|f| f.name() == name)
+    }
+
+    /// Returns the type of this field.
+    pub fn field_type(self) -> FieldType {
+        match self {
+            Field::RemoteCount => FieldType::Int,
+            Field::HasRemotes | Field::IsSubmodule | Field::HasSubmodules | Field::IsFork => {
+                FieldType::Bool
+            }
+            _ => FieldType::Text,
+        }
+    }
+
+    pub(crate) fn value(self, project: &GitProject) -> FieldValue {
+        let remotes = &project.remotes;
+        let config = project.config.as_ref();
+        let head = project.head.as_ref();
+
+        let text = |values: Vec<String>| FieldValue::Text(values);
+        let opt =
+            |value: Option<&str>| FieldValue::Text(value.map(str::to_string).into_iter().collect());
+
+        match self {
+            Field::Name => opt(Some(&project.name)),
+            Field::Path => text(vec![project.path.display().to_string()]),
+            Field::Service => text(remotes.iter().filter_map(|r| r.service.clone()).collect()),
+            Field::Account => text(remotes.iter().filter_map(|r| r.account.clone()).collect()),
+            Field::Remote => text(remotes.iter().map(|r| r.url.clone()).collect()),
+            Field::RemoteName => text(remotes.iter().map(|r| r.name.clone()).collect()),
+            Field::Host => text(
+                remotes
+                    .iter()
+                    .filter_map(|r| RemoteIdentity::parse(&r.url))
+                    .map(|id| id.host)
+                    .collect(),
+            ),
+            Field::Identity => text(
+                remotes
+                    .iter()
+                    .filter_map(|r| RemoteIdentity::parse(&r.url))
+                    .map(|id| id.to_string())
+                    .collect(),
+            ),
+            Field::RemoteCount => FieldValue::Int(remotes.len() as i64),
+            Field::HasRemotes => FieldValue::Bool(!remotes.is_empty()),
+            Field::UserName => opt(config.and_then(|c| c.user_name.as_deref())),
+            Field::UserEmail => opt(config.and_then(|c| c.user_email.as_deref())),
+            Field::Scope => opt(config.map(|c| match c.scope {
+                ConfigScope::Local => "local",
+                ConfigScope::Global => "global",
+                ConfigScope::System => "system",
+            })),
+            Field::Branch => opt(head.and_then(|h| h.branch.as_deref())),
+            Field::Commit => opt(head.and_then(|h| h.commit.as_deref())),
+            Field::IsSubmodule => FieldValue::Bool(project.is_submodule),
+            Field::HasSubmodules => FieldValue::Bool(project.has_submodules),
+            Field::IsFork => FieldValue::Bool(project.fork_relation().is_some()),
+            Field::Upstream => text(
+                project
+                    .fork_relation()
+                    .map(|f| f.upstream_identity.slug())
+                    .into_iter()
+                    .collect(),
+            ),
+            Field::Tag => text(project.tags.clone()),
+            Field::Notes => opt(project.notes.as_deref()),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The runtime value of a field for a specific project.
+pub(crate) enum FieldValue {
+    Text(Vec<String>),
+    Bool(bool),
+    Int(i64),
+}
+
+fn compare(actual: FieldValue, op: CompareOp, expected: &Literal) -> bool {
+    match (actual, expected) {
+        (FieldValue::Text(values), Literal::Str(s)) => match op {
+            CompareOp::Eq => values.iter().any(|v| v == s),
+            CompareOp::Ne => !values.iter().any(|v| v == s),
+            CompareOp::Match => values.iter().any(|v| glob_match(s, v)),
+            CompareOp::NotMatch => !values.iter().any(|v| glob_match(s, v)),
+            _ => false,
+        },
+        (FieldValue::Bool(b), Literal::Bool(e)) => match op {
+            CompareOp::Eq => b == *e,
+            CompareOp::Ne => b != *e,
+            _ => false,
+        },
+        (FieldValue::Int(i), Literal::Int(e)) => match op {
+            CompareOp::Eq => i == *e,
+            CompareOp::Ne => i != *e,
+            CompareOp::Lt => i < *e,
+            CompareOp::Le => i <= *e,
+            CompareOp::Gt => i > *e,
+            CompareOp::Ge => i >= *e,
+            _ => false,
+        },
+        // Ruled out by the type checker
+        _ => false,
+    }
+}
+
+/// Matches `text` against a glob `pattern` (`*`, `?`), ignoring case.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    // Iterative wildcard matching with single-star backtracking
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+// ---------------------------------------------------------------------------
+// Lexer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    Str(String),
+    Int(i64),
+    Op(CompareOp),
+    AndAnd,
+    OrOr,
+    Bang,
+    LParen,
+    RParen,
+    End,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Ident(s) => write!(f, "'{}'", s),
+            TokenKind::Str(s) => write!(f, "\"{}\"", s),
+            TokenKind::Int(i) => write!(f, "{}", i),
+            TokenKind::Op(op) => write!(f, "'{}'", op.symbol()),
+            TokenKind::AndAnd => f.write_str("'&&'"),
+            TokenKind::OrOr => f.write_str("'||'"),
+            TokenKind::Bang => f.write_str("'!'"),
+            TokenKind::LParen => f.write_str("'('"),
+            TokenKind::RParen => f.write_str("')'"),
+            TokenKind::End => f.write_str("end of expression"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    /// 1-based character column where the token starts.
+    column: usize,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let two = |next: char| chars.get(i + 1) == Some(&next);
+        let (kind, len) = match c {
+            '(' => (TokenKind::LParen, 1),
+            ')' => (TokenKind::RParen, 1),
+            '~' => (TokenKind::Op(CompareOp::Match), 1),
+            '&' if two('&') => (TokenKind::AndAnd, 2),
+            '|' if two('|') => (TokenKind::OrOr, 2),
+            '=' if two('=') => (TokenKind::Op(CompareOp::Eq), 2),
+            '!' if two('=') => (TokenKind::Op(CompareOp::Ne), 2),
+            '!' if two('~') => (TokenKind::Op(CompareOp::NotMatch), 2),
+            '!' => (TokenKind::Bang, 1),
+            '<' if two('=') => (TokenKind::Op(CompareOp::Le), 2),
+            '<' => (TokenKind::Op(CompareOp::Lt), 1),
+            '>' if two('=') => (TokenKind::Op(CompareOp::Ge), 2),
+            '>' => (TokenKind::Op(CompareOp::Gt), 1),
+            '=' => return Err(Error::invalid_filter(column, "use '==' for comparison")),
+            '&' => return Err(Error::invalid_filter(column, "use '&&' for logical AND")),
+            '|' => return Err(Error::invalid_filter(column, "use '||' for logical OR")),
+            '"' => {
+                let mut value = String::new();
+                let mut j = i + 1;
+                loop {
+                    match chars.get(j) {
+                        None => return Err(Error::invalid_filter(column, "unterminated string")),
+                        Some('"') => break,
+                        Some('\\') => {
+                            match chars.get(j + 1) {
+                                Some(&escaped @ ('"' | '\\')) => value.push(escaped),
+                                _ => return Err(Error::invalid_filter(
+                                    j + 1,
+                                    "invalid escape sequence (only \\\" and \\\\ are supported)",
+                                )),
+                            }
+                            j += 2;
+                        }
+                        Some(&other) => {
+                            value.push(other);
+                            j += 1;
+                        }
+                    }
+                }
+                (TokenKind::Str(value), j + 1 - i)
+            }
+            c if c.is_ascii_digit()
+                || (c == '-' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) =>
+            {
+                let mut j = i + 1;
+                while chars.get(j).is_some_and(|d| d.is_ascii_digit()) {
+                    j += 1;
+                }
+                let text: String = chars[i..j].iter().collect();
+                let value = text.parse().map_err(|_| {
+                    Error::invalid_filter(column, format!("invalid integer '{}'", text))
+                })?;
+                (TokenKind::Int(value), j - i)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut j = i + 1;
+                while chars
+                    .get(j)
+                    .is_some_and(|d| d.is_alphanumeric() || *d == '_')
+                {
+                    j += 1;
+                }
+                (TokenKind::Ident(chars[i..j].iter().collect()), j - i)
+            }
+            other => {
+                return Err(Error::invalid_filter(
+                    column,
+                    format!("unexpected character '{}'", other),
+                ))
+            }
+        };
+
+        tokens.push(Token { kind, column });
+        i += len;
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::End,
+        column: chars.len() + 1,
+    });
+    Ok(tokens)
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos]
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.pos].clone();
+        if token.kind != TokenKind::End {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn expect_end(&self) -> Result<()> {
+        let token = self.peek();
+        match token.kind {
+            TokenKind::End => Ok(()),
+            ref other => Err(Error::invalid_filter(
+                token.column,
+                format!(
+                    "unexpected {}, expected '&&', '||' or end of expression",
+                    other
+                ),
+            )),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut left = self.parse_and()?;
+        while self.peek().kind == TokenKind::OrOr {
+            self.next();
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut left = self.parse_unary()?;
+        while self.peek().kind == TokenKind::AndAnd {
+            self.next();
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr> {
+        if self.peek().kind == TokenKind::Bang {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr> {
+        let token = self.next();
+        match token.kind {
+            TokenKind::LParen => {
+                let expr = self.parse_or()?;
+                let close = self.next();
+                if close.kind != TokenKind::RParen {
+                    return Err(Error::invalid_filter(
+                        close.column,
+                        format!(
+                            "expected ')' to close '(' at column {}, found {}",
+                            token.column, close.kind
+                        ),
+                    ));
+                }
+                Ok(expr)
+            }
+            TokenKind::Ident(ref ident) if ident == "true" => Ok(Expr::Const(true)),
+            TokenKind::Ident(ref ident) if ident == "false" => Ok(Expr::Const(false)),
+            TokenKind::Ident(ident) => {
+                let field = Field::from_name(&ident).ok_or_else(|| {
+                    let known: Vec<&str> = Field::ALL.iter().map(|f| f.name()).collect();
+                    Error::invalid_filter(
+                        token.column,
+                        format!(
+                            "unknown field '{}' (known fields: {})",
+                            ident,
+                            known.join(", ")
+                        ),
+                    )
+                })?;
+                self.parse_field(field)
+            }
+            other => Err(Error::invalid_filter(
+                token.column,
+                format!("unexpected {}, expected a field, '!' or '('", other),
+            )),
+        }
+    }
+
+    fn parse_field(&mut self, field: Field) -> Result<Expr> {
+        let op = match self.peek().kind {
+            TokenKind::Op(op) => op,
+            _ if field.field_type() == FieldType::Bool => return Ok(Expr::Flag(field)),
+            ref other => {
+                return Err(Error::invalid_filter(
+                    self.peek().column,
+                    format!(
+                        "expected a comparison operator after '{}', found {}",
+                        field, other
+                    ),
+                ))
+            }
+        };
+        let op_column = self.next().column;
+
+        let value_token = self.next();
+        let value = match value_token.kind {
+            TokenKind::Str(s) => Literal::Str(s),
+            TokenKind::Int(i) => Literal::Int(i),
+            TokenKind::Ident(ref b) if b == "true" => Literal::Bool(true),
+            TokenKind::Ident(ref b) if b == "false" => Literal::Bool(false),
+            other => {
+                return Err(Error::invalid_filter(
+                    value_token.column,
+                    format!("expected a value after '{}', found {}", op.symbol(), other),
+                ))
+            }
+        };
+
+        let allowed: &[CompareOp] = match field.field_type() {
+            FieldType::Text => &[
+                CompareOp::Eq,
+                CompareOp::Ne,
+                CompareOp::Match,
+                CompareOp::NotMatch,
+            ],
+            FieldType::Bool => &[CompareOp::Eq, CompareOp::Ne],
+            FieldType::Int => &[
+                CompareOp::Eq,
+                CompareOp::Ne,
+                CompareOp::Lt,
+                CompareOp::Le,
+                CompareOp::Gt,
+                CompareOp::Ge,
+            ],
+        };
+        if !allowed.contains(&op) {
+            return Err(Error::invalid_filter(
+                op_column,
+                format!(
+                    "operator '{}' cannot be used with field '{}'",
+                    op.symbol(),
+                    field
+                ),
+            ));
+        }
+
+        let type_ok = matches!(
+            (field.field_type(), &value),
+            (FieldType::Text, Literal::Str(_))
+                | (FieldType::Bool, Literal::Bool(_))
+                | (FieldType::Int, Literal::Int(_))
+        );
+        if !type_ok {
+            let expected = match field.field_type() {
+                FieldType::Text => "a string",
+                FieldType::Bool => "true or false",
+                FieldType::Int => "an integer",
+            };
+            return Err(Error::invalid_filter(
+                value_token.column,
+                format!("field '{}' must be compared with {}", field, expected),
+            ));
+        }
+
+        Ok(Expr::Compare { field, op, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, remotes: &[(&str, &str)]) -> GitProject {
+        remotes.iter().fold(
+            GitProject::test(format!("/home/user/{}", name)),
+            |project, (remote, url)| project.with_named_remote(remote, url),
+        )
+    }
+
+    fn column_of(err: Error) -> usize {
+        match err {
+            Error::InvalidFilter { column, .. } => column,
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_precedence() {
+        let filter =
+            Filter::parse(r#"service == "github" && !has_remotes || is_submodule"#).unwrap();
+        match filter.expr() {
+            Expr::Or(left, right) => {
+                assert!(matches!(**left, Expr::And(_, _)));
+                assert_eq!(**right, Expr::Flag(Field::IsSubmodule));
+            }
+            other => panic!("unexpected tree: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_matches_any_remote() {
+        let p = project(
+            "tool",
+            &[
+                ("origin", "git@gitlab.com:me/tool.git"),
+                ("upstream", "https://github.com/acme/tool.git"),
+            ],
+        );
+
+        let filter: Filter = r#"service == "github" && account ~ "AC*""#.parse().unwrap();
+        assert!(filter.matches(&p));
+
+        let filter: Filter = r#"service != "github""#.parse().unwrap();
+        assert!(!filter.matches(&p));
+
+        let filter: Filter = r#"remote_count >= 2 && is_fork"#.parse().unwrap();
+        assert!(filter.matches(&p));
+    }
+
+    #[test]
+    fn test_missing_values() {
+        let p = project("local-only", &[]);
+
+        assert!(!Filter::parse(r#"account == "acme""#).unwrap().matches(&p));
+        assert!(Filter::parse(r#"account != "acme""#).unwrap().matches(&p));
+        assert!(Filter::parse("!has_remotes").unwrap().matches(&p));
+        assert!(Filter::parse(r#"(name ~ "local*") && remote_count == 0"#)
+            .unwrap()
+            .matches(&p));
+    }
+
+    #[test]
+    fn test_tags_and_notes() {
+        let mut p = project("tool", &[]);
+        p.tags = vec!["client-x".to_string(), "oncall".to_string()];
+
+        assert!(Filter::parse(r#"tag == "oncall""#).unwrap().matches(&p));
+        assert!(Filter::parse(r#"tag ~ "client-*" && tag != "archived""#)
+            .unwrap()
+            .matches(&p));
+        assert!(!Filter::parse(r#"notes ~ "*""#).unwrap().matches(&p));
+        p.notes = Some("Deployed weekly".to_string());
+        assert!(Filter::parse(r#"notes ~ "*weekly""#).unwrap().matches(&p));
+    }
+
+    #[test]
+    fn test_error_columns() {
+        assert_eq!(column_of(Filter::parse(r#"name = "x""#).unwrap_err()), 6);
+        assert_eq!(column_of(Filter::parse(r#"nmae == "x""#).unwrap_err()), 1);
+        assert_eq!(column_of(Filter::parse(r#"name == "x"#).unwrap_err()), 9);
+        assert_eq!(
+            column_of(Filter::parse(r#"is_fork == "yes""#).unwrap_err()),
+            12
+        );
+        assert_eq!(column_of(Filter::parse(r#"name < "x""#).unwrap_err()), 6);
+        assert_eq!(column_of(Filter::parse("(is_fork").unwrap_err()), 9);
+        assert_eq!(column_of(Filter::parse("name").unwrap_err()), 5);
+        assert_eq!(
+            column_of(Filter::parse("is_fork is_submodule").unwrap_err()),
+            9
+        );
+    }
+
+    #[test]
+    fn test_error_message() {
+        let err = Filter::parse("name # 1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid filter at column 6: unexpected character '#'"
+        );
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        let filter = Filter::parse(r#"name == "a\"b""#).unwrap();
+        assert_eq!(
+            *filter.expr(),
+            Expr::Compare {
+                field: Field::Name,
+                op: CompareOp::Eq,
+                value: Literal::Str("a\"b".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("acme*", "Acme-Corp"));
+        assert!(glob_match("*tool", "my-tool"));
+        assert!(glob_match("t?ol", "tool"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "aXXbYYc"));
+        assert!(!glob_match("acme", "acme-corp"));
+        assert!(!glob_match("a*b", "aXXc"));
+    }
+}
@@ -0,0 +1,253 @@
+//! Detection of fork/upstream relationships between remotes.
+//!
+//! A common setup is `origin` pointing to a personal fork and `upstream`
+//! pointing to the organization's repository. This module derives that
+//! relationship from the configured [`RemoteUrl`]s only; no network access
+//! is performed, so the result is a best-effort guess based on remote names
+//! and URL structure.
+
+use crate::identity::RemoteIdentity;
+use crate::models::{GitProject, RemoteUrl};
+use serde::{Deserialize, Serialize};
+
+/// Remote names that conventionally point to the canonical repository.
+const UPSTREAM_NAMES: &[&str] = &["upstream", "parent"];
+
+/// The relationship between a fork remote and its canonical upstream remote.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ForkRelation {
+    /// The remote pointing to the canonical repository (e.g., `upstream`).
+    pub upstream: RemoteUrl,
+
+    /// The normalized identity of the upstream remote.
+    pub upstream_identity: RemoteIdentity,
+
+    /// The remote pointing to the fork (e.g., `origin`).
+    pub fork: RemoteUrl,
+
+    /// The normalized identity of the fork remote.
+    pub fork_identity: RemoteIdentity,
+
+    /// Which of the two remotes the checked-out branch tracks.
+    ///
+    /// `None` if the branch has no upstream configured or `HEAD` is unknown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracking: Option<TrackingRemote>,
+}
+
+/// The remote tracked by the checked-out branch of a fork.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackingRemote {
+    /// The branch tracks the canonical upstream remote.
+    Upstream,
+
+    /// The branch tracks the fork remote.
+    Fork,
+
+    /// The branch tracks some other remote (by name).
+    Other(String),
+}
+
+/// Derives the fork/upstream relationship of a project from its remotes.
+///
+/// The canonical upstream is determined as follows:
+/// 1. A remote named `upstream` (or `parent`), if present.
+/// 2. Otherwise, another remote on the same host with the same repository
+///    name as `origin`, but a different owner.
+///
+/// The fork is `origin` (or the first other remote if there is no `origin`).
+/// Returns `None` if no such pair exists, or if both remotes point to the
+/// same repository.
+///
+/// # Example
+///
+/// ```
+/// # use git_projects_core::{detect_fork, GitProject, RemoteUrl};
+/// # use std::path::PathBuf;
+/// let remote = |name: &str, url: &str| RemoteUrl {
+///     name: name.to_string(),
+///     url: url.to_string(),
+///     service: None,
+///     account: None,
+/// };
+/// let project = GitProject {
+///     name: "tool".to_string(),
+///     path: PathBuf::from("/home/user/tool"),
+///     scan_root: None,
+///     relative_path: None,
+///     remotes: vec![
+///         remote("origin", "git@github.com:me/tool.git"),
+///         remote("upstream", "https://github.com/acme/tool.git"),
+///     ],
+///     config: None,
+///     is_submodule: false,
+///     has_submodules: false,
+///     last_scanned: chrono::Utc::now(),
+///     head: None,
+///     tags: vec![],
+///     notes: None,
+/// };
+///
+/// let relation = detect_fork(&project).unwrap();
+/// assert_eq!(relation.upstream_identity.slug(), "acme/tool");
+/// assert_eq!(relation.fork.name, "origin");
+/// ```
+pub fn detect_fork(project: &GitProject) -> Option<ForkRelation> {
+    let identified: Vec<(&RemoteUrl, RemoteIdentity)> = project
+        .remotes
+        .iter()
+        .filter_map(|r| RemoteIdentity::parse(&r.url).map(|id| (r, id)))
+        .collect();
+
+    let by_name = |name: &str| identified.iter().find(|(r, _)| r.name == name);
+
+    let (upstream, upstream_identity) = match identified
+        .iter()
+        .find(|(r, _)| UPSTREAM_NAMES.contains(&r.name.as_str()))
+    {
+        Some(found) => found,
+        None => {
+            // No conventional name: look for a sibling of origin under another owner
+            let (_, origin_id) = by_name("origin")?;
+            identified.iter().find(|(r, id)| {
+                r.name != "origin"
+                    && id.host == origin_id.host
+                    && id.name == origin_id.name
+                    && id.owner != origin_id.owner
+            })?
+        }
+    };
+
+    let (fork, fork_identity) = by_name("origin").or_else(|| {
+        identified
+            .iter()
+            .find(|(r, id)| r.name != upstream.name && id != upstream_identity)
+    })?;
+
+    if fork_identity == upstream_identity {
+        return None;
+    }
+
+    let tracking = project
+        .head
+        .as_ref()
+        .and_then(|h| h.upstream_remote.as_deref())
+        .map(|name| {
+            if name == upstream.name {
+                TrackingRemote::Upstream
+            } else if name == fork.name {
+                TrackingRemote::Fork
+            } else {
+                TrackingRemote::Other(name.to_string())
+            }
+        });
+
+    Some(ForkRelation {
+        upstream: (*upstream).clone(),
+        upstream_identity: upstream_identity.clone(),
+        fork: (*fork).clone(),
+        fork_identity: fork_identity.clone(),
+        tracking,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::HeadInfo;
+
+    fn project(remotes: &[(&str, &str)], tracks: Option<&str>) -> GitProject {
+        let project = GitProject {
+            head: Some(HeadInfo {
+                branch: Some("main".to_string()),
+                commit: None,
+                upstream_remote: tracks.map(str::to_string),
+            }),
+            ..GitProject::test("/home/user/tool")
+        };
+        remotes.iter().fold(project, |project, (name, url)| {
+            project.with_named_remote(name, url)
+        })
+    }
+
+    #[test]
+    fn test_origin_and_upstream() {
+        let p = project(
+            &[
+                ("origin", "git@github.com:me/tool.git"),
+                ("upstream", "https://github.com/acme/tool.git"),
+            ],
+            Some("upstream"),
+        );
+
+        let relation = detect_fork(&p).unwrap();
+        assert_eq!(relation.upstream.name, "upstream");
+        assert_eq!(relation.fork.name, "origin");
+        assert_eq!(relation.upstream_identity.slug(), "acme/tool");
+        assert_eq!(relation.tracking, Some(TrackingRemote::Upstream));
+    }
+
+    #[test]
+    fn test_sibling_remote_without_upstream_name() {
+        let p = project(
+            &[
+                ("origin", "git@github.com:me/tool.git"),
+                ("acme", "https://github.com/acme/tool.git"),
+            ],
+            Some("origin"),
+        );
+
+        let relation = detect_fork(&p).unwrap();
+        assert_eq!(relation.upstream.name, "acme");
+        assert_eq!(relation.tracking, Some(TrackingRemote::Fork));
+    }
+
+    #[test]
+    fn test_single_remote_is_not_a_fork() {
+        let p = project(&[("origin", "git@github.com:acme/tool.git")], None);
+        assert_eq!(detect_fork(&p), None);
+    }
+
+    #[test]
+    fn test_same_repository_is_not_a_fork() {
+        let p = project(
+            &[
+                ("origin", "git@github.com:acme/tool.git"),
+                ("upstream", "https://github.com/acme/tool"),
+            ],
+            None,
+        );
+        assert_eq!(detect_fork(&p), None);
+    }
+
+    #[test]
+    fn test_unrelated_remotes_are_not_a_fork() {
+        let p = project(
+            &[
+                ("origin", "git@github.com:me/tool.git"),
+                ("mirror", "https://gitlab.com/me/tool.git"),
+            ],
+            None,
+        );
+        assert_eq!(detect_fork(&p), None);
+    }
+
+    #[test]
+    fn test_tracking_other_remote() {
+        let p = project(
+            &[
+                ("origin", "git@github.com:me/tool.git"),
+                ("upstream", "https://github.com/acme/tool.git"),
+                ("backup", "https://example.com/me/tool.git"),
+            ],
+            Some("backup"),
+        );
+
+        let relation = detect_fork(&p).unwrap();
+        assert_eq!(
+            relation.tracking,
+            Some(TrackingRemote::Other("backup".to_string()))
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! Frecency of visited projects.
+//!
+//! Projects picked with `projects-cli pick` are remembered in a
+//! [`FrecencyStore`], so that frequently and recently used ones rank higher
+//! the next time. The store is a JSON file in the data directory,
+//! `~/.local/share/git-projects/frecency.json`.
+//!
+//! A visit counts four times within the first hour, twice within a day, half
+//! within a week and a quarter after that. When the counts add up to more
+//! than [`MAX_TOTAL`], all of them are scaled down and projects not visited
+//! in a long time are forgotten.
+
+use crate::error::Result;
+use crate::fuzzy;
+use crate::models::GitProject;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Name of the frecency file in the data directory.
+pub const FRECENCY_FILE: &str = "frecency.json";
+
+/// Sum of all visit counts above which the counts are aged.
+pub const MAX_TOTAL: f64 = 1000.0;
+
+/// Rank points per e-fold of frecency; one fuzzy match character is 16.
+const BOOST_WEIGHT: f64 = 16.0;
+
+/// Visits of one project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Visits {
+    /// Number of visits, reduced as the store ages.
+    pub count: f64,
+
+    /// Time of the last visit.
+    pub last_visit: DateTime<Utc>,
+}
+
+/// Visit counts of projects, keyed by path.
+///
+/// # Example
+///
+/// ```
+/// use chrono::Utc;
+/// use git_projects_core::FrecencyStore;
+/// use std::path::Path;
+///
+/// let mut store = FrecencyStore::default();
+/// let now = Utc::now();
+/// store.record(Path::new("/src/tool"), now);
+/// assert!(store.score(Path::new("/src/tool"), now) > store.score(Path::new("/src/other"), now));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrecencyStore {
+    entries: BTreeMap<PathBuf, Visits>,
+}
+
+impl FrecencyStore {
+    /// Returns the path of the frecency file in the data directory.
+    ///
+    /// `$XDG_DATA_HOME/git-projects`, or `~/.local/share/git-projects`.
+    pub fn default_path() -> Option<PathBuf> {
+        crate::config::data_dir().map(|dir| dir.join(FRECENCY_FILE))
+    }
+
+    /// Loads the store from a file.
+    ///
+    /// Returns an empty store if the file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`](crate::Error::Io) if the file cannot be read, or
+    /// [`Error::Json`](crate::Error::Json) if it is malformed.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let source = std::fs::read_to_string(path)?;
+        Ok(Self {
+            entries: serde_json::from_str(&source)?,
+        })
+    }
+
+    /// Writes the store to a file, creating its directory if needed.
+    ///
+    /// The file is replaced atomically, like the
+    /// [metadata store](crate::MetadataStore::save).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`](crate::Error::Io) if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let source = serde_json::to_string_pretty(&self.entries)?;
+        crate::config::write_atomic(path, &source)?;
+        Ok(())
+    }
+
+    /// Returns the visits of a project, if it was ever visited.
+    pub fn get(&self, path: &Path) -> Option<&Visits> {
+        self.entries.get(path)
+    }
+
+    /// Records a visit of the project at `path`.
+    pub fn record(&mut self, path: &Path, now: DateTime<Utc>) {
+        let visits = self.entries.entry(path.to_path_buf()).or_insert(Visits {
+            count: 0.0,
+            last_visit: now,
+        });
+        visits.count += 1.0;
+        visits.last_visit = now;
+
+        let total: f64 = self.entries.values().map(|v| v.count).sum();
+        if total > MAX_TOTAL {
+            for visits in self.entries.values_mut() {
+                visits.count *= 0.9;
+            }
+            self.entries.retain(|_, visits| visits.count >= 1.0);
+        }
+    }
+
+    /// Returns the frecency of a project: its visit count weighted by how
+    /// recently it was visited, or 0 if it was never visited.
+    pub fn score(&self, path: &Path, now: DateTime<Utc>) -> f64 {
+        let Some(visits) = self.entries.get(path) else {
+            return 0.0;
+        };
+        let age = now - visits.last_visit;
+        let weight = if age < Duration::hours(1) {
+            4.0
+        } else if age < Duration::days(1) {
+            2.0
+        } else if age < Duration::weeks(1) {
+            0.5
+        } else {
+            0.25
+        };
+        visits.count * weight
+    }
+
+    /// Returns the frecency of a project on the scale of
+    /// [fuzzy scores](crate::fuzzy::score), to add to them.
+    pub fn boost(&self, path: &Path, now: DateTime<Utc>) -> i64 {
+        (BOOST_WEIGHT * self.score(path, now).ln_1p()).round() as i64
+    }
+
+    /// Sorts the projects whose name matches `query` by fuzzy score plus
+    /// frecency [boost](Self::boost), best first.
+    ///
+    /// An empty query matches every project, so they are ordered by
+    /// frecency alone. Ties keep their original order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use git_projects_core::{FrecencyStore, GitProject};
+    /// # fn project(path: &str) -> GitProject {
+    /// #     GitProject::test(path)
+    /// # }
+    ///
+    /// let projects = [project("/src/tool"), project("/src/tools")];
+    /// let mut store = FrecencyStore::default();
+    /// let now = Utc::now();
+    /// for _ in 0..5 {
+    ///     store.record(&projects[1].path, now);
+    /// }
+    /// assert_eq!(store.rank("tool", &projects, now)[0].name, "tools");
+    /// ```
+    pub fn rank<'a>(
+        &self,
+        query: &str,
+        projects: &'a [GitProject],
+        now: DateTime<Utc>,
+    ) -> Vec<&'a GitProject> {
+        let mut ranked: Vec<(i64, &GitProject)> = fuzzy::rank(query, projects, |p| &p.name)
+            .into_iter()
+            .map(|(score, project)| (score + self.boost(&project.path, now), project))
+            .collect();
+        ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        ranked.into_iter().map(|(_, project)| project).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_decays_with_age() {
+        let mut store = FrecencyStore::default();
+        let path = Path::new("/src/tool");
+        let then = Utc::now();
+        store.record(path, then);
+        store.record(path, then);
+
+        assert_eq!(store.score(path, then), 8.0);
+        assert_eq!(store.score(path, then + Duration::hours(2)), 4.0);
+        assert_eq!(store.score(path, then + Duration::days(2)), 1.0);
+        assert_eq!(store.score(path, then + Duration::weeks(2)), 0.5);
+        assert_eq!(store.score(Path::new("/src/other"), then), 0.0);
+    }
+
+    #[test]
+    fn test_aging() {
+        let mut store = FrecencyStore::default();
+        let now = Utc::now();
+        store.record(Path::new("/src/once"), now);
+        for _ in 0..MAX_TOTAL as usize {
+            store.record(Path::new("/src/often"), now);
+        }
+
+        assert!(store.get(Path::new("/src/once")).is_none());
+        let often = store.get(Path::new("/src/often")).unwrap();
+        assert!(often.count < MAX_TOTAL);
+    }
+
+    #[test]
+    fn test_rank() {
+        let projects = [
+            GitProject::test("/src/git-projects"),
+            GitProject::test("/src/git-projects-core"),
+            GitProject::test("/src/tool"),
+        ];
+        let now = Utc::now();
+        let mut store = FrecencyStore::default();
+        let names = |store: &FrecencyStore, query| -> Vec<String> {
+            store
+                .rank(query, &projects, now)
+                .into_iter()
+                .map(|p| p.name.clone())
+                .collect()
+        };
+
+        assert_eq!(names(&store, "gp"), ["git-projects", "git-projects-core"]);
+        store.record(&projects[1].path, now);
+        assert_eq!(names(&store, "gp"), ["git-projects-core", "git-projects"]);
+        assert_eq!(
+            names(&store, ""),
+            ["git-projects-core", "git-projects", "tool"]
+        );
+        assert!(names(&store, "zzz").is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("state").join(FRECENCY_FILE);
+        assert_eq!(
+            FrecencyStore::load(&path).unwrap(),
+            FrecencyStore::default()
+        );
+
+        let mut store = FrecencyStore::default();
+        store.record(Path::new("/src/tool"), Utc::now());
+        store.save(&path).unwrap();
+        assert_eq!(FrecencyStore::load(&path).unwrap(), store);
+    }
+}
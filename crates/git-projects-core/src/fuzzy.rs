@@ -0,0 +1,151 @@
+//! Fuzzy matching of project names.
+//!
+//! A pattern matches a text if its characters appear in the text in order,
+//! ignoring case. [`score`] rates how well: consecutive characters and
+//! characters at word boundaries (`git-projects`, `gitProjects`) count more,
+//! gaps and unmatched characters count less. [`rank`] sorts candidates by
+//! that score.
+
+/// Score for each matched character.
+const MATCH: i64 = 16;
+/// Bonus for a character directly following the previous match.
+const CONSECUTIVE: i64 = 16;
+/// Bonus for a character at the start of a word.
+const BOUNDARY: i64 = 12;
+/// Penalty for skipping characters between two matches.
+const GAP: i64 = 4;
+
+/// Scores how well `pattern` matches `text`, or `None` if it doesn't.
+///
+/// Higher is better. An empty pattern matches everything with score 0.
+///
+/// # Example
+///
+/// ```
+/// use git_projects_core::fuzzy::score;
+///
+/// assert!(score("gpc", "git-projects-core") > score("gpc", "graphic"));
+/// assert_eq!(score("xyz", "git-projects-core"), None);
+/// ```
+pub fn score(pattern: &str, text: &str) -> Option<i64> {
+    let pattern: Vec<char> = pattern.chars().map(fold).collect();
+    if pattern.is_empty() {
+        return Some(0);
+    }
+    let original: Vec<char> = text.chars().collect();
+    let folded: Vec<char> = original.iter().copied().map(fold).collect();
+    if pattern.len() > folded.len() {
+        return None;
+    }
+
+    // best[j]: the best score with the current pattern character at text[j]
+    let mut best: Vec<Option<i64>> = vec![None; folded.len()];
+    for (i, &wanted) in pattern.iter().enumerate() {
+        let mut current = vec![None; folded.len()];
+        // The best score of the previous character at least two positions back
+        let mut gapped: Option<i64> = None;
+        for j in 0..folded.len() {
+            if folded[j] == wanted {
+                let bonus = MATCH
+                    + if is_boundary(&original, j) {
+                        BOUNDARY
+                    } else {
+                        0
+                    };
+                current[j] = if i == 0 {
+                    Some(bonus)
+                } else {
+                    let adjacent = j
+                        .checked_sub(1)
+                        .and_then(|k| best[k])
+                        .map(|s| s + CONSECUTIVE);
+                    adjacent.max(gapped.map(|s| s - GAP)).map(|s| s + bonus)
+                };
+            }
+            if let Some(previous) = j.checked_sub(1).and_then(|k| best[k]) {
+                gapped = gapped.max(Some(previous));
+            }
+        }
+        best = current;
+    }
+
+    let unmatched = (folded.len() - pattern.len()) as i64;
+    best.into_iter().flatten().max().map(|s| s - unmatched)
+}
+
+/// Sorts the items matching `pattern` by descending score.
+///
+/// Items that don't match are dropped; ties keep their original order.
+///
+/// # Example
+///
+/// ```
+/// use git_projects_core::fuzzy::rank;
+///
+/// let names = ["graphic", "git-projects-core", "tool"];
+/// let ranked: Vec<_> = rank("gpc", &names, |name| name)
+///     .into_iter()
+///     .map(|(_, name)| *name)
+///     .collect();
+/// assert_eq!(ranked, ["git-projects-core", "graphic"]);
+/// ```
+pub fn rank<'a, T>(pattern: &str, items: &'a [T], key: impl Fn(&T) -> &str) -> Vec<(i64, &'a T)> {
+    let mut ranked: Vec<(i64, &T)> = items
+        .iter()
+        .filter_map(|item| score(pattern, key(item)).map(|s| (s, item)))
+        .collect();
+    ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    ranked
+}
+
+/// Folds a character for case-insensitive comparison.
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Whether `chars[i]` starts a word.
+fn is_boundary(chars: &[char], i: usize) -> bool {
+    match i.checked_sub(1).map(|k| chars[k]) {
+        None => true,
+        Some(previous) => {
+            !previous.is_alphanumeric() || (previous.is_lowercase() && chars[i].is_uppercase())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching() {
+        assert_eq!(score("", "anything"), Some(0));
+        assert!(score("GPC", "git-projects-core").is_some());
+        assert!(score("core", "git-projects-core").is_some());
+        assert_eq!(score("cg", "git-projects-core"), None);
+        assert_eq!(score("toolong", "tool"), None);
+    }
+
+    #[test]
+    fn test_ranking() {
+        // Word starts beat scattered matches
+        assert!(score("gp", "git-projects") > score("gp", "gap"));
+        assert!(score("gp", "gitProjects") > score("gp", "gitprojects"));
+        // Consecutive characters beat gaps
+        assert!(score("tool", "tool-box") > score("tool", "t-o-o-l"));
+        // Shorter names win when everything else is equal
+        assert!(score("tool", "tool") > score("tool", "tools"));
+        // The best alignment is found, not the first one
+        assert!(score("core", "c-o-r-e-core") > score("core", "c-o-r-e"));
+    }
+
+    #[test]
+    fn test_rank_keeps_order_of_ties() {
+        let names = ["b-tool", "a-tool", "other"];
+        let ranked: Vec<_> = rank("tool", &names, |n| n)
+            .into_iter()
+            .map(|(_, n)| *n)
+            .collect();
+        assert_eq!(ranked, ["b-tool", "a-tool"]);
+    }
+}
@@ -5,8 +5,11 @@
 //! high-level, ergonomic APIs.
 
 use crate::error::{Error, Result};
-use crate::models::{ConfigScope, GitConfig, RemoteUrl};
-use std::path::Path;
+use crate::models::{
+    BranchInfo, ConfigEntry, ConfigScope, GitConfig, HeadInfo, IdentityDetails, RemoteDetails,
+    RemoteUrl, RepoStatus, SubmoduleInfo,
+};
+use std::path::{Path, PathBuf};
 
 /// Extracts all configured remote URLs from a Git repository.
 ///
@@ -137,32 +140,363 @@ pub fn extract_git_config(repo_path: &Path) -> Result<GitConfig> {
     })
 }
 
+/// Extracts the state of `HEAD` (checked-out branch and commit id).
+///
+/// # Arguments
+///
+/// * `repo_path` - Path to the Git repository
+///
+/// # Returns
+///
+/// A [`HeadInfo`] with the short branch name (unless `HEAD` is detached),
+/// the commit id (unless the branch is unborn) and the remote the branch
+/// tracks (if configured).
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The repository cannot be opened
+/// - The `HEAD` reference cannot be read
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::extract_head;
+/// use std::path::Path;
+///
+/// let head = extract_head(Path::new("/path/to/repo"))?;
+/// println!("On branch {}", head.branch.unwrap_or_default());
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn extract_head(repo_path: &Path) -> Result<HeadInfo> {
+    let repo = gix::open(repo_path).map_err(|e| Error::git_open(repo_path, e))?;
+
+    let head = repo
+        .head()
+        .map_err(|e| Error::git_head(repo_path, e.to_string()))?;
+
+    let branch = head.referent_name().map(|name| name.shorten().to_string());
+
+    // Unborn branches have no commit yet, which is not an error
+    let commit = head.id().map(|id| id.to_string());
+
+    // The remote configured via `branch.<name>.remote`, if any
+    let upstream_remote = branch.as_deref().and_then(|name| {
+        repo.branch_remote_name(name, gix::remote::Direction::Fetch)
+            .map(|remote| remote.as_bstr().to_string())
+    });
+
+    Ok(HeadInfo {
+        branch,
+        commit,
+        upstream_remote,
+    })
+}
+
+/// Determines the working tree status of a Git repository.
+///
+/// Checks for uncommitted changes to tracked files and counts the commits
+/// between `HEAD` and the remote-tracking branch of the checked-out branch.
+/// Bare repositories and unborn branches are reported as clean.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be opened, or the index, working
+/// tree or commit graph cannot be read.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::extract_status;
+/// use std::path::Path;
+///
+/// let status = extract_status(Path::new("/path/to/repo"))?;
+/// if status.dirty {
+///     println!("Uncommitted changes");
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn extract_status(repo_path: &Path) -> Result<RepoStatus> {
+    let repo = gix::open(repo_path).map_err(|e| Error::git_open(repo_path, e))?;
+    let status_error = |e: &dyn std::fmt::Display| Error::git_status(repo_path, e.to_string());
+
+    let head = repo
+        .head()
+        .map_err(|e| Error::git_head(repo_path, e.to_string()))?;
+    let Some(head_id) = head.id().map(|id| id.detach()) else {
+        // Nothing to compare with before the first commit
+        return Ok(RepoStatus::default());
+    };
+
+    let dirty = if repo.workdir().is_some() {
+        repo.is_dirty().map_err(|e| status_error(&e))?
+    } else {
+        false
+    };
+
+    // The remote-tracking branch (`refs/remotes/origin/main`), if configured
+    let tracking = head.referent_name().and_then(|name| {
+        repo.branch_remote_tracking_ref_name(name, gix::remote::Direction::Fetch)
+            .and_then(|name| name.ok())
+            .map(|name| name.into_owned())
+    });
+    let Some(tracking) = tracking else {
+        return Ok(RepoStatus {
+            dirty,
+            ..RepoStatus::default()
+        });
+    };
+    let upstream = Some(tracking.shorten().to_string());
+
+    // Not fetched yet
+    let Some(mut reference) = repo
+        .try_find_reference(tracking.as_ref())
+        .map_err(|e| status_error(&e))?
+    else {
+        return Ok(RepoStatus {
+            dirty,
+            upstream,
+            ..RepoStatus::default()
+        });
+    };
+    let upstream_id = reference
+        .peel_to_id()
+        .map_err(|e| status_error(&e))?
+        .detach();
+
+    let count = |tip: gix::ObjectId, hidden: gix::ObjectId| -> Result<usize> {
+        let walk = repo
+            .rev_walk([tip])
+            .with_hidden([hidden])
+            .all()
+            .map_err(|e| status_error(&e))?;
+        let mut count = 0;
+        for commit in walk {
+            commit.map_err(|e| status_error(&e))?;
+            count += 1;
+        }
+        Ok(count)
+    };
+
+    Ok(RepoStatus {
+        dirty,
+        upstream,
+        ahead: Some(count(head_id, upstream_id)?),
+        behind: Some(count(upstream_id, head_id)?),
+    })
+}
+
+/// Extracts all remotes with their fetch and push URLs.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be opened.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::extract_remote_details;
+/// use std::path::Path;
+///
+/// for remote in extract_remote_details(Path::new("/path/to/repo"))? {
+///     println!("{} pushes to {:?}", remote.name, remote.push_url);
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn extract_remote_details(repo_path: &Path) -> Result<Vec<RemoteDetails>> {
+    let repo = gix::open(repo_path).map_err(|e| Error::git_open(repo_path, e))?;
+
+    let mut remotes = Vec::new();
+    for name in repo.remote_names() {
+        let name_str = name.as_ref();
+        // Skip remotes that can't be loaded, as extract_remote_urls does
+        let Ok(remote) = repo.find_remote(name_str) else {
+            continue;
+        };
+        let url = |direction| {
+            remote
+                .url(direction)
+                .map(|url| url.to_bstring().to_string())
+        };
+        remotes.push(RemoteDetails {
+            name: name_str.to_string(),
+            fetch_url: url(gix::remote::Direction::Fetch),
+            push_url: url(gix::remote::Direction::Push),
+        });
+    }
+
+    Ok(remotes)
+}
+
+/// Extracts `user.name` and `user.email` with the scope and file that set them.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be opened.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::extract_identity;
+/// use std::path::Path;
+///
+/// let identity = extract_identity(Path::new("/path/to/repo"))?;
+/// if let Some(email) = identity.email {
+///     println!("{} (from {:?})", email.value, email.file);
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn extract_identity(repo_path: &Path) -> Result<IdentityDetails> {
+    let repo = gix::open(repo_path).map_err(|e| Error::git_open(repo_path, e))?;
+    let config = repo.config_snapshot();
+
+    Ok(IdentityDetails {
+        name: config_entry(&config, "user", "name"),
+        email: config_entry(&config, "user", "email"),
+    })
+}
+
+/// Extracts the local branches with their commits and upstreams.
+///
+/// Branches are ordered by name.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be opened or its references
+/// cannot be read.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::extract_branches;
+/// use std::path::Path;
+///
+/// for branch in extract_branches(Path::new("/path/to/repo"))? {
+///     let marker = if branch.is_head { "*" } else { " " };
+///     println!("{} {}", marker, branch.name);
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn extract_branches(repo_path: &Path) -> Result<Vec<BranchInfo>> {
+    let repo = gix::open(repo_path).map_err(|e| Error::git_open(repo_path, e))?;
+    let details_error = |e: &dyn std::fmt::Display| Error::git_details(repo_path, e.to_string());
+
+    let head_name = repo.head_name().map_err(|e| details_error(&e))?;
+    let references = repo.references().map_err(|e| details_error(&e))?;
+
+    let mut branches = Vec::new();
+    for reference in references.local_branches().map_err(|e| details_error(&e))? {
+        let mut reference = reference.map_err(|e| details_error(&e))?;
+        let name = reference.name().to_owned();
+        let commit = reference.peel_to_id().ok().map(|id| id.to_string());
+        let upstream = repo
+            .branch_remote_tracking_ref_name(name.as_ref(), gix::remote::Direction::Fetch)
+            .and_then(|tracking| tracking.ok())
+            .map(|tracking| tracking.shorten().to_string());
+        branches.push(BranchInfo {
+            name: name.shorten().to_string(),
+            commit,
+            upstream,
+            is_head: head_name.as_ref() == Some(&name),
+        });
+    }
+
+    Ok(branches)
+}
+
+/// Extracts the submodules declared in `.gitmodules`.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be opened or `.gitmodules` is
+/// malformed.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::extract_submodules;
+/// use std::path::Path;
+///
+/// for submodule in extract_submodules(Path::new("/path/to/repo"))? {
+///     println!("{}: {:?}", submodule.path.display(), submodule.url);
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn extract_submodules(repo_path: &Path) -> Result<Vec<SubmoduleInfo>> {
+    let repo = gix::open(repo_path).map_err(|e| Error::git_open(repo_path, e))?;
+    let details_error = |e: &dyn std::fmt::Display| Error::git_details(repo_path, e.to_string());
+
+    let Some(submodules) = repo.submodules().map_err(|e| details_error(&e))? else {
+        return Ok(Vec::new());
+    };
+
+    let mut result = Vec::new();
+    for submodule in submodules {
+        let path = submodule.path().map_err(|e| details_error(&e))?;
+        result.push(SubmoduleInfo {
+            name: submodule.name().to_string(),
+            path: PathBuf::from(path.to_string()),
+            url: submodule.url().ok().map(|url| url.to_bstring().to_string()),
+        });
+    }
+
+    Ok(result)
+}
+
 /// Helper function to get a config value and determine its scope.
 ///
-/// Checks local, global, and system configs in order and returns
-/// the first found value along with its scope.
+/// The scope is that of the file that set the effective value.
 fn get_config_value_with_scope(
     config: &gix::config::Snapshot,
     key: &str,
 ) -> (Option<String>, Option<ConfigScope>) {
-    // Try to get the value from the merged config
-    // In gix 0.77, config.string() returns Option<Cow<BStr>>, not Result
-    if let Some(value) = config.string(key) {
-        let value_str = value.to_string();
+    if let Some((section, name)) = key.split_once('.') {
+        if let Some(entry) = config_entry(config, section, name) {
+            return (Some(entry.value), Some(entry.scope));
+        }
+    }
 
-        // Determine scope by checking which file it came from
-        // This is a simplified approach - gitoxide provides the merged view
-        // We'll try to determine scope by checking each level
+    // Values without a section of their own, e.g. set programmatically
+    match config.string(key) {
+        Some(value) => (Some(value.to_string()), Some(ConfigScope::Local)),
+        None => (None, None),
+    }
+}
 
-        // Check if it's in local config (repo-specific)
-        // In gix 0.77, we need to check the source metadata
-        // For now, we'll use a simplified heuristic: assume Local if found
-        // This could be improved by inspecting config.meta()
+/// Finds the effective value of `section.key` along with the file that set it.
+///
+/// Later sections override earlier ones, as in Git.
+fn config_entry(config: &gix::config::Snapshot, section: &str, key: &str) -> Option<ConfigEntry> {
+    config
+        .plumbing()
+        .sections_by_name(section)?
+        .filter(|section| section.header().subsection_name().is_none())
+        .filter_map(|section| {
+            let value = section.value(key)?;
+            let meta = section.meta();
+            Some(ConfigEntry {
+                value: value.to_string(),
+                scope: config_scope(meta.source),
+                file: meta.path.clone(),
+            })
+        })
+        .last()
+}
 
-        return (Some(value_str), Some(ConfigScope::Local));
+/// Maps the source of a configuration section to its scope.
+fn config_scope(source: gix::config::Source) -> ConfigScope {
+    use gix::config::Source;
+    match source {
+        Source::GitInstallation | Source::System => ConfigScope::System,
+        Source::Git | Source::User => ConfigScope::Global,
+        // Environment and command-line overrides apply to this repository only
+        Source::Local
+        | Source::Worktree
+        | Source::Env
+        | Source::Cli
+        | Source::Api
+        | Source::EnvOverride => ConfigScope::Local,
     }
-
-    (None, None)
 }
 
 /// Determines the overall config scope when we have multiple values.
@@ -401,4 +735,74 @@ mod tests {
         assert_eq!(service, None);
         assert_eq!(account, None);
     }
+
+    /// Initializes a repository without commits on `main`
+    fn init_repo() -> tempfile::TempDir {
+        let temp = tempfile::TempDir::new().unwrap();
+        gix::init(temp.path()).unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_extract_head_unborn_branch() {
+        let temp = init_repo();
+
+        let head = extract_head(temp.path()).unwrap();
+        assert_eq!(head.branch, Some("main".to_string()));
+        assert_eq!(head.commit, None);
+        assert_eq!(head.upstream_remote, None);
+    }
+
+    #[test]
+    fn test_status_of_unborn_branch() {
+        let temp = init_repo();
+
+        let status = extract_status(temp.path()).unwrap();
+        assert_eq!(status, RepoStatus::default());
+    }
+
+    #[test]
+    fn test_details_from_local_config() {
+        let temp = init_repo();
+        let config_path = temp.path().join(".git/config");
+        let mut config = std::fs::read_to_string(&config_path).unwrap();
+        config.push_str(
+            "[user]\n\temail = dev@example.com\n\
+             [remote \"origin\"]\n\turl = https://example.com/acme/tool.git\n\
+             \tpushurl = git@example.com:acme/tool.git\n",
+        );
+        std::fs::write(config_path, config).unwrap();
+        std::fs::write(
+            temp.path().join(".gitmodules"),
+            "[submodule \"vendor/lib\"]\n\tpath = vendor/lib\n\turl = https://example.com/lib.git\n",
+        )
+        .unwrap();
+
+        let remotes = extract_remote_details(temp.path()).unwrap();
+        assert_eq!(
+            remotes,
+            [RemoteDetails {
+                name: "origin".to_string(),
+                fetch_url: Some("https://example.com/acme/tool.git".to_string()),
+                push_url: Some("git@example.com:acme/tool.git".to_string()),
+            }]
+        );
+
+        let email = extract_identity(temp.path()).unwrap().email.unwrap();
+        assert_eq!(email.value, "dev@example.com");
+        assert_eq!(email.scope, ConfigScope::Local);
+        assert!(email.file.unwrap().ends_with(".git/config"));
+
+        assert!(extract_branches(temp.path()).unwrap().is_empty());
+
+        let submodules = extract_submodules(temp.path()).unwrap();
+        assert_eq!(
+            submodules,
+            [SubmoduleInfo {
+                name: "vendor/lib".to_string(),
+                path: PathBuf::from("vendor/lib"),
+                url: Some("https://example.com/lib.git".to_string()),
+            }]
+        );
+    }
 }
@@ -0,0 +1,355 @@
+//! Grouping of scan results into a tree.
+//!
+//! Projects are grouped by one or more [`GroupKey`]s, e.g. service, then
+//! account. The `dir` key expands to one level per directory component,
+//! starting at the deepest directory containing all projects, so it renders
+//! the directory hierarchy.
+//!
+//! Groups keep the order in which their first project appears in the input,
+//! so sort the projects first to control the group order. Groups without a
+//! value (e.g., projects without remotes when grouping by service) come last.
+
+use crate::error::{Error, Result};
+use crate::models::GitProject;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A property used to group projects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupKey {
+    /// Hosting service of the primary remote (e.g., `"github"`).
+    Service,
+    /// Account of the primary remote.
+    Account,
+    /// Normalized host of the primary remote (e.g., `"github.com"`).
+    Host,
+    /// Checked-out branch.
+    Branch,
+    /// Parent directories, one level per path component.
+    Dir,
+}
+
+impl GroupKey {
+    /// All group keys.
+    pub const ALL: &'static [GroupKey] = &[
+        GroupKey::Service,
+        GroupKey::Account,
+        GroupKey::Host,
+        GroupKey::Branch,
+        GroupKey::Dir,
+    ];
+
+    /// Returns the name used for this key in `--group-by` lists.
+    pub fn name(self) -> &'static str {
+        match self {
+            GroupKey::Service => "service",
+            GroupKey::Account => "account",
+            GroupKey::Host => "host",
+            GroupKey::Branch => "branch",
+            GroupKey::Dir => "dir",
+        }
+    }
+
+    /// Parses a comma-separated list of group keys (e.g., `"service,account"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidGroup`] if the list is empty or contains an
+    /// unknown key.
+    pub fn parse_list(s: &str) -> Result<Vec<GroupKey>> {
+        let keys: Vec<GroupKey> = s
+            .split(',')
+            .filter(|k| !k.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_>>()?;
+        if keys.is_empty() {
+            return Err(Error::invalid_group("no group keys given"));
+        }
+        Ok(keys)
+    }
+}
+
+impl FromStr for GroupKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        GroupKey::ALL
+            .iter()
+            .copied()
+            .find(|k| k.name() == s)
+            .ok_or_else(|| {
+                let known: Vec<&str> = GroupKey::ALL.iter().map(|k| k.name()).collect();
+                Error::invalid_group(format!(
+                    "unknown group key '{}' (known keys: {})",
+                    s,
+                    known.join(", ")
+                ))
+            })
+    }
+}
+
+impl fmt::Display for GroupKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A node in the group tree.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectGroup {
+    /// The key this group was formed by.
+    pub key: GroupKey,
+
+    /// The shared value of the key, or `None` for projects without a value.
+    pub value: Option<String>,
+
+    /// The total number of projects in this group, including subgroups.
+    pub count: usize,
+
+    /// Nested groups formed by the next key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<ProjectGroup>,
+
+    /// Projects that belong directly to this group.
+    ///
+    /// Only the innermost groups contain projects, except with the `dir` key,
+    /// where a directory can contain both projects and subdirectories.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<GitProject>,
+}
+
+/// Groups projects into a tree by the given keys.
+///
+/// Returns the top-level groups. With no keys, returns no groups.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::{group_projects, DefaultScanner, GroupKey, ProjectScanner, ScanConfig};
+///
+/// let projects = DefaultScanner::new().scan(&ScanConfig::default())?;
+/// for group in group_projects(&projects, &[GroupKey::Service]) {
+///     println!("{}: {}", group.value.as_deref().unwrap_or("-"), group.count);
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn group_projects(projects: &[GitProject], keys: &[GroupKey]) -> Vec<ProjectGroup> {
+    if keys.is_empty() {
+        return Vec::new();
+    }
+
+    let root = keys
+        .contains(&GroupKey::Dir)
+        .then(|| common_ancestor(projects))
+        .flatten();
+
+    // Flatten every project's path through the tree into a list of levels
+    let items: Vec<Item<'_>> = projects
+        .iter()
+        .map(|project| {
+            let levels = keys
+                .iter()
+                .flat_map(|&key| levels_for(key, project, root.as_deref()))
+                .collect();
+            (project, levels)
+        })
+        .collect();
+
+    let (groups, _) = build(items, 0);
+    groups
+}
+
+/// A single level of the tree: the key and the project's value for it.
+type Level = (GroupKey, Option<String>);
+
+/// A project together with its path through the tree.
+type Item<'a> = (&'a GitProject, Vec<Level>);
+
+/// Builds the groups at `depth`, returning them plus projects that end here.
+fn build(items: Vec<Item<'_>>, depth: usize) -> (Vec<ProjectGroup>, Vec<GitProject>) {
+    let mut here = Vec::new();
+    let mut buckets: Vec<(Level, Vec<Item<'_>>)> = Vec::new();
+
+    for (project, levels) in items {
+        match levels.get(depth).cloned() {
+            None => here.push(project.clone()),
+            Some(level) => match buckets.iter_mut().find(|(l, _)| *l == level) {
+                Some((_, bucket)) => bucket.push((project, levels)),
+                None => buckets.push((level, vec![(project, levels)])),
+            },
+        }
+    }
+
+    // Stable partition: groups without a value go last
+    buckets.sort_by_key(|((_, value), _)| value.is_none());
+
+    let groups = buckets
+        .into_iter()
+        .map(|((key, value), bucket)| {
+            let count = bucket.len();
+            let (groups, projects) = build(bucket, depth + 1);
+            ProjectGroup {
+                key,
+                value,
+                count,
+                groups,
+                projects,
+            }
+        })
+        .collect();
+
+    (groups, here)
+}
+
+/// Returns the tree levels a key contributes for a project.
+fn levels_for(key: GroupKey, project: &GitProject, root: Option<&Path>) -> Vec<Level> {
+    let remote = project.primary_remote();
+    let value = match key {
+        GroupKey::Service => remote.and_then(|r| r.service.clone()),
+        GroupKey::Account => remote.and_then(|r| r.account.clone()),
+        GroupKey::Host => project.identity().map(|id| id.host),
+        GroupKey::Branch => project.head.as_ref().and_then(|h| h.branch.clone()),
+        GroupKey::Dir => {
+            // Keep the common ancestor itself as the first level, so that
+            // projects directly inside it are not left outside the tree
+            let parent = project.path.parent().unwrap_or(&project.path);
+            let relative = root
+                .and_then(|root| root.parent())
+                .and_then(|base| parent.strip_prefix(base).ok())
+                .unwrap_or(parent);
+            return relative
+                .components()
+                .map(|c| (key, Some(c.as_os_str().to_string_lossy().into_owned())))
+                .collect();
+        }
+    };
+    vec![(key, value)]
+}
+
+/// Returns the deepest directory containing all project parent directories.
+fn common_ancestor(projects: &[GitProject]) -> Option<PathBuf> {
+    let mut parents = projects.iter().map(|p| p.path.parent().unwrap_or(&p.path));
+    let mut common = parents.next()?.to_path_buf();
+    for parent in parents {
+        while !parent.starts_with(&common) {
+            if !common.pop() {
+                return None;
+            }
+        }
+    }
+    Some(common)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(path: &str, service: Option<&str>, account: Option<&str>) -> GitProject {
+        let project = GitProject::test(path);
+        match service {
+            Some(service) => project.with_remote(&format!(
+                "https://{}.com/{}/x.git",
+                service,
+                account.unwrap()
+            )),
+            None => project,
+        }
+    }
+
+    fn summary(groups: &[ProjectGroup]) -> Vec<(Option<&str>, usize)> {
+        groups
+            .iter()
+            .map(|g| (g.value.as_deref(), g.count))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            GroupKey::parse_list("service, account").unwrap(),
+            vec![GroupKey::Service, GroupKey::Account]
+        );
+        assert!(matches!(
+            GroupKey::parse_list("platform"),
+            Err(Error::InvalidGroup(_))
+        ));
+        assert!(GroupKey::parse_list("").is_err());
+    }
+
+    #[test]
+    fn test_group_by_service_and_account() {
+        let projects = vec![
+            project("/w/a", Some("github"), Some("acme")),
+            project("/w/local", None, None),
+            project("/w/b", Some("gitlab"), Some("me")),
+            project("/w/c", Some("github"), Some("me")),
+            project("/w/d", Some("github"), Some("acme")),
+        ];
+
+        let groups = group_projects(&projects, &[GroupKey::Service, GroupKey::Account]);
+        assert_eq!(
+            summary(&groups),
+            [(Some("github"), 3), (Some("gitlab"), 1), (None, 1)]
+        );
+        assert_eq!(
+            summary(&groups[0].groups),
+            [(Some("acme"), 2), (Some("me"), 1)]
+        );
+        assert_eq!(groups[0].groups[0].projects.len(), 2);
+        assert!(groups[0].projects.is_empty());
+    }
+
+    #[test]
+    fn test_group_by_dir() {
+        let projects = vec![
+            project("/home/u/work/acme/tool", None, None),
+            project("/home/u/work/acme/lib", None, None),
+            project("/home/u/work/solo", None, None),
+            project("/home/u/oss/rust", None, None),
+        ];
+
+        let groups = group_projects(&projects, &[GroupKey::Dir]);
+        assert_eq!(summary(&groups), [(Some("u"), 4)]);
+        assert_eq!(
+            summary(&groups[0].groups),
+            [(Some("work"), 3), (Some("oss"), 1)]
+        );
+
+        let work = &groups[0].groups[0];
+        assert_eq!(work.projects.len(), 1);
+        assert_eq!(work.projects[0].name, "solo");
+        assert_eq!(summary(&work.groups), [(Some("acme"), 2)]);
+    }
+
+    #[test]
+    fn test_group_by_dir_keeps_projects_in_common_ancestor() {
+        let projects = vec![project("/w/a", None, None), project("/w/sub/b", None, None)];
+
+        let groups = group_projects(&projects, &[GroupKey::Dir]);
+        assert_eq!(summary(&groups), [(Some("w"), 2)]);
+        assert_eq!(groups[0].projects[0].name, "a");
+        assert_eq!(summary(&groups[0].groups), [(Some("sub"), 1)]);
+    }
+
+    #[test]
+    fn test_group_json_is_nested() {
+        let projects = vec![project("/w/a", Some("github"), Some("acme"))];
+        let groups = group_projects(&projects, &[GroupKey::Service]);
+        let json = serde_json::to_value(&groups).unwrap();
+        assert_eq!(json[0]["key"], "service");
+        assert_eq!(json[0]["value"], "github");
+        assert_eq!(json[0]["count"], 1);
+        assert_eq!(json[0]["projects"][0]["name"], "a");
+        assert!(json[0].get("groups").is_none());
+    }
+
+    #[test]
+    fn test_no_keys() {
+        let projects = vec![project("/w/a", None, None)];
+        assert!(group_projects(&projects, &[]).is_empty());
+    }
+}
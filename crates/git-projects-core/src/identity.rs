@@ -0,0 +1,209 @@
+//! Normalized identity of a remote repository.
+//!
+//! The same upstream repository can be referenced by many different URLs:
+//! `https://github.com/Acme/Tool.git`, `git@github.com:acme/tool`,
+//! `ssh://git@github.com:22/acme/tool.git` all point to the same place.
+//! [`RemoteIdentity`] reduces a URL to `host + owner path + repo name` so that
+//! clones of the same repository can be matched against each other.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Hosting platforms whose repository pages live at `https://host/owner/name`.
+const WEB_HOSTS: &[&str] = &[
+    "github.com",
+    "gitlab.com",
+    "bitbucket.org",
+    "codeberg.org",
+    "git.sr.ht",
+];
+
+/// The normalized identity of a remote repository.
+///
+/// Scheme, user, port, `.git` suffix, trailing slashes and letter case are
+/// ignored. All components are stored in lowercase.
+///
+/// # Example
+///
+/// ```
+/// use git_projects_core::RemoteIdentity;
+///
+/// let https = RemoteIdentity::parse("https://github.com/Acme/Tool.git").unwrap();
+/// let ssh = RemoteIdentity::parse("git@github.com:acme/tool").unwrap();
+/// assert_eq!(https, ssh);
+/// assert_eq!(https.to_string(), "github.com/acme/tool");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RemoteIdentity {
+    /// The hostname, without user or port (e.g., `"github.com"`).
+    pub host: String,
+
+    /// The owner path between host and repository name.
+    ///
+    /// Usually a single account (`"acme"`), but may contain several segments
+    /// for nested groups (`"group/subgroup"`). Empty for URLs without an owner.
+    pub owner: String,
+
+    /// The repository name without `.git` suffix (e.g., `"tool"`).
+    pub name: String,
+}
+
+impl RemoteIdentity {
+    /// Parses a remote URL into its normalized identity.
+    ///
+    /// Supports the same formats as [`parse_git_url`](crate::git_analyzer::parse_git_url):
+    /// - HTTPS: `https://host/owner/repo.git`
+    /// - SCP-like SSH: `git@host:owner/repo.git`
+    /// - SSH with protocol: `ssh://git@host:22/owner/repo.git`
+    /// - Git protocol: `git://host/owner/repo.git`
+    ///
+    /// Returns `None` for local paths, `file://` URLs and anything without a
+    /// host and repository name.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+
+        let (host_part, path) = if let Some((scheme, rest)) = url.split_once("://") {
+            if scheme.eq_ignore_ascii_case("file") {
+                return None;
+            }
+            rest.split_once('/')?
+        } else {
+            // SCP-like syntax: [user@]host:path
+            let (host_part, path) = url.split_once(':')?;
+            if host_part.is_empty() || host_part.contains('/') {
+                return None;
+            }
+            (host_part, path)
+        };
+
+        // Strip user info and port from the host
+        let host = host_part.rsplit('@').next()?;
+        let host = host.split(':').next()?.to_lowercase();
+        if host.is_empty() {
+            return None;
+        }
+
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        let mut segments: Vec<String> = path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_lowercase())
+            .collect();
+
+        let name = segments.pop()?;
+        Some(Self {
+            host,
+            owner: segments.join("/"),
+            name,
+        })
+    }
+
+    /// Returns the `owner/name` part of the identity (e.g., `"acme/tool"`).
+    ///
+    /// Falls back to just the name if there is no owner.
+    pub fn slug(&self) -> String {
+        if self.owner.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}/{}", self.owner, self.name)
+        }
+    }
+
+    /// Returns the browser URL of the repository on a known hosting platform
+    /// (e.g., `"https://github.com/acme/tool"`).
+    ///
+    /// Returns `None` for self-hosted or unknown hosts, whose web interface
+    /// (if any) cannot be derived from the clone URL.
+    pub fn web_url(&self) -> Option<String> {
+        if !WEB_HOSTS.contains(&self.host.as_str()) || self.owner.is_empty() {
+            return None;
+        }
+        Some(format!("https://{}/{}", self.host, self.slug()))
+    }
+}
+
+impl fmt::Display for RemoteIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.host, self.slug())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(host: &str, owner: &str, name: &str) -> RemoteIdentity {
+        RemoteIdentity {
+            host: host.to_string(),
+            owner: owner.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_web_url() {
+        let id = RemoteIdentity::parse("git@gitlab.com:group/sub/tool.git").unwrap();
+        assert_eq!(
+            id.web_url().as_deref(),
+            Some("https://gitlab.com/group/sub/tool")
+        );
+        let id = RemoteIdentity::parse("git@git.example.com:acme/tool.git").unwrap();
+        assert_eq!(id.web_url(), None);
+    }
+
+    #[test]
+    fn test_parse_https() {
+        assert_eq!(
+            RemoteIdentity::parse("https://github.com/acme/tool.git"),
+            Some(identity("github.com", "acme", "tool"))
+        );
+    }
+
+    #[test]
+    fn test_parse_scp_like_ssh() {
+        assert_eq!(
+            RemoteIdentity::parse("git@github.com:acme/tool.git"),
+            Some(identity("github.com", "acme", "tool"))
+        );
+    }
+
+    #[test]
+    fn test_parse_ssh_with_user_and_port() {
+        assert_eq!(
+            RemoteIdentity::parse("ssh://git@gitlab.com:2222/acme/tool.git"),
+            Some(identity("gitlab.com", "acme", "tool"))
+        );
+    }
+
+    #[test]
+    fn test_normalization_ignores_case_scheme_and_suffix() {
+        let a = RemoteIdentity::parse("https://user@GitHub.com/Acme/Tool.git/").unwrap();
+        let b = RemoteIdentity::parse("git@github.com:acme/tool").unwrap();
+        let c = RemoteIdentity::parse("git://github.com/ACME/tool").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(b, c);
+    }
+
+    #[test]
+    fn test_parse_nested_groups() {
+        let id = RemoteIdentity::parse("https://gitlab.com/group/sub/project.git").unwrap();
+        assert_eq!(id.owner, "group/sub");
+        assert_eq!(id.name, "project");
+        assert_eq!(id.to_string(), "gitlab.com/group/sub/project");
+    }
+
+    #[test]
+    fn test_parse_local_paths() {
+        assert_eq!(RemoteIdentity::parse("file:///srv/git/tool.git"), None);
+        assert_eq!(RemoteIdentity::parse("/srv/git/tool.git"), None);
+        assert_eq!(RemoteIdentity::parse("not-a-url"), None);
+    }
+
+    #[test]
+    fn test_slug_without_owner() {
+        let id = RemoteIdentity::parse("https://example.com/tool.git").unwrap();
+        assert_eq!(id.owner, "");
+        assert_eq!(id.slug(), "tool");
+    }
+}
@@ -0,0 +1,592 @@
+//! SQLite inventory of scans, with history across runs.
+//!
+//! [`Inventory::sync`] records a scan in a normalized schema, and
+//! [`Inventory::query`] runs ad-hoc, read-only SQL against it:
+//!
+//! | Table                | Rows                                                    |
+//! |----------------------|---------------------------------------------------------|
+//! | `scans`              | one per sync: time, roots, number of projects           |
+//! | `projects`           | one per path: latest state, first and last scan seen    |
+//! | `project_scans`      | one per project and scan: the branch and commit then    |
+//! | `remotes`            | one per project, name and URL: first and last scan seen |
+//! | `identities`         | distinct `user.name`/`user.email` pairs                 |
+//! | `project_identities` | which identity a project used, in which scans           |
+//! | `submodules`         | parent and submodule projects                           |
+//!
+//! Nothing is deleted: a project, remote or identity that is gone keeps its
+//! rows, with a `last_scan` before the latest scan. The view
+//! `current_projects` holds the projects of the latest scan, so syncs should
+//! cover the same roots. The schema version is kept in `PRAGMA user_version`
+//! and migrated when the database is opened.
+
+use crate::error::{Error, Result};
+use crate::models::{GitProject, ScanConfig};
+use chrono::Utc;
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Version of the database schema, in `PRAGMA user_version`.
+pub const DATABASE_VERSION: u32 = 1;
+
+/// File name of the database in the data directory.
+const DATABASE_FILE: &str = "inventory.db";
+
+/// Migrations, applied in order; the one at index `i` upgrades a database
+/// from version `i` to `i + 1`.
+const MIGRATIONS: &[&str] = &["
+    CREATE TABLE scans (
+        id INTEGER PRIMARY KEY,
+        scanned_at TEXT NOT NULL,
+        roots TEXT NOT NULL,
+        project_count INTEGER NOT NULL
+    );
+    CREATE TABLE projects (
+        id INTEGER PRIMARY KEY,
+        path TEXT NOT NULL UNIQUE,
+        name TEXT NOT NULL,
+        identity TEXT,
+        is_submodule INTEGER NOT NULL,
+        branch TEXT,
+        head_commit TEXT,
+        first_scan INTEGER NOT NULL REFERENCES scans(id),
+        last_scan INTEGER NOT NULL REFERENCES scans(id)
+    );
+    CREATE INDEX projects_identity ON projects(identity);
+    CREATE TABLE project_scans (
+        project_id INTEGER NOT NULL REFERENCES projects(id),
+        scan_id INTEGER NOT NULL REFERENCES scans(id),
+        branch TEXT,
+        head_commit TEXT,
+        PRIMARY KEY (project_id, scan_id)
+    );
+    CREATE TABLE remotes (
+        id INTEGER PRIMARY KEY,
+        project_id INTEGER NOT NULL REFERENCES projects(id),
+        name TEXT NOT NULL,
+        url TEXT NOT NULL,
+        service TEXT,
+        account TEXT,
+        first_scan INTEGER NOT NULL REFERENCES scans(id),
+        last_scan INTEGER NOT NULL REFERENCES scans(id),
+        UNIQUE (project_id, name, url)
+    );
+    CREATE TABLE identities (
+        id INTEGER PRIMARY KEY,
+        user_name TEXT,
+        user_email TEXT
+    );
+    CREATE TABLE project_identities (
+        project_id INTEGER NOT NULL REFERENCES projects(id),
+        identity_id INTEGER NOT NULL REFERENCES identities(id),
+        scope TEXT NOT NULL,
+        first_scan INTEGER NOT NULL REFERENCES scans(id),
+        last_scan INTEGER NOT NULL REFERENCES scans(id),
+        PRIMARY KEY (project_id, identity_id, scope)
+    );
+    CREATE TABLE submodules (
+        parent_id INTEGER NOT NULL REFERENCES projects(id),
+        submodule_id INTEGER NOT NULL REFERENCES projects(id) UNIQUE,
+        PRIMARY KEY (parent_id, submodule_id)
+    );
+    CREATE VIEW current_projects AS
+        SELECT * FROM projects WHERE last_scan = (SELECT MAX(id) FROM scans);
+"];
+
+/// An inventory database.
+#[derive(Debug)]
+pub struct Inventory {
+    connection: Connection,
+}
+
+/// What [`Inventory::sync`] recorded.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SyncSummary {
+    /// The id of the scan in the `scans` table.
+    pub scan_id: i64,
+
+    /// The number of projects recorded.
+    pub projects: usize,
+
+    /// Projects seen for the first time.
+    pub added: Vec<PathBuf>,
+
+    /// Projects of the previous scan that are gone.
+    pub removed: Vec<PathBuf>,
+}
+
+/// The result of [`Inventory::query`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QueryResult {
+    /// The column names.
+    pub columns: Vec<String>,
+
+    /// The rows, with a value per column.
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+impl Inventory {
+    /// Returns the path of the database in the data directory.
+    ///
+    /// `$XDG_DATA_HOME/git-projects`, or `~/.local/share/git-projects`.
+    pub fn default_path() -> Option<PathBuf> {
+        crate::config::data_dir().map(|dir| dir.join(DATABASE_FILE))
+    }
+
+    /// Opens or creates a database, migrating it to [`DATABASE_VERSION`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the directory cannot be created,
+    /// [`Error::Database`] if the database cannot be opened or migrated, or
+    /// [`Error::Other`] if it was written by a newer version.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        Self::migrate(Connection::open(path)?)
+    }
+
+    /// Opens an existing database for [`query`](Self::query) only.
+    ///
+    /// Unlike [`open`](Self::open), this neither creates nor migrates the
+    /// file, so a mistyped path is an error rather than a new, empty database.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Database`] if the file does not exist or cannot be
+    /// opened, or [`Error::Other`] if it is not an inventory database or was
+    /// written by a newer version.
+    pub fn open_read_only(path: &Path) -> Result<Self> {
+        let flags = OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+        let connection = Connection::open_with_flags(path, flags)?;
+        let version: u32 = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version == 0 {
+            return Err(Error::other(format!(
+                "{} is not an inventory database",
+                path.display()
+            )));
+        }
+        if version > DATABASE_VERSION {
+            return Err(Error::other(format!(
+                "Database version {} is not supported (newest is {})",
+                version, DATABASE_VERSION
+            )));
+        }
+        Ok(Self { connection })
+    }
+
+    /// Creates a database in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Database`] if the schema cannot be created.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::migrate(Connection::open_in_memory()?)
+    }
+
+    fn migrate(mut connection: Connection) -> Result<Self> {
+        connection.pragma_update(None, "foreign_keys", true)?;
+        let version: u32 = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version > DATABASE_VERSION {
+            return Err(Error::other(format!(
+                "Database version {} is not supported (newest is {})",
+                version, DATABASE_VERSION
+            )));
+        }
+        let transaction = connection.transaction()?;
+        for migration in &MIGRATIONS[version as usize..] {
+            transaction.execute_batch(migration)?;
+        }
+        transaction.pragma_update(None, "user_version", DATABASE_VERSION)?;
+        transaction.commit()?;
+        Ok(Self { connection })
+    }
+
+    /// Records a scan.
+    ///
+    /// Projects are keyed by absolute path. Submodules are linked to the project
+    /// whose path contains theirs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Database`] if writing fails; nothing is recorded then.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use git_projects_core::inventory::Inventory;
+    /// use git_projects_core::{DefaultScanner, ProjectScanner, ScanConfig};
+    ///
+    /// let config = ScanConfig::default();
+    /// let projects = DefaultScanner::new().scan(&config)?;
+    /// let mut inventory = Inventory::open(&Inventory::default_path().unwrap())?;
+    /// let summary = inventory.sync(&projects, &config)?;
+    /// println!("{} new repositories", summary.added.len());
+    /// # Ok::<(), git_projects_core::Error>(())
+    /// ```
+    pub fn sync(
+        &mut self,
+        projects: &[GitProject],
+        scan_config: &ScanConfig,
+    ) -> Result<SyncSummary> {
+        let transaction = self.connection.transaction()?;
+        let previous: Option<i64> =
+            transaction.query_row("SELECT MAX(id) FROM scans", [], |row| row.get(0))?;
+        let roots: Vec<String> = scan_config
+            .root_paths
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect();
+        transaction.execute(
+            "INSERT INTO scans (scanned_at, roots, project_count) VALUES (?1, ?2, ?3)",
+            params![
+                Utc::now().to_rfc3339(),
+                serde_json::to_string(&roots)?,
+                projects.len()
+            ],
+        )?;
+        let scan = transaction.last_insert_rowid();
+
+        let mut summary = SyncSummary {
+            scan_id: scan,
+            projects: projects.len(),
+            added: Vec::new(),
+            removed: Vec::new(),
+        };
+        let mut ids = Vec::with_capacity(projects.len());
+        for project in projects {
+            // Keyed by absolute path, wherever the scan was started from
+            let path = std::path::absolute(&project.path)
+                .unwrap_or_else(|_| project.path.clone())
+                .display()
+                .to_string();
+            let head = project.head.as_ref();
+            let branch = head.and_then(|h| h.branch.as_deref());
+            let commit = head.and_then(|h| h.commit.as_deref());
+            let (id, first_scan): (i64, i64) = transaction.query_row(
+                "INSERT INTO projects
+                     (path, name, identity, is_submodule, branch, head_commit, first_scan, last_scan)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+                 ON CONFLICT (path) DO UPDATE SET
+                     name = excluded.name, identity = excluded.identity,
+                     is_submodule = excluded.is_submodule, branch = excluded.branch,
+                     head_commit = excluded.head_commit, last_scan = excluded.last_scan
+                 RETURNING id, first_scan",
+                params![
+                    path,
+                    project.name,
+                    project.identity().map(|identity| identity.to_string()),
+                    project.is_submodule,
+                    branch,
+                    commit,
+                    scan
+                ],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+            if first_scan == scan {
+                summary.added.push(project.path.clone());
+            }
+            ids.push(id);
+
+            transaction.execute(
+                "INSERT OR REPLACE INTO project_scans (project_id, scan_id, branch, head_commit)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![id, scan, branch, commit],
+            )?;
+            for remote in &project.remotes {
+                transaction.execute(
+                    "INSERT INTO remotes
+                         (project_id, name, url, service, account, first_scan, last_scan)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+                     ON CONFLICT (project_id, name, url) DO UPDATE SET
+                         service = excluded.service, account = excluded.account,
+                         last_scan = excluded.last_scan",
+                    params![
+                        id,
+                        remote.name,
+                        remote.url,
+                        remote.service,
+                        remote.account,
+                        scan
+                    ],
+                )?;
+            }
+            if let Some(config) = &project.config {
+                let identity: Option<i64> = transaction
+                    .query_row(
+                        "SELECT id FROM identities WHERE user_name IS ?1 AND user_email IS ?2",
+                        params![config.user_name, config.user_email],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                let identity = match identity {
+                    Some(identity) => identity,
+                    None => {
+                        transaction.execute(
+                            "INSERT INTO identities (user_name, user_email) VALUES (?1, ?2)",
+                            params![config.user_name, config.user_email],
+                        )?;
+                        transaction.last_insert_rowid()
+                    }
+                };
+                let scope = serde_json::to_value(config.scope)?;
+                transaction.execute(
+                    "INSERT INTO project_identities
+                         (project_id, identity_id, scope, first_scan, last_scan)
+                     VALUES (?1, ?2, ?3, ?4, ?4)
+                     ON CONFLICT (project_id, identity_id, scope) DO UPDATE SET
+                         last_scan = excluded.last_scan",
+                    params![id, identity, scope.as_str(), scan],
+                )?;
+            }
+        }
+
+        // Link each submodule to the innermost project containing it
+        for (project, &id) in projects.iter().zip(&ids) {
+            if !project.is_submodule {
+                continue;
+            }
+            let parent = projects
+                .iter()
+                .zip(&ids)
+                .filter(|(p, _)| p.path != project.path && project.path.starts_with(&p.path))
+                .max_by_key(|(p, _)| p.path.components().count());
+            if let Some((_, &parent)) = parent {
+                transaction.execute(
+                    "INSERT OR REPLACE INTO submodules (parent_id, submodule_id) VALUES (?1, ?2)",
+                    params![parent, id],
+                )?;
+            }
+        }
+
+        if let Some(previous) = previous {
+            let mut statement = transaction
+                .prepare("SELECT path FROM projects WHERE last_scan = ?1 ORDER BY path")?;
+            summary.removed = statement
+                .query_map([previous], |row| row.get::<_, String>(0))?
+                .map(|path| path.map(PathBuf::from))
+                .collect::<rusqlite::Result<_>>()?;
+        }
+        transaction.commit()?;
+        Ok(summary)
+    }
+
+    /// Runs a read-only SQL statement.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Database`] if the statement is invalid or fails, or
+    /// [`Error::Other`] if it would modify the database.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use git_projects_core::inventory::Inventory;
+    ///
+    /// let inventory = Inventory::open_in_memory()?;
+    /// let result = inventory.query("SELECT COUNT(*) AS scans FROM scans")?;
+    /// assert_eq!(result.columns, ["scans"]);
+    /// assert_eq!(result.rows, [[serde_json::json!(0)]]);
+    /// # Ok::<(), git_projects_core::Error>(())
+    /// ```
+    pub fn query(&self, sql: &str) -> Result<QueryResult> {
+        let mut statement = self.connection.prepare(sql)?;
+        if !statement.readonly() {
+            return Err(Error::other("Only statements that read are allowed"));
+        }
+        let columns: Vec<String> = statement
+            .column_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let count = columns.len();
+        let rows = statement
+            .query_map([], |row| {
+                (0..count)
+                    .map(|i| Ok(json_value(row.get_ref(i)?)))
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(QueryResult { columns, rows })
+    }
+}
+
+/// Converts an SQLite value to JSON; blobs become hex strings
+fn json_value(value: ValueRef<'_>) -> serde_json::Value {
+    match value {
+        ValueRef::Null => serde_json::Value::Null,
+        ValueRef::Integer(i) => i.into(),
+        ValueRef::Real(f) => f.into(),
+        ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned().into(),
+        ValueRef::Blob(blob) => blob
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+            .into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigScope, GitConfig, HeadInfo};
+    use serde_json::json;
+
+    fn project(path: &str, commit: &str) -> GitProject {
+        GitProject {
+            config: Some(GitConfig {
+                user_name: Some("Ada".to_string()),
+                user_email: None,
+                scope: ConfigScope::Global,
+            }),
+            head: Some(HeadInfo {
+                branch: Some("main".to_string()),
+                commit: Some(commit.to_string()),
+                upstream_remote: None,
+            }),
+            ..GitProject::test(path).with_remote(&format!("https://github.com/acme{}.git", path))
+        }
+    }
+
+    fn values(inventory: &Inventory, sql: &str) -> Vec<Vec<serde_json::Value>> {
+        inventory.query(sql).unwrap().rows
+    }
+
+    #[test]
+    fn test_sync_keeps_history() {
+        let mut inventory = Inventory::open_in_memory().unwrap();
+        let config = ScanConfig::default();
+        let mut submodule = project("/src/tool/vendor/lib", "c");
+        submodule.is_submodule = true;
+        let first = [
+            project("/src/tool", "a"),
+            project("/src/old", "a"),
+            submodule,
+        ];
+        let summary = inventory.sync(&first, &config).unwrap();
+        assert_eq!(summary.scan_id, 1);
+        assert_eq!(summary.added.len(), 3);
+        assert!(summary.removed.is_empty());
+
+        let mut moved = project("/src/tool", "b");
+        moved.remotes[0].url = "git@github.com:acme/tool.git".to_string();
+        let second = [moved, project("/src/new", "a")];
+        let summary = inventory.sync(&second, &config).unwrap();
+        assert_eq!(summary.added, [PathBuf::from("/src/new")]);
+        assert_eq!(
+            summary.removed,
+            [
+                PathBuf::from("/src/old"),
+                PathBuf::from("/src/tool/vendor/lib")
+            ]
+        );
+
+        assert_eq!(
+            values(
+                &inventory,
+                "SELECT path, first_scan FROM current_projects ORDER BY path"
+            ),
+            [
+                vec![json!("/src/new"), json!(2)],
+                vec![json!("/src/tool"), json!(1)]
+            ]
+        );
+        assert_eq!(
+            values(
+                &inventory,
+                "SELECT s.scan_id, s.head_commit FROM project_scans s
+                 JOIN projects p ON p.id = s.project_id
+                 WHERE p.path = '/src/tool' ORDER BY s.scan_id"
+            ),
+            [vec![json!(1), json!("a")], vec![json!(2), json!("b")]]
+        );
+        assert_eq!(
+            values(
+                &inventory,
+                "SELECT url, first_scan, last_scan FROM remotes
+                 WHERE project_id = 1 ORDER BY first_scan"
+            ),
+            [
+                vec![
+                    json!("https://github.com/acme/src/tool.git"),
+                    json!(1),
+                    json!(1)
+                ],
+                vec![json!("git@github.com:acme/tool.git"), json!(2), json!(2)]
+            ]
+        );
+        assert_eq!(
+            values(&inventory, "SELECT user_name, user_email FROM identities"),
+            [vec![json!("Ada"), json!(null)]]
+        );
+        assert_eq!(
+            values(
+                &inventory,
+                "SELECT p.path FROM submodules s JOIN projects p ON p.id = s.parent_id"
+            ),
+            [vec![json!("/src/tool")]]
+        );
+    }
+
+    #[test]
+    fn test_query_is_read_only() {
+        let inventory = Inventory::open_in_memory().unwrap();
+        assert!(inventory.query("DELETE FROM scans").is_err());
+        assert!(inventory.query("SELECT * FROM nowhere").is_err());
+        assert_eq!(
+            inventory
+                .query("SELECT 1 AS one, 'x' AS two, NULL")
+                .unwrap(),
+            QueryResult {
+                columns: vec!["one".to_string(), "two".to_string(), "NULL".to_string()],
+                rows: vec![vec![json!(1), json!("x"), json!(null)]],
+            }
+        );
+    }
+
+    #[test]
+    fn test_open_read_only() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("inventory.db");
+        assert!(Inventory::open_read_only(&path).is_err());
+        assert!(!path.exists());
+
+        std::fs::write(&path, "").unwrap();
+        assert!(Inventory::open_read_only(&path).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+        let mut inventory = Inventory::open(&path).unwrap();
+        inventory
+            .sync(&[project("/src/tool", "a")], &ScanConfig::default())
+            .unwrap();
+        drop(inventory);
+        let inventory = Inventory::open_read_only(&path).unwrap();
+        assert_eq!(
+            values(&inventory, "SELECT COUNT(*) FROM projects"),
+            [[json!(1)]]
+        );
+    }
+
+    #[test]
+    fn test_open_migrates_once() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("nested/inventory.db");
+        let mut inventory = Inventory::open(&path).unwrap();
+        inventory
+            .sync(&[project("/src/tool", "a")], &ScanConfig::default())
+            .unwrap();
+        drop(inventory);
+
+        let inventory = Inventory::open(&path).unwrap();
+        assert_eq!(
+            values(&inventory, "SELECT COUNT(*) FROM projects"),
+            [[json!(1)]]
+        );
+        assert_eq!(
+            values(&inventory, "PRAGMA user_version"),
+            [[json!(DATABASE_VERSION)]]
+        );
+    }
+}
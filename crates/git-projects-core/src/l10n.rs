@@ -119,14 +119,14 @@ impl Localizer {
     /// Searches for the locale file in these locations (in order):
     /// 1. `./locales/{locale}/main.ftl` (current directory)
     /// 2. `./crates/git-projects-core/locales/{locale}/main.ftl` (workspace structure)
-    /// 3. Embedded resources (if compiled in)
+    /// 3. The files compiled into the library
     fn load_locale(
         locale: &LanguageIdentifier,
     ) -> Result<(FluentBundle<FluentResource>, LanguageIdentifier)> {
         let locale_code = locale.to_string();
 
         // Try multiple possible paths for the locale file
-        let possible_paths = vec![
+        let possible_paths = [
             PathBuf::from(format!("locales/{}/main.ftl", locale_code)),
             PathBuf::from(format!(
                 "crates/git-projects-core/locales/{}/main.ftl",
@@ -200,7 +200,11 @@ impl Localizer {
         let formatted = if let Some(args) = args {
             let mut fluent_args = fluent::FluentArgs::new();
             for (key, value) in args {
-                fluent_args.set(*key, value.to_string());
+                // Numbers must be passed as numbers for plural selection
+                match value.parse::<u64>() {
+                    Ok(number) => fluent_args.set(*key, number),
+                    Err(_) => fluent_args.set(*key, value.to_string()),
+                }
             }
             self.bundle
                 .format_pattern(pattern, Some(&fluent_args), &mut errors)
@@ -253,29 +257,17 @@ pub fn detect_system_locale() -> String {
         .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
 }
 
-/// Attempts to get embedded locale content.
-///
-/// This function is called when locale files are not found on the filesystem.
-/// It checks for compile-time embedded resources.
-///
-/// In a production build, you could use `include_str!` to embed the locale files.
-///
-/// Example of how this might look:
+/// Returns the locale files compiled into the binary.
 ///
-/// ```rust
-/// # fn example(locale_code: &str) -> Option<String> {
-/// match locale_code {
-///     "en" => Some("embedded en content".to_string()),
-///     "de" => Some("embedded de content".to_string()),
-///     _ => None,
-/// }
-/// # }
-/// ```
-fn get_embedded_locale(_locale_code: &str) -> Option<String> {
-    // For now, return None - embedded resources would be added here
-    // This allows the library to work both as a development library
-    // (loading from filesystem) and as a compiled binary (embedded resources)
-    None
+/// Used when no locale file is found on the filesystem, so the CLI works
+/// from any directory, e.g. when run from shell functions.
+fn get_embedded_locale(locale_code: &str) -> Option<String> {
+    let content = match locale_code {
+        "en" => include_str!("../locales/en/main.ftl"),
+        "de" => include_str!("../locales/de/main.ftl"),
+        _ => return None,
+    };
+    Some(content.to_string())
 }
 
 #[cfg(test)]
@@ -335,4 +327,13 @@ mod tests {
         let msg = localizer.get("scanning", None);
         assert!(!msg.is_empty());
     }
+
+    #[test]
+    fn test_plural_selection() {
+        let localizer = Localizer::new("en").unwrap();
+        let one = localizer.get("scan-complete", Some(&[("count", "1")]));
+        let many = localizer.get("scan-complete", Some(&[("count", "2")]));
+        assert!(!one.contains("repositories"), "{}", one);
+        assert!(many.contains("repositories"), "{}", many);
+    }
 }
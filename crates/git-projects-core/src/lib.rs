@@ -8,7 +8,7 @@
 //! - **Rich metadata extraction** including remotes, configuration, and submodules
 //! - **Account detection** from remote URLs (best-effort extraction)
 //! - **Localization support** via Fluent (currently English and German)
-//! - **JSON serialization** for all data structures
+//! - **JSON serialization** for all data structures, plus CSV/TSV/NDJSON export
 //!
 //! ## Quick Start
 //!
@@ -21,6 +21,7 @@
 //!     max_depth: Some(3),
 //!     follow_symlinks: false,
 //!     include_submodules: true,
+//!     ignore_patterns: vec![],
 //! };
 //!
 //! let scanner = DefaultScanner::new();
@@ -36,6 +37,30 @@
 //! The library is organized into several modules:
 //!
 //! - [`models`] - Core data structures (GitProject, RemoteUrl, etc.)
+//! - [`config`] - Configuration files (`config.toml`, `.git-projects.toml`)
+//! - [`metadata`] - Tags and notes stored outside the repositories
+//! - [`identity`] - Normalized remote identities for matching clones
+//! - [`duplicates`] - Detection of duplicate clones
+//! - [`diff`] - Changes between two scans
+//! - [`audit`] - Consistency checks (missing identity, insecure remotes, ...)
+//! - [`details`] - Full details of a single project
+//! - [`fuzzy`] - Fuzzy matching of project names
+//! - [`exec`] - Running a command in many repositories
+//! - [`manifest`] - Workspace manifests: export and restore of a layout of clones
+//! - [`manifest_format`] - Manifests of mr, vcstool and repo
+//! - [`workspace`] - VS Code and JetBrains workspaces
+//! - [`fork`] - Fork/upstream relationships between remotes
+//! - [`frecency`] - Ranking of frequently and recently picked projects
+//! - [`cache`] - Cache of the last scan, for completion and quick jumps
+//! - `inventory` - SQLite inventory of scans, with history (`inventory` feature)
+//! - [`filter`] - Filter expression language for scan results
+//! - [`sort`] - Hierarchical, locale-aware sorting
+//! - [`group`] - Grouping of scan results into a tree
+//! - [`output`] - CSV, TSV, JSON and NDJSON writers
+//! - [`report`] - Markdown and HTML inventory reports
+//! - [`schema`] - Versioned JSON output schema
+//! - [`server`] - Daemon serving scan results over JSON-RPC and HTTP
+//! - [`template`] - User-defined output templates
 //! - [`scanner`] - Scanner trait and default implementation
 //! - [`git_analyzer`] - Low-level Git operations using gitoxide
 //! - [`error`] - Custom error types
@@ -45,21 +70,81 @@
 //!
 //! This crate also provides a `projects-cli` binary for command-line usage.
 //! See the binary's `--help` output for details.
+//!
+//! The binary and its dependencies (argument parsing, terminal UI) are
+//! behind the default `cli` feature, which also enables `inventory`.
+//! Depend on the library with `default-features = false` to leave them out.
 
 // Module declarations
+pub mod audit;
+pub mod cache;
+pub mod config;
+pub mod details;
+pub mod diff;
+pub mod duplicates;
 pub mod error;
+pub mod exec;
+pub mod filter;
+pub mod fork;
+pub mod frecency;
+pub mod fuzzy;
 pub mod git_analyzer;
+pub mod group;
+pub mod identity;
+#[cfg(feature = "inventory")]
+pub mod inventory;
 pub mod l10n;
+pub mod manifest;
+pub mod manifest_format;
+pub mod metadata;
 pub mod models;
+pub mod output;
+pub mod report;
 pub mod scanner;
+pub mod schema;
+pub mod server;
+pub mod sort;
+pub mod template;
+pub mod workspace;
 
 // Re-export commonly used types for convenience
+pub use audit::{audit, AuditCheck, AuditFinding};
+pub use cache::ProjectCache;
+pub use config::{Config, Profile, ProjectRule, ProjectSelector};
+pub use details::{find_project, project_details, ProjectDetails};
+pub use diff::{diff, Change, ChangeKind};
+pub use duplicates::{find_duplicates, DuplicateGroup};
 pub use error::{Error, Result};
-pub use models::{ConfigScope, GitConfig, GitProject, RemoteUrl, ScanConfig};
+pub use exec::{exec, ExecOptions, ExecReport, ExecResult, ExecStatus, ExecSummary};
+pub use filter::Filter;
+pub use fork::{detect_fork, ForkRelation, TrackingRemote};
+pub use frecency::FrecencyStore;
+pub use group::{group_projects, GroupKey, ProjectGroup};
+pub use identity::RemoteIdentity;
+#[cfg(feature = "inventory")]
+pub use inventory::{Inventory, QueryResult, SyncSummary};
+pub use manifest::{
+    Manifest, ManifestCheck, ManifestEntry, ManifestRemote, RestoreOptions, RestoreResult,
+    RestoreStatus,
+};
+pub use manifest_format::ManifestFormat;
+pub use metadata::{MetadataStore, ProjectMetadata};
+pub use models::{
+    BranchInfo, ConfigEntry, ConfigScope, GitConfig, GitProject, HeadInfo, IdentityDetails,
+    PathStyle, RemoteDetails, RemoteUrl, RepoStatus, ScanConfig, SubmoduleInfo,
+};
+pub use output::{Column, OutputFormat};
+pub use report::{render_report, ReportFormat, ReportOptions, ReportSummary};
 pub use scanner::{DefaultScanner, ProjectScanner};
+pub use schema::{ProjectList, SCHEMA_VERSION};
+pub use sort::{SortDirection, SortKey, SortPreset, SortSpec};
+pub use template::Template;
 
 // Re-export key functions from git_analyzer that might be useful to library users
-pub use git_analyzer::{extract_git_config, extract_remote_urls};
+pub use git_analyzer::{
+    extract_branches, extract_git_config, extract_head, extract_identity, extract_remote_details,
+    extract_remote_urls, extract_status, extract_submodules,
+};
 
 /// Library version, derived from Cargo.toml
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,813 @@
+//! Workspace manifests: recreating a layout of clones on another machine.
+//!
+//! A [`Manifest`] lists each project by its path relative to the scan root,
+//! with all remotes, the default branch and its tags. [`restore_entry`]
+//! clones a missing project from its first remote into the same relative
+//! path under another root, checks out the default branch and adds the other
+//! remotes. Projects that are already there get missing remotes added;
+//! remotes with the same name but a different URL are reported as conflicts
+//! and left alone. [`check`] compares a manifest with a scan instead.
+//!
+//! Manifests are JSON documents like the one below;
+//! [`ManifestFormat`](crate::manifest_format::ManifestFormat) converts them to
+//! and from the formats of other tools.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "projects": [
+//!     {
+//!       "path": "acme/tool",
+//!       "remotes": [
+//!         { "name": "origin", "url": "git@github.com:acme/tool.git" },
+//!         { "name": "upstream", "url": "https://github.com/upstream/tool.git" }
+//!       ],
+//!       "default_branch": "main",
+//!       "tags": ["client-x"]
+//!     }
+//!   ]
+//! }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::git_analyzer::extract_remote_details;
+use crate::identity::RemoteIdentity;
+use crate::manifest_format::ManifestFormat;
+use crate::models::GitProject;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+/// Version of the manifest format written by [`Manifest::from_projects`].
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// A layout of projects that can be restored elsewhere.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Manifest {
+    /// The version of the format; see [`MANIFEST_VERSION`].
+    pub version: u32,
+
+    /// The projects, in scan order.
+    pub projects: Vec<ManifestEntry>,
+}
+
+/// One project in a [`Manifest`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// The path relative to the scan root, or the absolute path for projects
+    /// found outside of one.
+    pub path: PathBuf,
+
+    /// All remotes; the first one is cloned from.
+    pub remotes: Vec<ManifestRemote>,
+
+    /// The branch to check out after cloning; the remote's `HEAD` if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+
+    /// The tags of the project.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// A remote of a [`ManifestEntry`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ManifestRemote {
+    /// The name of the remote (e.g., `"origin"`).
+    pub name: String,
+
+    /// The fetch URL.
+    pub url: String,
+
+    /// The push URL, if it differs from the fetch URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub push_url: Option<String>,
+}
+
+impl Manifest {
+    /// Creates a manifest of the projects, reading their remotes and default
+    /// branches from the repositories.
+    ///
+    /// Submodules are left out, as they are restored with their parent.
+    /// `origin` is listed first if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a repository cannot be opened.
+    pub fn from_projects(projects: &[GitProject]) -> Result<Self> {
+        let mut entries = Vec::new();
+        for project in projects.iter().filter(|p| !p.is_submodule) {
+            let mut remotes: Vec<ManifestRemote> = extract_remote_details(&project.path)?
+                .into_iter()
+                .filter_map(|remote| {
+                    let url = remote.fetch_url?;
+                    Some(ManifestRemote {
+                        push_url: remote.push_url.filter(|push| *push != url),
+                        name: remote.name,
+                        url,
+                    })
+                })
+                .collect();
+            remotes.sort_by_key(|remote| remote.name != "origin");
+
+            let default_branch = remotes
+                .first()
+                .and_then(|remote| remote_default_branch(&project.path, &remote.name))
+                .or_else(|| project.head.as_ref().and_then(|head| head.branch.clone()));
+
+            entries.push(ManifestEntry {
+                path: project
+                    .relative_path
+                    .clone()
+                    .unwrap_or_else(|| project.path.clone()),
+                remotes,
+                default_branch,
+                tags: project.tags.clone(),
+            });
+        }
+        Ok(Self {
+            version: MANIFEST_VERSION,
+            projects: entries,
+        })
+    }
+
+    /// Loads a manifest from a file in any [format](ManifestFormat),
+    /// detected from its content.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read, or
+    /// [`Error::InvalidManifest`] or [`Error::Json`] if it is malformed.
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        ManifestFormat::detect(&source).parse(&source)
+    }
+
+    /// Parses a manifest from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] if the source is malformed, or
+    /// [`Error::InvalidManifest`] if it has a newer version.
+    pub fn parse(source: &str) -> Result<Self> {
+        let manifest: Self = serde_json::from_str(source)?;
+        if manifest.version > MANIFEST_VERSION {
+            return Err(Error::invalid_manifest(format!(
+                "Manifest version {} is not supported (newest is {})",
+                manifest.version, MANIFEST_VERSION
+            )));
+        }
+        Ok(manifest)
+    }
+
+    /// Serializes the manifest as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] if serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// What [`restore_entry`] did with a project.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestoreStatus {
+    /// The project was missing and has been cloned.
+    Cloned,
+    /// The project was there; missing remotes have been added.
+    Updated,
+    /// The project was there with all remotes.
+    Unchanged,
+    /// The path exists, but is not a repository or has remotes with other URLs.
+    Conflict,
+    /// Cloning or adding remotes failed.
+    Failed,
+}
+
+/// A remote whose URL differs from the manifest.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoteConflict {
+    /// The name of the remote.
+    pub remote: String,
+
+    /// The URL in the manifest.
+    pub expected: String,
+
+    /// The URL in the existing repository.
+    pub actual: String,
+}
+
+/// Options for [`restore_entry`] and [`restore`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RestoreOptions {
+    /// Report what would be done without changing anything.
+    pub dry_run: bool,
+
+    /// Restore entries whose path is absolute or contains `..`.
+    ///
+    /// Such paths point outside the restore root, so they are rejected
+    /// unless set; manifests converted from other tools may contain them.
+    pub allow_outside_root: bool,
+}
+
+/// The outcome of restoring one project.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RestoreResult {
+    /// The path of the project.
+    pub path: PathBuf,
+
+    /// What was done.
+    pub status: RestoreStatus,
+
+    /// The remotes that were added, including the one cloned from.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_remotes: Vec<String>,
+
+    /// Remotes with the same name but another URL.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<RemoteConflict>,
+
+    /// Why the project could not be restored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Restores one project of a manifest under `root`.
+///
+/// Clones it if its path does not exist or is an empty directory, and adds
+/// missing remotes otherwise. With [`RestoreOptions::dry_run`], nothing is
+/// changed and the result says what would be done.
+///
+/// Failures are reported in the result, so that a restore can go on with
+/// the next project. An entry whose path is absolute or contains `..` fails
+/// without touching anything, unless [`RestoreOptions::allow_outside_root`]
+/// is set.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::manifest::{restore_entry, Manifest, RestoreOptions};
+/// use std::path::Path;
+///
+/// let manifest = Manifest::load(Path::new("workspace.json"))?;
+/// let options = RestoreOptions::default();
+/// for entry in &manifest.projects {
+///     let result = restore_entry(entry, Path::new("/home/me/src"), &options);
+///     println!("{}: {:?}", result.path.display(), result.status);
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn restore_entry(
+    entry: &ManifestEntry,
+    root: &Path,
+    options: &RestoreOptions,
+) -> RestoreResult {
+    let path = root.join(&entry.path);
+    let mut result = RestoreResult {
+        path: path.clone(),
+        status: RestoreStatus::Unchanged,
+        added_remotes: vec![],
+        conflicts: vec![],
+        error: None,
+    };
+
+    let outside = entry
+        .path
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+    if outside && !options.allow_outside_root {
+        result.status = RestoreStatus::Failed;
+        result.error = Some(format!(
+            "Path {} is outside the restore root",
+            entry.path.display()
+        ));
+        return result;
+    }
+
+    let missing = match std::fs::read_dir(&path) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => true,
+        Err(e) => {
+            result.status = RestoreStatus::Failed;
+            result.error = Some(e.to_string());
+            return result;
+        }
+    };
+    if missing {
+        result.status = RestoreStatus::Cloned;
+        result.added_remotes = entry.remotes.iter().map(|r| r.name.clone()).collect();
+        if !options.dry_run {
+            if let Err(e) = clone_entry(entry, &path) {
+                result.status = RestoreStatus::Failed;
+                result.error = Some(e.to_string());
+            }
+        }
+        return result;
+    }
+
+    let existing = match extract_remote_details(&path) {
+        Ok(existing) => existing,
+        Err(_) => {
+            result.status = RestoreStatus::Conflict;
+            result.error = Some("Path exists but is not a Git repository".to_string());
+            return result;
+        }
+    };
+    let mut to_add = Vec::new();
+    for remote in &entry.remotes {
+        let same_name = existing.iter().find(|r| r.name == remote.name);
+        match same_name.and_then(|r| r.fetch_url.as_deref()) {
+            Some(actual) if same_remote(actual, &remote.url) => {}
+            Some(actual) => result.conflicts.push(RemoteConflict {
+                remote: remote.name.clone(),
+                expected: remote.url.clone(),
+                actual: actual.to_string(),
+            }),
+            None => {
+                // The same URL under another name is good enough
+                let present = existing.iter().any(|r| {
+                    r.fetch_url
+                        .as_deref()
+                        .is_some_and(|url| same_remote(url, &remote.url))
+                });
+                if !present {
+                    to_add.push(remote);
+                }
+            }
+        }
+    }
+
+    if !result.conflicts.is_empty() {
+        result.status = RestoreStatus::Conflict;
+    } else if !to_add.is_empty() {
+        result.status = RestoreStatus::Updated;
+    }
+    if to_add.is_empty() {
+        return result;
+    }
+    result.added_remotes = to_add.iter().map(|r| r.name.clone()).collect();
+    if !options.dry_run {
+        if let Err(e) = add_remotes(&path, &to_add) {
+            result.status = RestoreStatus::Failed;
+            result.added_remotes.clear();
+            result.error = Some(e.to_string());
+        }
+    }
+    result
+}
+
+/// Restores all projects of a manifest under `root`; see [`restore_entry`].
+pub fn restore(manifest: &Manifest, root: &Path, options: &RestoreOptions) -> Vec<RestoreResult> {
+    manifest
+        .projects
+        .iter()
+        .map(|entry| restore_entry(entry, root, options))
+        .collect()
+}
+
+/// Differences between a manifest and the projects on disk.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ManifestCheck {
+    /// Paths in the manifest without a project.
+    pub missing: Vec<PathBuf>,
+
+    /// Projects that are not in the manifest.
+    pub extra: Vec<PathBuf>,
+
+    /// Projects that lack a remote of the manifest.
+    pub mismatched: Vec<RemoteMismatch>,
+}
+
+impl ManifestCheck {
+    /// Whether the manifest and the projects agree.
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+/// A project whose remotes differ from its manifest entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoteMismatch {
+    /// The path of the project, as in the manifest.
+    pub path: PathBuf,
+
+    /// The URLs in the manifest.
+    pub expected: Vec<String>,
+
+    /// The URLs of the project.
+    pub actual: Vec<String>,
+}
+
+/// Compares a manifest with scanned projects.
+///
+/// Projects are matched by their path relative to the scan root, as written
+/// by [`Manifest::from_projects`]; submodules are ignored. A project matches
+/// its entry if it has a remote for each URL in the manifest, whatever its
+/// name, since not all formats keep remote names.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::manifest::{check, Manifest};
+/// use git_projects_core::{DefaultScanner, ProjectScanner, ScanConfig};
+/// use std::path::Path;
+///
+/// let manifest = Manifest::load(Path::new(".repos"))?;
+/// let projects = DefaultScanner::new().scan(&ScanConfig::default())?;
+/// let result = check(&manifest, &projects);
+/// for path in &result.missing {
+///     println!("missing: {}", path.display());
+/// }
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn check(manifest: &Manifest, projects: &[GitProject]) -> ManifestCheck {
+    let key = |project: &GitProject| {
+        project
+            .relative_path
+            .clone()
+            .unwrap_or_else(|| project.path.clone())
+    };
+    let projects: Vec<(PathBuf, &GitProject)> = projects
+        .iter()
+        .filter(|p| !p.is_submodule)
+        .map(|p| (key(p), p))
+        .collect();
+
+    let mut result = ManifestCheck::default();
+    for entry in &manifest.projects {
+        let Some((_, project)) = projects.iter().find(|(path, _)| *path == entry.path) else {
+            result.missing.push(entry.path.clone());
+            continue;
+        };
+        let matches = entry.remotes.iter().all(|expected| {
+            project
+                .remotes
+                .iter()
+                .any(|actual| same_remote(&actual.url, &expected.url))
+        });
+        if !matches {
+            result.mismatched.push(RemoteMismatch {
+                path: entry.path.clone(),
+                expected: entry.remotes.iter().map(|r| r.url.clone()).collect(),
+                actual: project.remotes.iter().map(|r| r.url.clone()).collect(),
+            });
+        }
+    }
+    result.extra = projects
+        .into_iter()
+        .map(|(path, _)| path)
+        .filter(|path| !manifest.projects.iter().any(|entry| entry.path == *path))
+        .collect();
+    result
+}
+
+/// Whether two URLs point at the same repository, ignoring the scheme
+fn same_remote(a: &str, b: &str) -> bool {
+    a == b
+        || matches!(
+            (RemoteIdentity::parse(a), RemoteIdentity::parse(b)),
+            (Some(a), Some(b)) if a == b
+        )
+}
+
+/// Returns the branch `refs/remotes/<remote>/HEAD` points to
+fn remote_default_branch(repo_path: &Path, remote: &str) -> Option<String> {
+    let repo = gix::open(repo_path).ok()?;
+    let head = repo
+        .try_find_reference(format!("refs/remotes/{}/HEAD", remote).as_str())
+        .ok()??;
+    let target = head.target().try_name()?.as_bstr().to_string();
+    target
+        .strip_prefix(&format!("refs/remotes/{}/", remote))
+        .map(str::to_string)
+}
+
+/// Clones a project from its first remote and adds the others
+fn clone_entry(entry: &ManifestEntry, path: &Path) -> Result<()> {
+    let Some((first, others)) = entry.remotes.split_first() else {
+        return Err(Error::git_clone(path, "no remote to clone from"));
+    };
+    let clone_error = |e: &dyn std::fmt::Display| Error::git_clone(path, e.to_string());
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut prepare = gix::prepare_clone(first.url.as_str(), path)
+        .map_err(|e| clone_error(&e))?
+        .with_remote_name(first.name.as_str())
+        .map_err(|e| clone_error(&e))?;
+    if let Some(branch) = &entry.default_branch {
+        prepare = prepare
+            .with_ref_name(Some(branch.as_str()))
+            .map_err(|e| clone_error(&e))?;
+    }
+    if let Some(push_url) = first.push_url.clone() {
+        prepare = prepare.configure_remote(move |remote| {
+            Ok(remote.with_push_url_without_url_rewrite(push_url.as_str())?)
+        });
+    }
+
+    let interrupt = AtomicBool::new(false);
+    let (mut checkout, _) = prepare
+        .fetch_then_checkout(gix::progress::Discard, &interrupt)
+        .map_err(|e| clone_error(&e))?;
+    checkout
+        .main_worktree(gix::progress::Discard, &interrupt)
+        .map_err(|e| clone_error(&e))?;
+
+    let others: Vec<&ManifestRemote> = others.iter().collect();
+    add_remotes(path, &others)
+}
+
+/// Adds remotes to the local configuration of a repository
+fn add_remotes(path: &Path, remotes: &[&ManifestRemote]) -> Result<()> {
+    if remotes.is_empty() {
+        return Ok(());
+    }
+    let repo = gix::open(path).map_err(|e| Error::git_open(path, e))?;
+    let remote_error = |e: &dyn std::fmt::Display| Error::git_remote(path, e.to_string());
+
+    let mut config = gix::config::File::new(gix::config::file::Metadata::from(
+        gix::config::Source::Local,
+    ));
+    for remote in remotes {
+        let refspec = format!("+refs/heads/*:refs/remotes/{}/*", remote.name);
+        let mut new = repo
+            .remote_at_without_url_rewrite(remote.url.as_str())
+            .map_err(|e| remote_error(&e))?
+            .with_refspecs(Some(refspec.as_str()), gix::remote::Direction::Fetch)
+            .map_err(|e| remote_error(&e))?;
+        if let Some(push_url) = &remote.push_url {
+            new = new
+                .with_push_url_without_url_rewrite(push_url.as_str())
+                .map_err(|e| remote_error(&e))?;
+        }
+        new.save_as_to(remote.name.as_str(), &mut config)
+            .map_err(|e| remote_error(&e))?;
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(repo.git_dir().join("config"))?;
+    file.write_all(b"\n")?;
+    config.write_to(&mut file)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ScanConfig;
+    use crate::scanner::{DefaultScanner, ProjectScanner};
+    use tempfile::TempDir;
+
+    /// Creates a bare repository with one empty commit on `branch`, returning
+    /// its `file://` URL
+    fn bare_remote(dir: &Path, name: &str, branch: &str) -> String {
+        let path = dir.join(name);
+        let repo = gix::init_bare(&path).unwrap();
+        let tree = repo.write_object(gix::objs::Tree::empty()).unwrap();
+        let signature = gix::actor::SignatureRef {
+            name: "Dev".into(),
+            email: "dev@example.com".into(),
+            time: "0 +0000",
+        };
+        repo.commit_as(
+            signature,
+            signature,
+            format!("refs/heads/{}", branch).as_str(),
+            "Initial commit",
+            tree,
+            None::<gix::ObjectId>,
+        )
+        .unwrap();
+        format!("file://{}", path.display())
+    }
+
+    fn remote(name: &str, url: &str) -> ManifestRemote {
+        ManifestRemote {
+            name: name.to_string(),
+            url: url.to_string(),
+            push_url: None,
+        }
+    }
+
+    #[test]
+    fn test_restore_clones_missing_projects() {
+        let remotes = TempDir::new().unwrap();
+        let origin = bare_remote(remotes.path(), "tool.git", "develop");
+        let upstream = bare_remote(remotes.path(), "upstream.git", "main");
+        let entry = ManifestEntry {
+            path: PathBuf::from("acme/tool"),
+            remotes: vec![
+                ManifestRemote {
+                    push_url: Some("git@example.com:acme/tool.git".to_string()),
+                    ..remote("origin", &origin)
+                },
+                remote("upstream", &upstream),
+            ],
+            default_branch: Some("develop".to_string()),
+            tags: vec![],
+        };
+
+        let root = TempDir::new().unwrap();
+        let dry_run = RestoreOptions {
+            dry_run: true,
+            ..RestoreOptions::default()
+        };
+        let planned = restore_entry(&entry, root.path(), &dry_run);
+        assert_eq!(planned.status, RestoreStatus::Cloned);
+        assert!(!root.path().join("acme").exists());
+
+        let result = restore_entry(&entry, root.path(), &RestoreOptions::default());
+        assert_eq!(result.error, None);
+        assert_eq!(result.status, RestoreStatus::Cloned);
+        assert_eq!(result.added_remotes, ["origin", "upstream"]);
+
+        let path = root.path().join("acme/tool");
+        let repo = gix::open(&path).unwrap();
+        let head = repo.head_name().unwrap().unwrap();
+        assert_eq!(head.as_bstr(), "refs/heads/develop");
+        let details = extract_remote_details(&path).unwrap();
+        let urls: Vec<_> = details
+            .iter()
+            .map(|r| (r.name.as_str(), r.fetch_url.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            urls,
+            [("origin", origin.as_str()), ("upstream", upstream.as_str())]
+        );
+        assert_eq!(
+            details[0].push_url.as_deref(),
+            Some("git@example.com:acme/tool.git")
+        );
+
+        // A second restore finds everything in place
+        let again = restore_entry(&entry, root.path(), &RestoreOptions::default());
+        assert_eq!(again.status, RestoreStatus::Unchanged);
+    }
+
+    #[test]
+    fn test_restore_existing_projects() {
+        let remotes = TempDir::new().unwrap();
+        let origin = bare_remote(remotes.path(), "tool.git", "main");
+        let root = TempDir::new().unwrap();
+        let entry = ManifestEntry {
+            path: PathBuf::from("tool"),
+            remotes: vec![remote("origin", &origin)],
+            default_branch: None,
+            tags: vec![],
+        };
+        assert_eq!(
+            restore_entry(&entry, root.path(), &RestoreOptions::default()).status,
+            RestoreStatus::Cloned
+        );
+
+        // A new remote is added to the existing clone
+        let fork = bare_remote(remotes.path(), "fork.git", "main");
+        let mut with_fork = entry.clone();
+        with_fork.remotes.push(remote("fork", &fork));
+        let result = restore_entry(&with_fork, root.path(), &RestoreOptions::default());
+        assert_eq!(result.status, RestoreStatus::Updated);
+        assert_eq!(result.added_remotes, ["fork"]);
+        assert_eq!(
+            extract_remote_details(&root.path().join("tool"))
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // A remote with the same name but another URL is a conflict
+        let mut moved = entry.clone();
+        moved.remotes[0].url = fork.clone();
+        let result = restore_entry(&moved, root.path(), &RestoreOptions::default());
+        assert_eq!(result.status, RestoreStatus::Conflict);
+        assert_eq!(
+            result.conflicts,
+            [RemoteConflict {
+                remote: "origin".to_string(),
+                expected: fork,
+                actual: origin,
+            }]
+        );
+
+        // So is a directory that is not a repository
+        std::fs::create_dir(root.path().join("notes")).unwrap();
+        std::fs::write(root.path().join("notes/todo.txt"), "").unwrap();
+        let notes = ManifestEntry {
+            path: PathBuf::from("notes"),
+            ..entry
+        };
+        let result = restore_entry(&notes, root.path(), &RestoreOptions::default());
+        assert_eq!(result.status, RestoreStatus::Conflict);
+        assert!(result.error.is_some());
+    }
+
+    #[test]
+    fn test_restore_rejects_paths_outside_root() {
+        let remotes = TempDir::new().unwrap();
+        let origin = bare_remote(remotes.path(), "tool.git", "main");
+        let parent = TempDir::new().unwrap();
+        let root = parent.path().join("src");
+        std::fs::create_dir(&root).unwrap();
+        let entry = |path: PathBuf| ManifestEntry {
+            path,
+            remotes: vec![remote("origin", &origin)],
+            default_branch: None,
+            tags: vec![],
+        };
+
+        for path in [
+            PathBuf::from("../escaped"),
+            PathBuf::from("acme/../../escaped"),
+            parent.path().join("escaped"),
+        ] {
+            let result = restore_entry(&entry(path), &root, &RestoreOptions::default());
+            assert_eq!(result.status, RestoreStatus::Failed);
+            assert!(result.error.unwrap().contains("outside the restore root"));
+        }
+        assert!(!parent.path().join("escaped").exists());
+
+        // Unless explicitly allowed
+        let options = RestoreOptions {
+            allow_outside_root: true,
+            ..RestoreOptions::default()
+        };
+        let result = restore_entry(&entry(PathBuf::from("../escaped")), &root, &options);
+        assert_eq!(result.error, None);
+        assert_eq!(result.status, RestoreStatus::Cloned);
+        assert!(parent.path().join("escaped/.git").exists());
+    }
+
+    #[test]
+    fn test_manifest_from_projects() {
+        let remotes = TempDir::new().unwrap();
+        let origin = bare_remote(remotes.path(), "tool.git", "main");
+        let root = TempDir::new().unwrap();
+        let entry = ManifestEntry {
+            path: PathBuf::from("tool"),
+            remotes: vec![remote("origin", &origin)],
+            default_branch: Some("main".to_string()),
+            tags: vec!["oncall".to_string()],
+        };
+        restore_entry(&entry, root.path(), &RestoreOptions::default());
+
+        let scan_config = ScanConfig {
+            root_paths: vec![root.path().to_path_buf()],
+            ..ScanConfig::default()
+        };
+        let mut projects = DefaultScanner::new().scan(&scan_config).unwrap();
+        projects[0].tags = vec!["oncall".to_string()];
+        let manifest = Manifest::from_projects(&projects).unwrap();
+        assert_eq!(manifest.projects, [entry]);
+
+        let parsed = Manifest::parse(&manifest.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, manifest);
+        assert!(Manifest::parse(r#"{"version": 99, "projects": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let project = |path: &str, url: &str| GitProject {
+            scan_root: Some(PathBuf::from("/src")),
+            relative_path: Some(PathBuf::from(path)),
+            ..GitProject::test(PathBuf::from("/src").join(path)).with_named_remote("github", url)
+        };
+        let entry = |path: &str, url: &str| ManifestEntry {
+            path: PathBuf::from(path),
+            remotes: vec![remote("origin", url)],
+            default_branch: None,
+            tags: vec![],
+        };
+        let manifest = Manifest {
+            version: MANIFEST_VERSION,
+            projects: vec![
+                entry("tool", "https://github.com/acme/tool.git"),
+                entry("lib", "https://github.com/acme/lib.git"),
+                entry("gone", "https://github.com/acme/gone.git"),
+            ],
+        };
+        let projects = [
+            project("tool", "git@github.com:acme/tool"),
+            project("lib", "https://github.com/fork/lib.git"),
+            project("scratch", "https://github.com/acme/scratch.git"),
+        ];
+
+        let result = check(&manifest, &projects);
+        assert_eq!(result.missing, [PathBuf::from("gone")]);
+        assert_eq!(result.extra, [PathBuf::from("scratch")]);
+        assert_eq!(
+            result.mismatched,
+            [RemoteMismatch {
+                path: PathBuf::from("lib"),
+                expected: vec!["https://github.com/acme/lib.git".to_string()],
+                actual: vec!["https://github.com/fork/lib.git".to_string()],
+            }]
+        );
+        assert!(!result.is_clean());
+        assert!(check(&manifest, &projects[..1]).mismatched.is_empty());
+    }
+}
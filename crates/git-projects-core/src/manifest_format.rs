@@ -0,0 +1,793 @@
+//! Manifests of other multi-repository tools.
+//!
+//! Converts a [`Manifest`] to and from the formats of
+//! [myrepos](https://myrepos.branchable.com/) (`mr`, `.mrconfig`),
+//! [vcstool](https://github.com/dirk-thomas/vcstool) (`.repos`) and Google's
+//! [repo](https://gerrit.googlesource.com/git-repo) (`default.xml`), so that
+//! they can be generated from a scan, restored or
+//! [checked](crate::manifest::check) against the disk.
+//!
+//! None of them holds everything a manifest does:
+//!
+//! | Format     | Remotes            | Branch | Tags     |
+//! |------------|--------------------|--------|----------|
+//! | `mrconfig` | all, with push URL | yes    | no       |
+//! | `vcstool`  | first only         | yes    | no       |
+//! | `repo`     | first only         | yes    | `groups` |
+//!
+//! Remotes read from vcstool and repo manifests are named `origin`.
+
+use crate::error::{Error, Result};
+use crate::manifest::{Manifest, ManifestEntry, ManifestRemote, MANIFEST_VERSION};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+/// A manifest file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    /// The JSON format of [`Manifest`].
+    Json,
+    /// A myrepos `.mrconfig`, with a `git clone` checkout command per repository.
+    Mrconfig,
+    /// A vcstool `.repos` YAML file.
+    Vcstool,
+    /// A repo manifest XML file.
+    Repo,
+}
+
+impl ManifestFormat {
+    /// Guesses the format from the first character that is not whitespace or
+    /// in a comment line: `{` for JSON, `<` for repo XML, `[` for `.mrconfig`
+    /// sections, and YAML otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use git_projects_core::ManifestFormat;
+    ///
+    /// assert_eq!(ManifestFormat::detect("# mr\n[src/tool]\n"), ManifestFormat::Mrconfig);
+    /// assert_eq!(ManifestFormat::detect("repositories:\n"), ManifestFormat::Vcstool);
+    /// ```
+    pub fn detect(source: &str) -> Self {
+        let first = source
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with(';'))
+            .and_then(|line| line.chars().next());
+        match first {
+            Some('{') => Self::Json,
+            Some('<') => Self::Repo,
+            Some('[') => Self::Mrconfig,
+            _ => Self::Vcstool,
+        }
+    }
+
+    /// Parses a manifest in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidManifest`] if the source is malformed, or
+    /// [`Error::Json`] for malformed JSON.
+    pub fn parse(self, source: &str) -> Result<Manifest> {
+        let projects = match self {
+            Self::Json => return Manifest::parse(source),
+            Self::Mrconfig => parse_mrconfig(source),
+            Self::Vcstool => parse_vcstool(source)?,
+            Self::Repo => parse_repo(source)?,
+        };
+        Ok(Manifest {
+            version: MANIFEST_VERSION,
+            projects,
+        })
+    }
+
+    /// Renders a manifest in this format.
+    ///
+    /// Projects without a remote are left out of the formats of other tools,
+    /// which cannot clone them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] or [`Error::InvalidManifest`] if serialization
+    /// fails.
+    pub fn render(self, manifest: &Manifest) -> Result<String> {
+        match self {
+            Self::Json => manifest.to_json().map(|json| json + "\n"),
+            Self::Mrconfig => Ok(render_mrconfig(manifest)),
+            Self::Vcstool => render_vcstool(manifest),
+            Self::Repo => Ok(render_repo(manifest)),
+        }
+    }
+}
+
+/// Quotes a word for a POSIX shell
+fn shell_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
+
+/// Splits a line of shell commands into words, with `&&`, `||` and `;` as
+/// separate words
+///
+/// Handles single and double quotes and backslashes; anything fancier is
+/// taken literally.
+fn shell_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    word.push(c);
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => word.extend(chars.next()),
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                word.extend(chars.next());
+            }
+            ';' | '&' | '|' => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+                let mut separator = c.to_string();
+                if c != ';' && chars.peek() == Some(&c) {
+                    separator.extend(chars.next());
+                }
+                words.push(separator);
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '#' if !in_word => break,
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+/// Options of `git clone` that take a value
+const CLONE_VALUE_OPTIONS: &[&str] = &[
+    "-b",
+    "--branch",
+    "-o",
+    "--origin",
+    "-c",
+    "--config",
+    "-j",
+    "--jobs",
+    "-u",
+    "--upload-pack",
+    "--depth",
+    "--filter",
+    "--reference",
+    "--reference-if-able",
+    "--separate-git-dir",
+    "--shallow-exclude",
+    "--shallow-since",
+    "--template",
+];
+
+/// Renders a `.mrconfig` whose checkout commands clone the first remote and
+/// add the others
+fn render_mrconfig(manifest: &Manifest) -> String {
+    let mut out = String::new();
+    for entry in &manifest.projects {
+        let Some(first) = entry.remotes.first() else {
+            continue;
+        };
+        let dir = entry
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| entry.path.display().to_string());
+
+        let mut clone = String::from("git clone");
+        if first.name != "origin" {
+            clone += &format!(" --origin {}", shell_quote(&first.name));
+        }
+        if let Some(branch) = &entry.default_branch {
+            clone += &format!(" --branch {}", shell_quote(branch));
+        }
+        clone += &format!(" {} {}", shell_quote(&first.url), shell_quote(&dir));
+
+        let mut commands = vec![clone];
+        let mut setup = Vec::new();
+        for (i, remote) in entry.remotes.iter().enumerate() {
+            if i > 0 {
+                setup.push(format!(
+                    "git remote add {} {}",
+                    shell_quote(&remote.name),
+                    shell_quote(&remote.url)
+                ));
+            }
+            if let Some(push_url) = &remote.push_url {
+                setup.push(format!(
+                    "git remote set-url --push {} {}",
+                    shell_quote(&remote.name),
+                    shell_quote(push_url)
+                ));
+            }
+        }
+        if !setup.is_empty() {
+            commands.push(format!("cd {}", shell_quote(&dir)));
+            commands.extend(setup);
+        }
+
+        let _ = writeln!(out, "[{}]", entry.path.display());
+        if let [command] = commands.as_slice() {
+            let _ = writeln!(out, "checkout = {}", command);
+        } else {
+            let _ = writeln!(out, "checkout =");
+            for command in commands {
+                let _ = writeln!(out, "\t{}", command);
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Reads the `git clone` and `git remote` commands of each section's
+/// checkout command; sections without `git clone` are skipped
+fn parse_mrconfig(source: &str) -> Vec<ManifestEntry> {
+    // Sections with their checkout command, continuation lines joined
+    let mut sections: Vec<(String, Vec<String>)> = Vec::new();
+    let mut in_checkout = false;
+    for line in source.lines() {
+        let trimmed = line.trim();
+        let continuation = line.starts_with([' ', '\t']);
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+        if continuation {
+            if in_checkout {
+                if let Some((_, lines)) = sections.last_mut() {
+                    lines.push(trimmed.to_string());
+                }
+            }
+            continue;
+        }
+        if let Some(section) = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            sections.push((section.trim().to_string(), Vec::new()));
+            in_checkout = false;
+            continue;
+        }
+        in_checkout = false;
+        if let Some((key, value)) = trimmed.split_once('=') {
+            if key.trim() == "checkout" {
+                in_checkout = true;
+                if let Some((_, lines)) = sections.last_mut() {
+                    if !value.trim().is_empty() {
+                        lines.push(value.trim().to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    sections
+        .into_iter()
+        .filter(|(path, _)| path != "DEFAULT")
+        .filter_map(|(path, lines)| mr_entry(path, &lines))
+        .collect()
+}
+
+/// Builds an entry from the commands of a checkout script
+fn mr_entry(path: String, lines: &[String]) -> Option<ManifestEntry> {
+    let mut entry = ManifestEntry {
+        path: PathBuf::from(path),
+        remotes: Vec::new(),
+        default_branch: None,
+        tags: Vec::new(),
+    };
+    // Each line is a command of its own
+    let words: Vec<String> = lines
+        .iter()
+        .flat_map(|line| shell_words(line).into_iter().chain([";".to_string()]))
+        .collect();
+    let commands = words.split(|word| matches!(word.as_str(), "&&" | "||" | ";" | "&" | "|"));
+    for command in commands {
+        match command {
+            [git, clone, args @ ..] if git == "git" && clone == "clone" => {
+                let mut name = "origin".to_string();
+                let mut positional = Vec::new();
+                let mut args = args.iter();
+                while let Some(arg) = args.next() {
+                    if let Some((option, value)) = arg.split_once('=') {
+                        if option.starts_with("--") {
+                            match option {
+                                "--branch" => entry.default_branch = Some(value.to_string()),
+                                "--origin" => name = value.to_string(),
+                                _ => {}
+                            }
+                            continue;
+                        }
+                    }
+                    if CLONE_VALUE_OPTIONS.contains(&arg.as_str()) {
+                        let value = args.next().cloned();
+                        match arg.as_str() {
+                            "-b" | "--branch" => entry.default_branch = value,
+                            "-o" | "--origin" => name = value.unwrap_or(name),
+                            _ => {}
+                        }
+                    } else if !arg.starts_with('-') {
+                        positional.push(arg.clone());
+                    }
+                }
+                let url = positional.into_iter().next()?;
+                entry.remotes.insert(
+                    0,
+                    ManifestRemote {
+                        name,
+                        url,
+                        push_url: None,
+                    },
+                );
+            }
+            [git, remote, add, args @ ..] if git == "git" && remote == "remote" && add == "add" => {
+                let positional: Vec<&String> =
+                    args.iter().filter(|arg| !arg.starts_with('-')).collect();
+                if let [name, url] = positional.as_slice() {
+                    entry.remotes.push(ManifestRemote {
+                        name: name.to_string(),
+                        url: url.to_string(),
+                        push_url: None,
+                    });
+                }
+            }
+            [git, remote, set_url, push, name, url]
+                if git == "git"
+                    && remote == "remote"
+                    && set_url == "set-url"
+                    && push == "--push" =>
+            {
+                if let Some(remote) = entry.remotes.iter_mut().find(|r| r.name == *name) {
+                    remote.push_url = Some(url.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    // Only sections that clone a Git repository
+    if entry.remotes.is_empty() || !words.iter().any(|word| word == "clone") {
+        return None;
+    }
+    Some(entry)
+}
+
+/// A vcstool `.repos` file
+#[derive(Debug, Serialize, Deserialize)]
+struct VcsFile {
+    #[serde(default)]
+    repositories: BTreeMap<String, VcsRepository>,
+}
+
+/// A repository in a vcstool `.repos` file
+#[derive(Debug, Serialize, Deserialize)]
+struct VcsRepository {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+}
+
+/// Renders a vcstool `.repos` file, with the first remote of each project
+fn render_vcstool(manifest: &Manifest) -> Result<String> {
+    let repositories = manifest
+        .projects
+        .iter()
+        .filter_map(|entry| {
+            let remote = entry.remotes.first()?;
+            Some((
+                entry.path.display().to_string(),
+                VcsRepository {
+                    kind: "git".to_string(),
+                    url: remote.url.clone(),
+                    version: entry.default_branch.clone(),
+                },
+            ))
+        })
+        .collect();
+    serde_norway::to_string(&VcsFile { repositories })
+        .map_err(|e| Error::invalid_manifest(e.to_string()))
+}
+
+/// Reads the Git repositories of a vcstool `.repos` file
+fn parse_vcstool(source: &str) -> Result<Vec<ManifestEntry>> {
+    let file: VcsFile =
+        serde_norway::from_str(source).map_err(|e| Error::invalid_manifest(e.to_string()))?;
+    Ok(file
+        .repositories
+        .into_iter()
+        .filter(|(_, repository)| repository.kind == "git")
+        .map(|(path, repository)| ManifestEntry {
+            path: PathBuf::from(path),
+            remotes: vec![ManifestRemote {
+                name: "origin".to_string(),
+                url: repository.url,
+                push_url: None,
+            }],
+            default_branch: repository.version,
+            tags: Vec::new(),
+        })
+        .collect())
+}
+
+/// Splits a URL into the part up to the host, for a repo `<remote>`, and
+/// the path, for the project name
+///
+/// SCP-like URLs (`git@host:path`) become `ssh://` URLs, which repo can join.
+fn split_url(url: &str) -> (String, String) {
+    if let Some((scheme, rest)) = url.split_once("://") {
+        let host_end = rest.find('/').unwrap_or(rest.len());
+        let (host, path) = rest.split_at(host_end);
+        return (
+            format!("{}://{}/", scheme, host),
+            path.trim_start_matches('/').to_string(),
+        );
+    }
+    match url.split_once(':') {
+        Some((host, path)) if !host.contains('/') && !host.is_empty() => (
+            format!("ssh://{}/", host),
+            path.trim_start_matches('/').to_string(),
+        ),
+        _ => (String::new(), url.to_string()),
+    }
+}
+
+/// Escapes a string for an XML attribute value
+pub(crate) fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders a repo manifest with one `<remote>` per host
+fn render_repo(manifest: &Manifest) -> String {
+    // Fetch URL -> remote name, named after the host
+    let mut remotes: Vec<(String, String)> = Vec::new();
+    let mut projects = Vec::new();
+    for entry in &manifest.projects {
+        let Some(remote) = entry.remotes.first() else {
+            continue;
+        };
+        let (fetch, name) = split_url(&remote.url);
+        let remote_name = match remotes.iter().find(|(f, _)| *f == fetch) {
+            Some((_, remote_name)) => remote_name.clone(),
+            None => {
+                let host = fetch
+                    .split_once("://")
+                    .map_or(fetch.as_str(), |(_, rest)| rest)
+                    .trim_end_matches('/');
+                let host = host.rsplit('@').next().unwrap_or(host);
+                let base = if host.is_empty() { "local" } else { host };
+                let mut remote_name = base.to_string();
+                let mut n = 1;
+                while remotes.iter().any(|(_, r)| *r == remote_name) {
+                    n += 1;
+                    remote_name = format!("{}-{}", base, n);
+                }
+                remotes.push((fetch, remote_name.clone()));
+                remote_name
+            }
+        };
+        projects.push((entry, name, remote_name));
+    }
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<manifest>\n");
+    for (fetch, name) in &remotes {
+        let _ = writeln!(
+            out,
+            "  <remote name=\"{}\" fetch=\"{}\" />",
+            xml_escape(name),
+            xml_escape(fetch)
+        );
+    }
+    for (entry, name, remote) in projects {
+        let _ = write!(
+            out,
+            "  <project name=\"{}\" path=\"{}\" remote=\"{}\"",
+            xml_escape(&name),
+            xml_escape(&entry.path.display().to_string()),
+            xml_escape(&remote)
+        );
+        if let Some(branch) = &entry.default_branch {
+            let _ = write!(out, " revision=\"{}\"", xml_escape(branch));
+        }
+        if !entry.tags.is_empty() {
+            let _ = write!(out, " groups=\"{}\"", xml_escape(&entry.tags.join(",")));
+        }
+        out.push_str(" />\n");
+    }
+    out.push_str("</manifest>\n");
+    out
+}
+
+/// Reads the projects of a repo manifest, resolving their remotes and the
+/// `<default>` remote and revision
+fn parse_repo(source: &str) -> Result<Vec<ManifestEntry>> {
+    let document =
+        roxmltree::Document::parse(source).map_err(|e| Error::invalid_manifest(e.to_string()))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "manifest" {
+        return Err(Error::invalid_manifest("expected a <manifest> element"));
+    }
+    let elements = |name: &'static str| {
+        root.children()
+            .filter(move |node| node.is_element() && node.tag_name().name() == name)
+    };
+
+    let fetch_urls: BTreeMap<&str, &str> = elements("remote")
+        .filter_map(|remote| Some((remote.attribute("name")?, remote.attribute("fetch")?)))
+        .collect();
+    let default = elements("default").next();
+    let default_remote = default.and_then(|d| d.attribute("remote"));
+    let default_revision = default.and_then(|d| d.attribute("revision"));
+
+    let mut entries = Vec::new();
+    for project in elements("project") {
+        let name = project
+            .attribute("name")
+            .ok_or_else(|| Error::invalid_manifest("<project> without a name"))?;
+        let remote = project
+            .attribute("remote")
+            .or(default_remote)
+            .ok_or_else(|| Error::invalid_manifest(format!("no remote for project {}", name)))?;
+        let fetch = fetch_urls
+            .get(remote)
+            .ok_or_else(|| Error::invalid_manifest(format!("unknown remote {}", remote)))?;
+        if !fetch.contains("://") {
+            return Err(Error::invalid_manifest(format!(
+                "relative fetch URL {} of remote {} is not supported",
+                fetch, remote
+            )));
+        }
+        let url = if fetch.ends_with('/') {
+            format!("{}{}", fetch, name)
+        } else {
+            format!("{}/{}", fetch, name)
+        };
+        let tags = project
+            .attribute("groups")
+            .unwrap_or_default()
+            .split([',', ' '])
+            .filter(|group| !group.is_empty() && !group.contains(':'))
+            .map(str::to_string)
+            .collect();
+
+        entries.push(ManifestEntry {
+            path: PathBuf::from(project.attribute("path").unwrap_or(name)),
+            remotes: vec![ManifestRemote {
+                name: "origin".to_string(),
+                url,
+                push_url: None,
+            }],
+            default_branch: project
+                .attribute("revision")
+                .or(default_revision)
+                .map(str::to_string),
+            tags,
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> Manifest {
+        Manifest {
+            version: MANIFEST_VERSION,
+            projects: vec![
+                ManifestEntry {
+                    path: PathBuf::from("acme/tool"),
+                    remotes: vec![
+                        ManifestRemote {
+                            name: "origin".to_string(),
+                            url: "https://github.com/acme/tool.git".to_string(),
+                            push_url: Some("git@github.com:acme/tool.git".to_string()),
+                        },
+                        ManifestRemote {
+                            name: "upstream".to_string(),
+                            url: "https://github.com/upstream/tool.git".to_string(),
+                            push_url: None,
+                        },
+                    ],
+                    default_branch: Some("main".to_string()),
+                    tags: vec!["oncall".to_string()],
+                },
+                ManifestEntry {
+                    path: PathBuf::from("lib"),
+                    remotes: vec![ManifestRemote {
+                        name: "origin".to_string(),
+                        url: "git@gitlab.com:acme/it's lib.git".to_string(),
+                        push_url: None,
+                    }],
+                    default_branch: None,
+                    tags: vec![],
+                },
+                ManifestEntry {
+                    path: PathBuf::from("scratch"),
+                    remotes: vec![],
+                    default_branch: None,
+                    tags: vec![],
+                },
+            ],
+        }
+    }
+
+    /// Keeps what a format with only the first remote can hold
+    fn first_remote_only(mut manifest: Manifest, tags: bool) -> Manifest {
+        manifest.projects.retain(|entry| !entry.remotes.is_empty());
+        for entry in &mut manifest.projects {
+            entry.remotes.truncate(1);
+            entry.remotes[0].push_url = None;
+            if !tags {
+                entry.tags.clear();
+            }
+        }
+        manifest
+    }
+
+    #[test]
+    fn test_mrconfig_round_trip() {
+        let manifest = manifest();
+        let rendered = ManifestFormat::Mrconfig.render(&manifest).unwrap();
+        assert!(rendered.contains(
+            "[acme/tool]\ncheckout =\n\tgit clone --branch 'main' 'https://github.com/acme/tool.git' 'tool'\n\tcd 'tool'\n"
+        ));
+        assert!(rendered
+            .contains("checkout = git clone 'git@gitlab.com:acme/it'\\''s lib.git' 'lib'\n"));
+        assert_eq!(ManifestFormat::detect(&rendered), ManifestFormat::Mrconfig);
+
+        let mut expected = manifest;
+        expected.projects.truncate(2);
+        expected.projects[0].tags.clear();
+        assert_eq!(ManifestFormat::Mrconfig.parse(&rendered).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_mrconfig_handwritten() {
+        let source = "\
+[DEFAULT]
+lib = echo hi
+
+# A comment
+[src/tool]
+checkout = git clone -b develop --depth 1 https://example.com/tool.git tool && cd tool && git remote add -f fork \"https://example.com/fork.git\"
+update = git pull
+
+[src/notes]
+checkout = svn co https://example.com/svn/notes notes
+";
+        let manifest = ManifestFormat::Mrconfig.parse(source).unwrap();
+        assert_eq!(manifest.projects.len(), 1);
+        let entry = &manifest.projects[0];
+        assert_eq!(entry.path, PathBuf::from("src/tool"));
+        assert_eq!(entry.default_branch.as_deref(), Some("develop"));
+        let remotes: Vec<_> = entry
+            .remotes
+            .iter()
+            .map(|r| (r.name.as_str(), r.url.as_str()))
+            .collect();
+        assert_eq!(
+            remotes,
+            [
+                ("origin", "https://example.com/tool.git"),
+                ("fork", "https://example.com/fork.git")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vcstool_round_trip() {
+        let rendered = ManifestFormat::Vcstool.render(&manifest()).unwrap();
+        assert!(rendered.starts_with("repositories:\n  acme/tool:\n    type: git\n"));
+        assert_eq!(ManifestFormat::detect(&rendered), ManifestFormat::Vcstool);
+        assert_eq!(
+            ManifestFormat::Vcstool.parse(&rendered).unwrap(),
+            first_remote_only(manifest(), false)
+        );
+
+        let source = "repositories:\n  a:\n    type: hg\n    url: https://example.com/a\n";
+        assert!(ManifestFormat::Vcstool
+            .parse(source)
+            .unwrap()
+            .projects
+            .is_empty());
+        assert!(ManifestFormat::Vcstool.parse("repositories: [").is_err());
+    }
+
+    #[test]
+    fn test_repo_round_trip() {
+        let rendered = ManifestFormat::Repo.render(&manifest()).unwrap();
+        assert!(rendered.contains("<remote name=\"github.com\" fetch=\"https://github.com/\" />"));
+        assert!(rendered.contains(
+            "<project name=\"acme/tool.git\" path=\"acme/tool\" remote=\"github.com\" revision=\"main\" groups=\"oncall\" />"
+        ));
+        assert_eq!(ManifestFormat::detect(&rendered), ManifestFormat::Repo);
+
+        // SCP-like URLs come back as ssh:// URLs
+        let mut expected = first_remote_only(manifest(), true);
+        expected.projects[1].remotes[0].url = "ssh://git@gitlab.com/acme/it's lib.git".to_string();
+        assert_eq!(ManifestFormat::Repo.parse(&rendered).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_repo_defaults() {
+        let source = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest>
+  <remote name="aosp" fetch="https://android.googlesource.com" />
+  <default remote="aosp" revision="main" />
+  <project name="platform/build" path="build/make" groups="pdk,name:build" />
+  <project name="tools/repo" revision="stable" />
+</manifest>"#;
+        let manifest = ManifestFormat::Repo.parse(source).unwrap();
+        let entries: Vec<_> = manifest
+            .projects
+            .iter()
+            .map(|e| {
+                (
+                    e.path.to_str().unwrap(),
+                    e.remotes[0].url.as_str(),
+                    e.default_branch.as_deref().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (
+                    "build/make",
+                    "https://android.googlesource.com/platform/build",
+                    "main"
+                ),
+                (
+                    "tools/repo",
+                    "https://android.googlesource.com/tools/repo",
+                    "stable"
+                )
+            ]
+        );
+        assert_eq!(manifest.projects[0].tags, ["pdk"]);
+
+        let relative =
+            r#"<manifest><remote name="r" fetch=".." /><project name="a" remote="r" /></manifest>"#;
+        assert!(ManifestFormat::Repo.parse(relative).is_err());
+        assert!(ManifestFormat::Repo.parse("<other />").is_err());
+    }
+}
@@ -0,0 +1,347 @@
+//! Tags and notes stored outside the repositories.
+//!
+//! Repositories can be tagged (`client-x`, `archived`, `oncall`) and given a
+//! short note without touching the repositories themselves. The
+//! [`MetadataStore`] keeps them in a TOML file in the configuration
+//! directory, `~/.config/git-projects/metadata.toml`:
+//!
+//! ```toml
+//! ["github.com/acme/tool"]
+//! tags = ["client-x", "oncall"]
+//! notes = "Deployed from the release branch"
+//!
+//! ["/home/me/scratch/prototype"]
+//! tags = ["archived"]
+//! ```
+//!
+//! Entries are keyed by the normalized [identity](crate::RemoteIdentity) of
+//! the primary remote, so they follow a project across clones and machines.
+//! Projects without a remote fall back to their absolute path.
+
+use crate::error::{Error, Result};
+use crate::models::GitProject;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Name of the metadata file in the configuration directory.
+pub const METADATA_FILE: &str = "metadata.toml";
+
+/// Tags and notes of one project.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectMetadata {
+    /// Tags, in the order they were added.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// A short free-form note.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl ProjectMetadata {
+    fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.notes.is_none()
+    }
+}
+
+/// Tags and notes of projects, keyed by remote identity or path.
+///
+/// # Example
+///
+/// ```
+/// use git_projects_core::MetadataStore;
+///
+/// let mut store = MetadataStore::default();
+/// store.add_tags("github.com/acme/tool", &["client-x".to_string()]);
+/// assert_eq!(store.get("github.com/acme/tool").unwrap().tags, ["client-x"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetadataStore {
+    entries: BTreeMap<String, ProjectMetadata>,
+}
+
+impl MetadataStore {
+    /// Returns the path of the metadata file in the configuration directory
+    /// (see [`Config::default_path`](crate::Config::default_path)).
+    pub fn default_path() -> Option<PathBuf> {
+        crate::config::config_dir().map(|dir| dir.join(METADATA_FILE))
+    }
+
+    /// Loads the store from a file.
+    ///
+    /// Returns an empty store if the file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read, or [`Error::Config`]
+    /// if it is malformed.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let source = std::fs::read_to_string(path)?;
+        Self::parse(&source, path)
+    }
+
+    /// Parses the contents of a metadata file; `path` is used in errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] with the line and column of the error.
+    pub fn parse(source: &str, path: &Path) -> Result<Self> {
+        let entries = toml::from_str(source).map_err(|e| {
+            let offset = e.span().map_or(0, |span| span.start);
+            let (line, column) = crate::config::location(source, offset);
+            Error::config(path, line, column, e.message())
+        })?;
+        Ok(Self { entries })
+    }
+
+    /// Writes the store to a file, creating its directory if needed.
+    ///
+    /// The file is replaced atomically, so a failed write never leaves a
+    /// truncated store behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let source = toml::to_string(&self.entries)
+            .map_err(|e| Error::other(format!("Failed to serialize metadata: {}", e)))?;
+        crate::config::write_atomic(path, &source)?;
+        Ok(())
+    }
+
+    /// Returns the key under which the metadata of `project` is stored: the
+    /// identity of its primary remote, or its absolute path.
+    pub fn key(project: &GitProject) -> String {
+        match project.identity() {
+            Some(identity) => identity.to_string(),
+            None => path_key(&project.path),
+        }
+    }
+
+    /// Returns the key for the repository containing `path`.
+    ///
+    /// The repository is opened to read its remotes, so the key matches the
+    /// one of the scanned project.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::GitDiscover`] if `path` is not inside a repository.
+    pub fn key_for_path(path: &Path) -> Result<String> {
+        let repo = gix::discover(path).map_err(|e| Error::git_discover(path, e))?;
+        let root = repo.workdir().unwrap_or_else(|| repo.path());
+        let project = GitProject {
+            remotes: crate::git_analyzer::extract_remote_urls(root).unwrap_or_default(),
+            ..GitProject::test(root)
+        };
+        Ok(Self::key(&project))
+    }
+
+    /// Returns the metadata stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&ProjectMetadata> {
+        self.entries.get(key)
+    }
+
+    /// Iterates over all entries, ordered by key.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &ProjectMetadata)> {
+        self.entries.iter().map(|(key, meta)| (key.as_str(), meta))
+    }
+
+    /// Adds tags to the entry for `key`, skipping tags it already has.
+    ///
+    /// Returns the number of tags added.
+    pub fn add_tags(&mut self, key: &str, tags: &[String]) -> usize {
+        let entry = self.entries.entry(key.to_string()).or_default();
+        let mut added = 0;
+        for tag in tags {
+            if !entry.tags.contains(tag) {
+                entry.tags.push(tag.clone());
+                added += 1;
+            }
+        }
+        self.remove_if_empty(key);
+        added
+    }
+
+    /// Removes tags from the entry for `key`.
+    ///
+    /// Returns the number of tags removed.
+    pub fn remove_tags(&mut self, key: &str, tags: &[String]) -> usize {
+        let Some(entry) = self.entries.get_mut(key) else {
+            return 0;
+        };
+        let before = entry.tags.len();
+        entry.tags.retain(|tag| !tags.contains(tag));
+        let removed = before - entry.tags.len();
+        self.remove_if_empty(key);
+        removed
+    }
+
+    /// Sets or clears the note of the entry for `key`.
+    pub fn set_notes(&mut self, key: &str, notes: Option<String>) {
+        self.entries.entry(key.to_string()).or_default().notes = notes;
+        self.remove_if_empty(key);
+    }
+
+    /// Returns every tag with the number of entries that have it.
+    pub fn tag_counts(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for tag in self.entries.values().flat_map(|meta| &meta.tags) {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Merges the stored tags and notes into scan results.
+    ///
+    /// Both the identity entry and the path entry of a project apply, so
+    /// tags added before a remote was configured are kept. A note in the
+    /// identity entry takes precedence.
+    pub fn apply(&self, projects: &mut [GitProject]) {
+        for project in projects {
+            let by_identity = project
+                .identity()
+                .and_then(|identity| self.entries.get(&identity.to_string()));
+            let by_path = self.entries.get(&path_key(&project.path));
+
+            for meta in by_identity.into_iter().chain(by_path) {
+                for tag in &meta.tags {
+                    if !project.tags.contains(tag) {
+                        project.tags.push(tag.clone());
+                    }
+                }
+                if project.notes.is_none() {
+                    project.notes = meta.notes.clone();
+                }
+            }
+        }
+    }
+
+    fn remove_if_empty(&mut self, key: &str) {
+        if self.entries.get(key).is_some_and(ProjectMetadata::is_empty) {
+            self.entries.remove(key);
+        }
+    }
+}
+
+/// Returns the key of a project without a remote: its canonical path.
+fn path_key(path: &Path) -> String {
+    std::fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(path: &str, url: Option<&str>) -> GitProject {
+        url.into_iter()
+            .fold(GitProject::test(path), GitProject::with_remote)
+    }
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_add_and_remove_tags() {
+        let mut store = MetadataStore::default();
+        assert_eq!(store.add_tags("a", &tags(&["x", "y"])), 2);
+        assert_eq!(store.add_tags("a", &tags(&["y", "z"])), 1);
+        assert_eq!(store.get("a").unwrap().tags, ["x", "y", "z"]);
+
+        assert_eq!(store.remove_tags("a", &tags(&["x", "nope"])), 1);
+        assert_eq!(store.remove_tags("missing", &tags(&["x"])), 0);
+        store.add_tags("b", &tags(&["z"]));
+        assert_eq!(
+            store.tag_counts().into_iter().collect::<Vec<_>>(),
+            [("y", 1), ("z", 2)]
+        );
+
+        // Entries without tags or notes are dropped
+        store.remove_tags("a", &tags(&["y", "z"]));
+        assert!(store.get("a").is_none());
+        store.set_notes("b", Some("note".to_string()));
+        store.remove_tags("b", &tags(&["z"]));
+        assert_eq!(store.get("b").unwrap().notes.as_deref(), Some("note"));
+    }
+
+    #[test]
+    fn test_apply_by_identity_and_path() {
+        let mut store = MetadataStore::default();
+        store.add_tags("github.com/acme/tool", &tags(&["client-x"]));
+        store.set_notes("github.com/acme/tool", Some("prod".to_string()));
+        store.add_tags("/w/tool", &tags(&["client-x", "oncall"]));
+        store.add_tags("/w/notes", &tags(&["personal"]));
+
+        let mut projects = vec![
+            project("/w/tool", Some("git@github.com:Acme/Tool.git")),
+            project("/w/notes", None),
+            project("/w/other", None),
+        ];
+        projects[0].tags = tags(&["from-config"]);
+        store.apply(&mut projects);
+
+        assert_eq!(projects[0].tags, ["from-config", "client-x", "oncall"]);
+        assert_eq!(projects[0].notes.as_deref(), Some("prod"));
+        assert_eq!(projects[1].tags, ["personal"]);
+        assert!(projects[2].tags.is_empty());
+        assert_eq!(
+            MetadataStore::key(&projects[0]),
+            "github.com/acme/tool".to_string()
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("nested").join(METADATA_FILE);
+        assert_eq!(
+            MetadataStore::load(&path).unwrap(),
+            MetadataStore::default()
+        );
+
+        let mut store = MetadataStore::default();
+        store.add_tags("github.com/acme/tool", &tags(&["client-x"]));
+        store.set_notes("/home/me/scratch", Some("multi\nline \"note\"".to_string()));
+        store.save(&path).unwrap();
+        assert_eq!(MetadataStore::load(&path).unwrap(), store);
+
+        std::fs::write(&path, "[\"a\"]\ntags = [\"x\"]\ncolor = \"red\"\n").unwrap();
+        match MetadataStore::load(&path) {
+            Err(Error::Config { line, .. }) => assert_eq!(line, 3),
+            other => panic!("expected a config error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_key_for_path() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let git_dir = temp.path().join(".git");
+        std::fs::create_dir_all(git_dir.join("refs")).unwrap();
+        std::fs::create_dir(git_dir.join("objects")).unwrap();
+        std::fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        let sub = temp.path().join("src");
+        std::fs::create_dir(&sub).unwrap();
+        assert_eq!(
+            MetadataStore::key_for_path(&sub).unwrap(),
+            path_key(temp.path())
+        );
+
+        std::fs::write(
+            git_dir.join("config"),
+            "[remote \"origin\"]\n\turl = https://github.com/Acme/Tool.git\n",
+        )
+        .unwrap();
+        assert_eq!(
+            MetadataStore::key_for_path(temp.path()).unwrap(),
+            "github.com/acme/tool"
+        );
+    }
+}
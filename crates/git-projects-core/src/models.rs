@@ -1,11 +1,15 @@
 //! Core data models for Git project metadata.
 //!
-//! All types in this module are designed to be JSON-serializable and match
-//! the schema defined in `docs/API_SCHEMA.json`.
+//! All types in this module are JSON-serializable. The JSON schema of the
+//! scan output (`docs/API_SCHEMA.json`) is generated from these types, so
+//! their doc comments double as schema descriptions; see [`crate::schema`].
 
+use crate::fork::ForkRelation;
+use crate::identity::RemoteIdentity;
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Represents a Git project (repository) on the local file system.
 ///
@@ -21,14 +25,20 @@ use std::path::PathBuf;
 /// let project = GitProject {
 ///     name: "my-project".to_string(),
 ///     path: PathBuf::from("/home/user/projects/my-project"),
+///     scan_root: None,
+///     relative_path: None,
 ///     remotes: vec![],
 ///     config: None,
 ///     is_submodule: false,
 ///     has_submodules: false,
 ///     last_scanned: chrono::Utc::now(),
+///     head: None,
+///     tags: vec![],
+///     notes: None,
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[schemars(description = "A Git project (repository) on the local file system.")]
 pub struct GitProject {
     /// The name of the project (typically the directory name).
     ///
@@ -42,6 +52,20 @@ pub struct GitProject {
     /// the `.git` folder itself for bare repositories).
     pub path: PathBuf,
 
+    /// The scan root under which this project was found.
+    ///
+    /// `None` for projects that were not produced by a scan (or were
+    /// deserialized from output of an older version).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan_root: Option<PathBuf>,
+
+    /// Path of the project relative to [`scan_root`](Self::scan_root).
+    ///
+    /// `"."` if the scan root itself is the repository. Unlike `path`, this
+    /// stays meaningful when inventories are shared between machines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_path: Option<PathBuf>,
+
     /// List of remote URLs configured for this repository.
     ///
     /// Typically includes `origin`, but may contain multiple remotes.
@@ -69,13 +93,120 @@ pub struct GitProject {
     ///
     /// Useful for incremental scans and cache invalidation.
     pub last_scanned: DateTime<Utc>,
+
+    /// The state of `HEAD` (checked-out branch and commit).
+    ///
+    /// `None` if `HEAD` could not be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<HeadInfo>,
+
+    /// Tags assigned to the project, e.g. `client-x` or `archived`.
+    ///
+    /// Set from the configuration file (see [`Config`](crate::Config)) and
+    /// the [`MetadataStore`](crate::MetadataStore).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// A short note about the project, from the
+    /// [`MetadataStore`](crate::MetadataStore).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl GitProject {
+    /// Returns the remote that best represents where this project comes from.
+    ///
+    /// Prefers `origin`, falling back to the first configured remote.
+    pub fn primary_remote(&self) -> Option<&RemoteUrl> {
+        self.remotes
+            .iter()
+            .find(|r| r.name == "origin")
+            .or_else(|| self.remotes.first())
+    }
+
+    /// Returns the normalized identity of the primary remote.
+    ///
+    /// Two clones of the same upstream repository have equal identities,
+    /// regardless of the URL syntax used to clone them.
+    pub fn identity(&self) -> Option<RemoteIdentity> {
+        self.primary_remote()
+            .and_then(|r| RemoteIdentity::parse(&r.url))
+    }
+
+    /// Returns the fork/upstream relationship derived from the remotes.
+    ///
+    /// `None` if the project does not look like a fork. See
+    /// [`detect_fork`](crate::fork::detect_fork) for the detection rules.
+    pub fn fork_relation(&self) -> Option<ForkRelation> {
+        crate::fork::detect_fork(self)
+    }
+
+    /// Formats the project path for display in the given style.
+    ///
+    /// Falls back to the absolute path if the style does not apply, e.g.
+    /// [`PathStyle::Relative`] without a known scan root, or
+    /// [`PathStyle::Tilde`] for paths outside the home directory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_projects_core::{GitProject, PathStyle};
+    /// # use std::path::PathBuf;
+    /// let project = GitProject {
+    ///     name: "tool".to_string(),
+    ///     path: PathBuf::from("/home/user/projects/tool"),
+    ///     scan_root: Some(PathBuf::from("/home/user/projects")),
+    ///     relative_path: Some(PathBuf::from("tool")),
+    ///     remotes: vec![],
+    ///     config: None,
+    ///     is_submodule: false,
+    ///     has_submodules: false,
+    ///     last_scanned: chrono::Utc::now(),
+    ///     head: None,
+    ///     tags: vec![],
+    ///     notes: None,
+    /// };
+    /// assert_eq!(project.display_path(PathStyle::Relative), "tool");
+    /// assert_eq!(project.display_path(PathStyle::Absolute), "/home/user/projects/tool");
+    /// ```
+    pub fn display_path(&self, style: PathStyle) -> String {
+        match style {
+            PathStyle::Absolute => {}
+            PathStyle::Relative => {
+                if let Some(relative) = &self.relative_path {
+                    return relative.display().to_string();
+                }
+            }
+            PathStyle::Tilde => {
+                if let Some(rest) = dirs::home_dir()
+                    .and_then(|home| self.path.strip_prefix(&home).ok().map(Path::to_path_buf))
+                {
+                    return Path::new("~").join(rest).display().to_string();
+                }
+            }
+        }
+        self.path.display().to_string()
+    }
+}
+
+/// How project paths are displayed to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathStyle {
+    /// The absolute path (e.g., `/home/user/projects/tool`).
+    #[default]
+    Absolute,
+    /// The path relative to the scan root (e.g., `tool`).
+    Relative,
+    /// The absolute path with the home directory shortened to `~`
+    /// (e.g., `~/projects/tool`).
+    Tilde,
 }
 
 /// Represents a Git remote URL with associated metadata.
 ///
 /// Stores the remote name (e.g., "origin") and its URL, along with
 /// best-effort extraction of the hosting service and account name.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
 pub struct RemoteUrl {
     /// The name of the remote (e.g., "origin", "upstream").
     pub name: String,
@@ -110,11 +241,142 @@ pub struct RemoteUrl {
     pub account: Option<String>,
 }
 
+/// The state of a repository's `HEAD`.
+///
+/// Both fields are best-effort: a detached `HEAD` has no branch, and a
+/// freshly initialized repository has no commit yet.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+pub struct HeadInfo {
+    /// The short name of the checked-out branch (e.g., `"main"`).
+    ///
+    /// `None` if `HEAD` is detached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+
+    /// The full hex object id of the commit `HEAD` points to.
+    ///
+    /// `None` if the branch is unborn (no commits yet).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+
+    /// The remote the checked-out branch tracks (`branch.<name>.remote`).
+    ///
+    /// `None` if `HEAD` is detached or the branch has no upstream configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_remote: Option<String>,
+}
+
+/// The working tree state of a repository relative to its `HEAD` and upstream.
+///
+/// Unlike the other metadata this is not collected during a scan, since
+/// checking the working tree is expensive; see
+/// [`extract_status`](crate::extract_status).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+pub struct RepoStatus {
+    /// Whether tracked files have uncommitted changes, staged or not.
+    ///
+    /// Untracked files are not considered.
+    pub dirty: bool,
+
+    /// The remote-tracking branch of the checked-out branch (e.g.,
+    /// `"origin/main"`).
+    ///
+    /// `None` if `HEAD` is detached or the branch has no upstream.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+
+    /// Commits on `HEAD` that are not on the upstream.
+    ///
+    /// `None` if there is no upstream, or it has not been fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ahead: Option<usize>,
+
+    /// Commits on the upstream that are not on `HEAD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub behind: Option<usize>,
+}
+
+/// A remote with both of its URLs.
+///
+/// Unlike [`RemoteUrl`], which only holds the fetch URL, this distinguishes
+/// the push URL (`remote.<name>.pushurl`) where one is configured.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct RemoteDetails {
+    /// The name of the remote (e.g., `"origin"`).
+    pub name: String,
+
+    /// The URL used for fetching (`remote.<name>.url`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetch_url: Option<String>,
+
+    /// The URL used for pushing; the fetch URL unless `pushurl` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub push_url: Option<String>,
+}
+
+/// A Git configuration value and where it was set.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct ConfigEntry {
+    /// The effective value.
+    pub value: String,
+
+    /// The scope of the file that set the value.
+    pub scope: ConfigScope,
+
+    /// The file that set the value, if it came from a file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
+/// The commit identity of a repository, with the source of each value.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+pub struct IdentityDetails {
+    /// `user.name`, if set anywhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<ConfigEntry>,
+
+    /// `user.email`, if set anywhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<ConfigEntry>,
+}
+
+/// A local branch.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct BranchInfo {
+    /// The short name of the branch (e.g., `"main"`).
+    pub name: String,
+
+    /// The full hex object id of the commit the branch points to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+
+    /// The remote-tracking branch it follows (e.g., `"origin/main"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+
+    /// Whether the branch is checked out.
+    pub is_head: bool,
+}
+
+/// A submodule declared in `.gitmodules`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SubmoduleInfo {
+    /// The name of the submodule.
+    pub name: String,
+
+    /// The path of the submodule, relative to the repository.
+    pub path: PathBuf,
+
+    /// The URL the submodule is cloned from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
 /// Git user configuration (user.name and user.email) with scope.
 ///
 /// Represents the identity configuration found in Git config files.
 /// The scope indicates where the configuration was found (local vs global).
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct GitConfig {
     /// User's name from git config.
     ///
@@ -138,7 +400,7 @@ pub struct GitConfig {
 ///
 /// Git config can be set at different levels. This enum tracks where
 /// a particular configuration value was found.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ConfigScope {
     /// Repository-local configuration (`.git/config`).
@@ -175,9 +437,10 @@ pub enum ConfigScope {
 ///     max_depth: Some(3),
 ///     follow_symlinks: false,
 ///     include_submodules: true,
+///     ignore_patterns: vec![],
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ScanConfig {
     /// Root directories to start scanning from.
     ///
@@ -206,6 +469,15 @@ pub struct ScanConfig {
     /// - `true` → report submodules as separate projects
     /// - `false` → skip submodules (only report parent repositories)
     pub include_submodules: bool,
+
+    /// Glob patterns for directories to skip during scanning.
+    ///
+    /// Patterns are matched against paths relative to the scan root, using
+    /// `/` as separator: `**/vendor` or `build/*`. A pattern without a `/`
+    /// matches a directory of that name at any depth, like `node_modules`.
+    /// Absolute patterns (starting with `/`) are matched against the full path.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore_patterns: Vec<String>,
 }
 
 impl Default for ScanConfig {
@@ -215,31 +487,73 @@ impl Default for ScanConfig {
     /// - Max depth: 3 levels
     /// - Don't follow symlinks
     /// - Include submodules
+    /// - No ignore patterns
     fn default() -> Self {
         Self {
             root_paths: vec![dirs::home_dir().unwrap_or_else(|| PathBuf::from("."))],
             max_depth: Some(3),
             follow_symlinks: false,
             include_submodules: true,
+            ignore_patterns: Vec::new(),
+        }
+    }
+}
+
+impl GitProject {
+    /// Returns a project at `path`, named after its last component, with
+    /// nothing else set. Tests and examples set the fields they need on top
+    /// of it.
+    #[doc(hidden)]
+    pub fn test(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            path,
+            scan_root: None,
+            relative_path: None,
+            remotes: Vec::new(),
+            config: None,
+            is_submodule: false,
+            has_submodules: false,
+            last_scanned: Utc::now(),
+            head: None,
+            tags: Vec::new(),
+            notes: None,
         }
     }
 }
 
+#[cfg(test)]
+impl GitProject {
+    /// Adds an `origin` remote, with the service and account parsed from
+    /// `url` as a scan would.
+    pub(crate) fn with_remote(self, url: &str) -> Self {
+        self.with_named_remote("origin", url)
+    }
+
+    /// Adds a remote named `name`, see [`with_remote`](Self::with_remote).
+    pub(crate) fn with_named_remote(mut self, name: &str, url: &str) -> Self {
+        let (service, account) = crate::git_analyzer::parse_git_url(url);
+        self.remotes.push(RemoteUrl {
+            name: name.to_string(),
+            url: url.to_string(),
+            service,
+            account,
+        });
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_git_project_serialization() {
-        let project = GitProject {
-            name: "test-repo".to_string(),
-            path: PathBuf::from("/home/user/test-repo"),
-            remotes: vec![],
-            config: None,
-            is_submodule: false,
-            has_submodules: false,
-            last_scanned: Utc::now(),
-        };
+        let project = GitProject::test("/home/user/test-repo");
 
         let json = serde_json::to_string(&project).unwrap();
         assert!(json.contains("test-repo"));
@@ -248,6 +562,19 @@ mod tests {
         assert_eq!(deserialized.name, project.name);
     }
 
+    #[test]
+    fn test_primary_remote_prefers_origin() {
+        let project = GitProject::test("/home/user/tool")
+            .with_named_remote("upstream", "https://github.com/acme/tool.git")
+            .with_remote("git@github.com:user/tool.git");
+
+        assert_eq!(project.primary_remote().unwrap().name, "origin");
+        assert_eq!(
+            project.identity().unwrap().to_string(),
+            "github.com/user/tool"
+        );
+    }
+
     #[test]
     fn test_remote_url_serialization() {
         let remote = RemoteUrl {
@@ -0,0 +1,590 @@
+//! Machine-readable output formats for scan results.
+//!
+//! This module provides writers for JSON, NDJSON (one JSON object per line),
+//! CSV and TSV, so every frontend produces identical files.
+//!
+//! # Flattening Rules
+//!
+//! Tabular formats (CSV, TSV, and JSON/NDJSON with explicit columns) need a
+//! flat record per project. Nested data is flattened into [`Column`]s:
+//!
+//! - `remotes` becomes `name=url` pairs joined with `;`
+//!   (e.g., `origin=git@github.com:me/tool.git;upstream=https://...`).
+//!   `remote_names` and `remote_urls` contain just the names or URLs.
+//! - `service`, `account`, `url` and `identity` describe the primary remote
+//!   (`origin`, or the first remote).
+//! - `config` is split into `user_name`, `user_email` and `scope`.
+//! - `head` is split into `branch` and `commit`.
+//! - Missing values are empty strings (CSV/TSV) or `null` (JSON).
+//! - Booleans are `true`/`false`, timestamps are RFC 3339.
+//!
+//! Without explicit columns, JSON and NDJSON serialize the full nested
+//! [`GitProject`] instead. JSON output is then wrapped in the versioned
+//! envelope described in [`crate::schema`]; NDJSON has one project per line
+//! and no envelope.
+
+use crate::error::{Error, Result};
+use crate::models::{ConfigScope, GitProject};
+use crate::schema::SCHEMA_VERSION;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+/// An output format for scan results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable, aligned table.
+    #[default]
+    Table,
+    /// Pretty-printed JSON: a versioned envelope, or an array of flat
+    /// objects with `--columns`.
+    Json,
+    /// Newline-delimited JSON: one compact object per line.
+    Ndjson,
+    /// Comma-separated values (RFC 4180) with a header row.
+    Csv,
+    /// Tab-separated values with a header row.
+    Tsv,
+}
+
+impl OutputFormat {
+    /// All output formats.
+    pub const ALL: &'static [OutputFormat] = &[
+        OutputFormat::Table,
+        OutputFormat::Json,
+        OutputFormat::Ndjson,
+        OutputFormat::Csv,
+        OutputFormat::Tsv,
+    ];
+
+    /// Returns the name used for this format in `--format`.
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+        OutputFormat::ALL
+            .iter()
+            .copied()
+            .find(|f| f.name() == s)
+            .ok_or_else(|| {
+                let known: Vec<&str> = OutputFormat::ALL.iter().map(|f| f.name()).collect();
+                Error::invalid_output(format!(
+                    "unknown format '{}' (known formats: {})",
+                    s,
+                    known.join(", ")
+                ))
+            })
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A flat column of a project record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// Project name.
+    Name,
+    /// Absolute project path.
+    Path,
+    /// Scan root the project was found under.
+    ScanRoot,
+    /// Project path relative to the scan root.
+    RelativePath,
+    /// Hosting service of the primary remote.
+    Service,
+    /// Account of the primary remote.
+    Account,
+    /// URL of the primary remote.
+    Url,
+    /// Normalized identity of the primary remote (`host/owner/name`).
+    Identity,
+    /// All remotes as `name=url` pairs, joined with `;`.
+    Remotes,
+    /// All remote names, joined with `;`.
+    RemoteNames,
+    /// All remote URLs, joined with `;`.
+    RemoteUrls,
+    /// Number of remotes.
+    RemoteCount,
+    /// Configured `user.name`.
+    UserName,
+    /// Configured `user.email`.
+    UserEmail,
+    /// Scope of the identity configuration.
+    Scope,
+    /// Checked-out branch.
+    Branch,
+    /// `HEAD` commit id.
+    Commit,
+    /// Whether the project is a submodule.
+    IsSubmodule,
+    /// Whether the project contains submodules.
+    HasSubmodules,
+    /// Whether the project is a fork of another remote.
+    IsFork,
+    /// `owner/name` of the upstream repository, if the project is a fork.
+    Upstream,
+    /// Tags of the project, joined with `;`.
+    Tags,
+    /// Note about the project.
+    Notes,
+    /// Scan timestamp (RFC 3339).
+    LastScanned,
+}
+
+impl Column {
+    /// All columns, in their natural order.
+    pub const ALL: &'static [Column] = &[
+        Column::Name,
+        Column::Path,
+        Column::ScanRoot,
+        Column::RelativePath,
+        Column::Service,
+        Column::Account,
+        Column::Url,
+        Column::Identity,
+        Column::Remotes,
+        Column::RemoteNames,
+        Column::RemoteUrls,
+        Column::RemoteCount,
+        Column::UserName,
+        Column::UserEmail,
+        Column::Scope,
+        Column::Branch,
+        Column::Commit,
+        Column::IsSubmodule,
+        Column::HasSubmodules,
+        Column::IsFork,
+        Column::Upstream,
+        Column::Tags,
+        Column::Notes,
+        Column::LastScanned,
+    ];
+
+    /// The columns used when none are selected explicitly.
+    pub const DEFAULT: &'static [Column] = &[
+        Column::Name,
+        Column::Path,
+        Column::Service,
+        Column::Account,
+        Column::Remotes,
+        Column::UserName,
+        Column::UserEmail,
+        Column::Scope,
+        Column::Branch,
+        Column::IsSubmodule,
+        Column::HasSubmodules,
+    ];
+
+    /// Returns the column name, used in headers and `--columns`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Column::Name => "name",
+            Column::Path => "path",
+            Column::ScanRoot => "scan_root",
+            Column::RelativePath => "relative_path",
+            Column::Service => "service",
+            Column::Account => "account",
+            Column::Url => "url",
+            Column::Identity => "identity",
+            Column::Remotes => "remotes",
+            Column::RemoteNames => "remote_names",
+            Column::RemoteUrls => "remote_urls",
+            Column::RemoteCount => "remote_count",
+            Column::UserName => "user_name",
+            Column::UserEmail => "user_email",
+            Column::Scope => "scope",
+            Column::Branch => "branch",
+            Column::Commit => "commit",
+            Column::IsSubmodule => "is_submodule",
+            Column::HasSubmodules => "has_submodules",
+            Column::IsFork => "is_fork",
+            Column::Upstream => "upstream",
+            Column::Tags => "tags",
+            Column::Notes => "notes",
+            Column::LastScanned => "last_scanned",
+        }
+    }
+
+    /// Parses a comma-separated list of column names (e.g., `"name,url"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidOutput`] if the list is empty or contains an
+    /// unknown column.
+    pub fn parse_list(s: &str) -> Result<Vec<Column>> {
+        let columns: Vec<Column> = s
+            .split(',')
+            .filter(|c| !c.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_>>()?;
+        if columns.is_empty() {
+            return Err(Error::invalid_output("no columns given"));
+        }
+        Ok(columns)
+    }
+
+    /// Extracts the flattened value of this column from a project.
+    pub fn value(self, project: &GitProject) -> CellValue {
+        let remote = project.primary_remote();
+        let config = project.config.as_ref();
+        let head = project.head.as_ref();
+        let joined = |f: &dyn Fn(&crate::models::RemoteUrl) -> String| {
+            CellValue::Text(Some(
+                project.remotes.iter().map(f).collect::<Vec<_>>().join(";"),
+            ))
+        };
+
+        match self {
+            Column::Name => CellValue::Text(Some(project.name.clone())),
+            Column::Path => CellValue::Text(Some(project.path.display().to_string())),
+            Column::ScanRoot => {
+                CellValue::Text(project.scan_root.as_ref().map(|p| p.display().to_string()))
+            }
+            Column::RelativePath => CellValue::Text(
+                project
+                    .relative_path
+                    .as_ref()
+                    .map(|p| p.display().to_string()),
+            ),
+            Column::Service => CellValue::Text(remote.and_then(|r| r.service.clone())),
+            Column::Account => CellValue::Text(remote.and_then(|r| r.account.clone())),
+            Column::Url => CellValue::Text(remote.map(|r| r.url.clone())),
+            Column::Identity => CellValue::Text(project.identity().map(|id| id.to_string())),
+            Column::Remotes => joined(&|r| format!("{}={}", r.name, r.url)),
+            Column::RemoteNames => joined(&|r| r.name.clone()),
+            Column::RemoteUrls => joined(&|r| r.url.clone()),
+            Column::RemoteCount => CellValue::Int(project.remotes.len() as i64),
+            Column::UserName => CellValue::Text(config.and_then(|c| c.user_name.clone())),
+            Column::UserEmail => CellValue::Text(config.and_then(|c| c.user_email.clone())),
+            Column::Scope => CellValue::Text(config.map(|c| {
+                match c.scope {
+                    ConfigScope::Local => "local",
+                    ConfigScope::Global => "global",
+                    ConfigScope::System => "system",
+                }
+                .to_string()
+            })),
+            Column::Branch => CellValue::Text(head.and_then(|h| h.branch.clone())),
+            Column::Commit => CellValue::Text(head.and_then(|h| h.commit.clone())),
+            Column::IsSubmodule => CellValue::Bool(project.is_submodule),
+            Column::HasSubmodules => CellValue::Bool(project.has_submodules),
+            Column::IsFork => CellValue::Bool(project.fork_relation().is_some()),
+            Column::Upstream => {
+                CellValue::Text(project.fork_relation().map(|f| f.upstream_identity.slug()))
+            }
+            Column::Tags => CellValue::Text(Some(project.tags.join(";"))),
+            Column::Notes => CellValue::Text(project.notes.clone()),
+            Column::LastScanned => CellValue::Text(Some(project.last_scanned.to_rfc3339())),
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        Column::ALL
+            .iter()
+            .copied()
+            .find(|c| c.name() == s)
+            .ok_or_else(|| {
+                let known: Vec<&str> = Column::ALL.iter().map(|c| c.name()).collect();
+                Error::invalid_output(format!(
+                    "unknown column '{}' (known columns: {})",
+                    s,
+                    known.join(", ")
+                ))
+            })
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The flattened value of a [`Column`] for one project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CellValue {
+    /// Text, or `None` if the value is missing.
+    Text(Option<String>),
+    /// A boolean flag.
+    Bool(bool),
+    /// An integer.
+    Int(i64),
+}
+
+impl CellValue {
+    /// Renders the value as plain text (missing values become empty).
+    pub fn to_text(&self) -> String {
+        match self {
+            CellValue::Text(text) => text.clone().unwrap_or_default(),
+            CellValue::Bool(b) => b.to_string(),
+            CellValue::Int(i) => i.to_string(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            CellValue::Text(text) => text.clone().map(Value::String).unwrap_or(Value::Null),
+            CellValue::Bool(b) => Value::Bool(*b),
+            CellValue::Int(i) => Value::from(*i),
+        }
+    }
+}
+
+/// Writes projects as pretty-printed JSON.
+///
+/// Writes a [`ProjectList`](crate::schema::ProjectList) envelope with the
+/// full nested [`GitProject`]s. With `columns`, writes a bare array of flat
+/// objects with those keys instead: they are not `GitProject`s, so the
+/// schema version does not apply to them.
+pub fn write_json<W: Write>(
+    writer: &mut W,
+    projects: &[GitProject],
+    columns: Option<&[Column]>,
+) -> Result<()> {
+    match columns {
+        Some(columns) => {
+            let records: Vec<Value> = projects.iter().map(|p| flat_record(p, columns)).collect();
+            serde_json::to_writer_pretty(&mut *writer, &records).map_err(json_error)?;
+        }
+        None => {
+            let envelope = Envelope {
+                schema_version: SCHEMA_VERSION,
+                projects,
+            };
+            serde_json::to_writer_pretty(&mut *writer, &envelope).map_err(json_error)?;
+        }
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Borrowing counterpart of [`ProjectList`](crate::schema::ProjectList), so
+/// projects need not be cloned for writing.
+#[derive(Serialize)]
+struct Envelope<'a> {
+    schema_version: &'a str,
+    projects: &'a [GitProject],
+}
+
+/// Writes projects as newline-delimited JSON (one compact object per line).
+///
+/// With `columns`, each project is a flat object with those keys; otherwise
+/// the full nested [`GitProject`] is written.
+pub fn write_ndjson<W: Write>(
+    writer: &mut W,
+    projects: &[GitProject],
+    columns: Option<&[Column]>,
+) -> Result<()> {
+    for project in projects {
+        match columns {
+            Some(columns) => serde_json::to_writer(&mut *writer, &flat_record(project, columns)),
+            None => serde_json::to_writer(&mut *writer, project),
+        }
+        .map_err(json_error)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Writes projects as CSV (RFC 4180) with a header row.
+///
+/// Fields containing commas, quotes or line breaks are quoted, and quotes
+/// are doubled. Lines end with CRLF as required by the RFC.
+pub fn write_csv<W: Write>(
+    writer: &mut W,
+    projects: &[GitProject],
+    columns: &[Column],
+) -> Result<()> {
+    let escape = |field: &str| {
+        if field.contains([',', '"', '\r', '\n']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    };
+    write_delimited(writer, projects, columns, ",", "\r\n", escape)
+}
+
+/// Writes projects as TSV with a header row.
+///
+/// TSV has no quoting, so tabs and line breaks inside fields are replaced
+/// with spaces.
+pub fn write_tsv<W: Write>(
+    writer: &mut W,
+    projects: &[GitProject],
+    columns: &[Column],
+) -> Result<()> {
+    let escape = |field: &str| field.replace(['\t', '\r', '\n'], " ");
+    write_delimited(writer, projects, columns, "\t", "\n", escape)
+}
+
+fn write_delimited<W: Write>(
+    writer: &mut W,
+    projects: &[GitProject],
+    columns: &[Column],
+    delimiter: &str,
+    line_end: &str,
+    escape: impl Fn(&str) -> String,
+) -> Result<()> {
+    let header: Vec<String> = columns.iter().map(|c| escape(c.name())).collect();
+    write!(writer, "{}{}", header.join(delimiter), line_end)?;
+
+    for project in projects {
+        let row: Vec<String> = columns
+            .iter()
+            .map(|c| escape(&c.value(project).to_text()))
+            .collect();
+        write!(writer, "{}{}", row.join(delimiter), line_end)?;
+    }
+    Ok(())
+}
+
+/// Reports I/O failures during serialization (e.g., a closed pipe) as
+/// [`Error::Io`], so callers can tell them apart from encoding errors.
+fn json_error(e: serde_json::Error) -> Error {
+    if e.is_io() {
+        Error::Io(e.into())
+    } else {
+        Error::Json(e)
+    }
+}
+
+fn flat_record(project: &GitProject, columns: &[Column]) -> Value {
+    let mut record = Map::new();
+    for column in columns {
+        record.insert(column.name().to_string(), column.value(project).to_json());
+    }
+    Value::Object(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::GitConfig;
+
+    fn project() -> GitProject {
+        GitProject {
+            name: "tool, \"the\" best".to_string(),
+            config: Some(GitConfig {
+                user_name: Some("Jane\tDoe".to_string()),
+                user_email: None,
+                scope: ConfigScope::Local,
+            }),
+            has_submodules: true,
+            ..GitProject::test("/home/user/tool")
+                .with_remote("git@github.com:me/tool.git")
+                .with_named_remote("upstream", "https://github.com/acme/tool.git")
+        }
+    }
+
+    fn written(f: impl FnOnce(&mut Vec<u8>) -> Result<()>) -> String {
+        let mut buf = Vec::new();
+        f(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_parse_format_and_columns() {
+        assert_eq!(
+            "NDJSON".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Ndjson
+        );
+        assert!(matches!(
+            "xml".parse::<OutputFormat>(),
+            Err(Error::InvalidOutput(_))
+        ));
+        assert_eq!(
+            Column::parse_list("name, url").unwrap(),
+            vec![Column::Name, Column::Url]
+        );
+        assert!(Column::parse_list("nope").is_err());
+    }
+
+    #[test]
+    fn test_flattening() {
+        let p = project();
+        assert_eq!(
+            Column::Remotes.value(&p).to_text(),
+            "origin=git@github.com:me/tool.git;upstream=https://github.com/acme/tool.git"
+        );
+        assert_eq!(
+            Column::Url.value(&p).to_text(),
+            "git@github.com:me/tool.git"
+        );
+        assert_eq!(Column::Scope.value(&p).to_text(), "local");
+        assert_eq!(Column::UserEmail.value(&p), CellValue::Text(None));
+        assert_eq!(Column::Upstream.value(&p).to_text(), "acme/tool");
+        assert_eq!(Column::RemoteCount.value(&p), CellValue::Int(2));
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let out = written(|w| {
+            write_csv(
+                w,
+                &[project()],
+                &[Column::Name, Column::UserEmail, Column::HasSubmodules],
+            )
+        });
+        assert_eq!(
+            out,
+            "name,user_email,has_submodules\r\n\"tool, \"\"the\"\" best\",,true\r\n"
+        );
+    }
+
+    #[test]
+    fn test_tsv_replaces_tabs() {
+        let out = written(|w| write_tsv(w, &[project()], &[Column::Path, Column::UserName]));
+        assert_eq!(out, "path\tuser_name\n/home/user/tool\tJane Doe\n");
+    }
+
+    #[test]
+    fn test_ndjson() {
+        let out = written(|w| write_ndjson(w, &[project(), project()], None));
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let value: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(value["remotes"][1]["name"], "upstream");
+
+        let out =
+            written(|w| write_ndjson(w, &[project()], Some(&[Column::Service, Column::UserEmail])));
+        assert_eq!(out, "{\"service\":\"github\",\"user_email\":null}\n");
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let out = written(|w| write_json(w, &[project()], None));
+        let parsed: crate::schema::ProjectList = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed.schema_version, SCHEMA_VERSION);
+        assert_eq!(parsed.projects[0].name, project().name);
+
+        // Flat objects are not wrapped in the envelope
+        let out = written(|w| write_json(w, &[project()], Some(&[Column::Service])));
+        let value: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value, serde_json::json!([{ "service": "github" }]));
+    }
+}
@@ -0,0 +1,571 @@
+//! Markdown and HTML inventory reports.
+//!
+//! A report is a human-readable document listing scan results, e.g. for a
+//! team wiki. It starts with summary statistics, followed by one section per
+//! group (see [`group`](crate::group)), each with a table of projects. Remotes
+//! on known hosting platforms are rendered as links to the repository page.
+//!
+//! All headings and labels are localized through a [`Localizer`].
+
+use crate::duplicates::find_duplicates;
+use crate::group::{group_projects, GroupKey, ProjectGroup};
+use crate::identity::RemoteIdentity;
+use crate::l10n::Localizer;
+use crate::models::{GitProject, PathStyle, RemoteUrl};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The document format of a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// A Markdown document (CommonMark with GFM tables).
+    #[default]
+    Markdown,
+    /// A standalone HTML page with inline styles.
+    Html,
+}
+
+/// Options controlling the content of a report.
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    /// The document title. Defaults to the localized `report-title`.
+    pub title: Option<String>,
+
+    /// Keys used to split the projects into sections. With no keys, all
+    /// projects are listed in a single section.
+    pub group_by: Vec<GroupKey>,
+
+    /// Timestamp shown below the title. Omitted if `None`, which keeps the
+    /// output reproducible.
+    pub generated_at: Option<DateTime<Utc>>,
+
+    /// How project paths are shown.
+    pub path_style: PathStyle,
+}
+
+/// Summary statistics of a set of projects.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReportSummary {
+    /// Number of projects.
+    pub total: usize,
+
+    /// Projects with at least one remote.
+    pub with_remotes: usize,
+
+    /// Projects without any remote.
+    pub local_only: usize,
+
+    /// Projects that are forks of another remote.
+    pub forks: usize,
+
+    /// Projects that are submodules of another project.
+    pub submodules: usize,
+
+    /// Number of remotes that are cloned more than once.
+    pub duplicate_groups: usize,
+
+    /// Project count per hosting service of the primary remote, most common
+    /// first.
+    pub services: Vec<(String, usize)>,
+}
+
+impl ReportSummary {
+    /// Computes the summary statistics of the given projects.
+    pub fn from_projects(projects: &[GitProject]) -> Self {
+        let mut services: BTreeMap<String, usize> = BTreeMap::new();
+        for service in projects
+            .iter()
+            .filter_map(|p| p.primary_remote()?.service.clone())
+        {
+            *services.entry(service).or_default() += 1;
+        }
+        let mut services: Vec<(String, usize)> = services.into_iter().collect();
+        services.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        let with_remotes = projects.iter().filter(|p| !p.remotes.is_empty()).count();
+        Self {
+            total: projects.len(),
+            with_remotes,
+            local_only: projects.len() - with_remotes,
+            forks: projects
+                .iter()
+                .filter(|p| p.fork_relation().is_some())
+                .count(),
+            submodules: projects.iter().filter(|p| p.is_submodule).count(),
+            duplicate_groups: find_duplicates(projects).len(),
+            services,
+        }
+    }
+}
+
+/// Renders an inventory report of the given projects.
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::l10n::Localizer;
+/// use git_projects_core::report::{render_report, ReportFormat, ReportOptions};
+/// use git_projects_core::{DefaultScanner, GroupKey, ProjectScanner, ScanConfig};
+///
+/// let projects = DefaultScanner::new().scan(&ScanConfig::default())?;
+/// let options = ReportOptions {
+///     group_by: vec![GroupKey::Service],
+///     ..Default::default()
+/// };
+/// let localizer = Localizer::new("en")?;
+/// let markdown = render_report(&projects, ReportFormat::Markdown, &options, &localizer);
+/// std::fs::write("inventory.md", markdown)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn render_report(
+    projects: &[GitProject],
+    format: ReportFormat,
+    options: &ReportOptions,
+    localizer: &Localizer,
+) -> String {
+    let report = Report::new(projects, options, localizer);
+    match format {
+        ReportFormat::Markdown => report.markdown(),
+        ReportFormat::Html => report.html(),
+    }
+}
+
+/// Shared state of both renderers.
+struct Report<'a> {
+    projects: &'a [GitProject],
+    options: &'a ReportOptions,
+    localizer: &'a Localizer,
+    groups: Vec<ProjectGroup>,
+}
+
+impl<'a> Report<'a> {
+    fn new(
+        projects: &'a [GitProject],
+        options: &'a ReportOptions,
+        localizer: &'a Localizer,
+    ) -> Self {
+        Self {
+            projects,
+            options,
+            localizer,
+            groups: group_projects(projects, &options.group_by),
+        }
+    }
+
+    /// Returns a localized message without Unicode isolation marks, which
+    /// would otherwise end up verbatim in the document.
+    fn text(&self, id: &str, args: Option<&[(&str, &str)]>) -> String {
+        self.localizer
+            .get(id, args)
+            .replace(['\u{2068}', '\u{2069}'], "")
+    }
+
+    fn title(&self) -> String {
+        self.options
+            .title
+            .clone()
+            .unwrap_or_else(|| self.text("report-title", None))
+    }
+
+    fn generated(&self) -> Option<String> {
+        let date = self.options.generated_at?;
+        let date = date.format("%Y-%m-%d %H:%M UTC").to_string();
+        Some(self.text("report-generated", Some(&[("date", date.as_str())])))
+    }
+
+    /// Returns the summary table rows as (label, value) pairs.
+    fn summary_rows(&self) -> Vec<(String, String)> {
+        let summary = ReportSummary::from_projects(self.projects);
+        let services = if summary.services.is_empty() {
+            "-".to_string()
+        } else {
+            summary
+                .services
+                .iter()
+                .map(|(service, count)| format!("{} ({})", service, count))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        vec![
+            (
+                self.text("report-stat-total", None),
+                summary.total.to_string(),
+            ),
+            (
+                self.text("report-stat-with-remotes", None),
+                summary.with_remotes.to_string(),
+            ),
+            (
+                self.text("report-stat-local-only", None),
+                summary.local_only.to_string(),
+            ),
+            (
+                self.text("report-stat-forks", None),
+                summary.forks.to_string(),
+            ),
+            (
+                self.text("report-stat-submodules", None),
+                summary.submodules.to_string(),
+            ),
+            (
+                self.text("report-stat-duplicates", None),
+                summary.duplicate_groups.to_string(),
+            ),
+            (self.text("report-stat-services", None), services),
+        ]
+    }
+
+    fn project_headers(&self) -> [String; 4] {
+        [
+            self.text("header-name", None),
+            self.text("header-path", None),
+            self.text("header-remotes", None),
+            self.text("header-branch", None),
+        ]
+    }
+
+    fn group_heading(&self, group: &ProjectGroup) -> String {
+        let label = match &group.value {
+            Some(value) => value.clone(),
+            None => self.text("group-none", None),
+        };
+        format!("{} ({})", label, group.count)
+    }
+
+    fn branch(&self, project: &GitProject) -> String {
+        match &project.head {
+            Some(head) => match (&head.branch, &head.commit) {
+                (Some(branch), _) => branch.clone(),
+                (None, Some(_)) => self.text("head-detached", None),
+                (None, None) => self.text("head-unborn", None),
+            },
+            None => String::new(),
+        }
+    }
+
+    // Markdown
+
+    fn markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}\n", md_escape(&self.title()));
+        if let Some(generated) = self.generated() {
+            let _ = writeln!(out, "{}\n", md_escape(&generated));
+        }
+
+        let _ = writeln!(out, "## {}\n", self.text("report-summary", None));
+        let _ = writeln!(
+            out,
+            "| {} | {} |\n| --- | --- |",
+            self.text("report-metric", None),
+            self.text("report-value", None)
+        );
+        for (label, value) in self.summary_rows() {
+            let _ = writeln!(out, "| {} | {} |", md_escape(&label), md_escape(&value));
+        }
+
+        if self.groups.is_empty() {
+            let _ = writeln!(out, "\n## {}", self.text("report-repositories", None));
+            self.markdown_table(&mut out, self.projects);
+        } else {
+            for group in &self.groups {
+                self.markdown_group(&mut out, group, 2);
+            }
+        }
+        out
+    }
+
+    fn markdown_group(&self, out: &mut String, group: &ProjectGroup, level: usize) {
+        let _ = writeln!(
+            out,
+            "\n{} {}",
+            "#".repeat(level.min(6)),
+            md_escape(&self.group_heading(group))
+        );
+        if !group.projects.is_empty() {
+            self.markdown_table(out, &group.projects);
+        }
+        for subgroup in &group.groups {
+            self.markdown_group(out, subgroup, level + 1);
+        }
+    }
+
+    fn markdown_table(&self, out: &mut String, projects: &[GitProject]) {
+        if projects.is_empty() {
+            let _ = writeln!(out, "\n{}", self.text("scan-no-results", None));
+            return;
+        }
+        let headers = self.project_headers();
+        let _ = writeln!(out, "\n| {} |", headers.join(" | "));
+        let _ = writeln!(out, "|{}", " --- |".repeat(headers.len()));
+        for project in projects {
+            let remotes: Vec<String> = project.remotes.iter().map(md_remote).collect();
+            let _ = writeln!(
+                out,
+                "| {} | `{}` | {} | {} |",
+                md_escape(&project.name),
+                md_escape(&project.display_path(self.options.path_style)),
+                remotes.join("<br>"),
+                md_escape(&self.branch(project))
+            );
+        }
+    }
+
+    // HTML
+
+    fn html(&self) -> String {
+        let title = html_escape(&self.title());
+        let mut out = String::new();
+        let _ = writeln!(out, "<!DOCTYPE html>");
+        let _ = writeln!(
+            out,
+            "<html lang=\"{}\">",
+            html_escape(&self.localizer.locale())
+        );
+        let _ = writeln!(out, "<head>");
+        let _ = writeln!(out, "<meta charset=\"utf-8\">");
+        let _ = writeln!(out, "<title>{}</title>", title);
+        let _ = writeln!(out, "<style>{}</style>", HTML_STYLE);
+        let _ = writeln!(out, "</head>");
+        let _ = writeln!(out, "<body>");
+        let _ = writeln!(out, "<h1>{}</h1>", title);
+        if let Some(generated) = self.generated() {
+            let _ = writeln!(
+                out,
+                "<p class=\"generated\">{}</p>",
+                html_escape(&generated)
+            );
+        }
+
+        let _ = writeln!(out, "<section class=\"summary\">");
+        let _ = writeln!(
+            out,
+            "<h2>{}</h2>",
+            html_escape(&self.text("report-summary", None))
+        );
+        let _ = writeln!(out, "<table>");
+        let _ = writeln!(
+            out,
+            "<tr><th>{}</th><th>{}</th></tr>",
+            html_escape(&self.text("report-metric", None)),
+            html_escape(&self.text("report-value", None))
+        );
+        for (label, value) in self.summary_rows() {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td></tr>",
+                html_escape(&label),
+                html_escape(&value)
+            );
+        }
+        let _ = writeln!(out, "</table>");
+        let _ = writeln!(out, "</section>");
+
+        if self.groups.is_empty() {
+            let _ = writeln!(out, "<section>");
+            let _ = writeln!(
+                out,
+                "<h2>{}</h2>",
+                html_escape(&self.text("report-repositories", None))
+            );
+            self.html_table(&mut out, self.projects);
+            let _ = writeln!(out, "</section>");
+        } else {
+            for group in &self.groups {
+                self.html_group(&mut out, group, 2);
+            }
+        }
+
+        let _ = writeln!(out, "</body>");
+        let _ = writeln!(out, "</html>");
+        out
+    }
+
+    fn html_group(&self, out: &mut String, group: &ProjectGroup, level: usize) {
+        let level = level.min(6);
+        let _ = writeln!(out, "<section>");
+        let _ = writeln!(
+            out,
+            "<h{level}>{}</h{level}>",
+            html_escape(&self.group_heading(group))
+        );
+        if !group.projects.is_empty() {
+            self.html_table(out, &group.projects);
+        }
+        for subgroup in &group.groups {
+            self.html_group(out, subgroup, level + 1);
+        }
+        let _ = writeln!(out, "</section>");
+    }
+
+    fn html_table(&self, out: &mut String, projects: &[GitProject]) {
+        if projects.is_empty() {
+            let _ = writeln!(
+                out,
+                "<p>{}</p>",
+                html_escape(&self.text("scan-no-results", None))
+            );
+            return;
+        }
+        let _ = writeln!(out, "<table>");
+        let headers: Vec<String> = self
+            .project_headers()
+            .iter()
+            .map(|h| format!("<th>{}</th>", html_escape(h)))
+            .collect();
+        let _ = writeln!(out, "<tr>{}</tr>", headers.concat());
+        for project in projects {
+            let remotes: Vec<String> = project.remotes.iter().map(html_remote).collect();
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+                html_escape(&project.name),
+                html_escape(&project.display_path(self.options.path_style)),
+                remotes.join("<br>"),
+                html_escape(&self.branch(project))
+            );
+        }
+        let _ = writeln!(out, "</table>");
+    }
+}
+
+/// Minimal styling so the standalone page is readable without a stylesheet.
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2em auto;max-width:70em;\
+padding:0 1em}table{border-collapse:collapse;margin:0.5em 0 1.5em}\
+th,td{border:1px solid #ccc;padding:0.3em 0.6em;text-align:left;vertical-align:top}\
+th{background:#f3f3f3}code{font-size:0.9em}.generated{color:#666}";
+
+/// Renders a remote as a Markdown link if it is on a known platform.
+fn md_remote(remote: &RemoteUrl) -> String {
+    match RemoteIdentity::parse(&remote.url).and_then(|id| id.web_url()) {
+        Some(url) => format!("[{}]({})", md_escape(&remote.name), url),
+        None => format!("{}: `{}`", md_escape(&remote.name), md_escape(&remote.url)),
+    }
+}
+
+/// Renders a remote as an HTML link if it is on a known platform.
+fn html_remote(remote: &RemoteUrl) -> String {
+    match RemoteIdentity::parse(&remote.url).and_then(|id| id.web_url()) {
+        Some(url) => format!(
+            "<a href=\"{}\">{}</a>",
+            html_escape(&url),
+            html_escape(&remote.name)
+        ),
+        None => format!(
+            "{}: <code>{}</code>",
+            html_escape(&remote.name),
+            html_escape(&remote.url)
+        ),
+    }
+}
+
+/// Escapes characters that would break a Markdown table cell.
+fn md_escape(s: &str) -> String {
+    s.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Escapes the HTML special characters.
+fn html_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::HeadInfo;
+    use chrono::TimeZone;
+    use std::path::PathBuf;
+
+    fn project(name: &str, remotes: &[(&str, &str)]) -> GitProject {
+        let project = GitProject {
+            scan_root: Some(PathBuf::from("/w")),
+            relative_path: Some(PathBuf::from(name)),
+            head: Some(HeadInfo {
+                branch: Some("main".to_string()),
+                ..Default::default()
+            }),
+            ..GitProject::test(format!("/w/{}", name))
+        };
+        remotes.iter().fold(project, |project, (name, url)| {
+            project.with_named_remote(name, url)
+        })
+    }
+
+    fn projects() -> Vec<GitProject> {
+        vec![
+            project(
+                "tool",
+                &[
+                    ("origin", "git@github.com:me/tool.git"),
+                    ("upstream", "https://github.com/acme/tool.git"),
+                ],
+            ),
+            project(
+                "intranet",
+                &[("origin", "git@git.corp.example:web/intranet.git")],
+            ),
+            project("notes|<draft>", &[]),
+        ]
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = ReportSummary::from_projects(&projects());
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.with_remotes, 2);
+        assert_eq!(summary.local_only, 1);
+        assert_eq!(summary.forks, 1);
+        assert_eq!(summary.duplicate_groups, 0);
+        assert_eq!(summary.services, vec![("github".to_string(), 1)]);
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let localizer = Localizer::new("en").unwrap();
+        let options = ReportOptions {
+            title: Some("Team inventory".to_string()),
+            group_by: vec![GroupKey::Service],
+            generated_at: Some(Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap()),
+            path_style: PathStyle::Absolute,
+        };
+        let md = render_report(&projects(), ReportFormat::Markdown, &options, &localizer);
+
+        assert!(md.starts_with("# Team inventory\n"));
+        assert!(md.contains("2024-05-01 12:30 UTC"));
+        assert!(md.contains("| Forks | 1 |"));
+        assert!(md.contains("\n## github (1)\n"));
+        assert!(md.contains("[upstream](https://github.com/acme/tool)"));
+        assert!(md.contains("origin: `git@git.corp.example:web/intranet.git`"));
+        assert!(md.contains("notes\\|<draft>"));
+        assert!(!md.contains('\u{2068}'));
+    }
+
+    #[test]
+    fn test_html_report() {
+        let localizer = Localizer::new("de").unwrap();
+        let options = ReportOptions {
+            path_style: PathStyle::Relative,
+            ..Default::default()
+        };
+        let html = render_report(&projects(), ReportFormat::Html, &options, &localizer);
+
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"de\">"));
+        assert!(html.contains("<h2>Zusammenfassung</h2>"));
+        assert!(html.contains("<a href=\"https://github.com/me/tool\">origin</a>"));
+        assert!(html.contains("notes|&lt;draft&gt;"));
+        assert!(html.contains("<td><code>tool</code></td>"));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+}
@@ -8,10 +8,15 @@ use crate::error::{Error, Result};
 use crate::git_analyzer;
 use crate::models::{GitProject, ScanConfig};
 use chrono::Utc;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use walkdir::WalkDir;
 
+/// Called with the path of each repository found by a scan
+type ProgressFn = dyn Fn(&Path) + Send + Sync;
+
 /// Trait for scanning and discovering Git projects.
 ///
 /// Implementations of this trait define how directories are traversed
@@ -29,6 +34,7 @@ use walkdir::WalkDir;
 ///     max_depth: Some(3),
 ///     follow_symlinks: false,
 ///     include_submodules: true,
+///     ignore_patterns: vec![],
 /// };
 ///
 /// let projects = scanner.scan(&config)?;
@@ -64,10 +70,13 @@ pub trait ProjectScanner {
 /// - **I/O bound** - Speed depends on disk and filesystem
 /// - **Memory efficient** - Processes repos one at a time
 /// - **Parallel scanning** - Could be added in future versions
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct DefaultScanner {
     /// Whether to emit verbose logging (for debugging).
     pub verbose: bool,
+
+    /// Called for each repository found, see [`DefaultScanner::with_progress`].
+    progress: Option<Arc<ProgressFn>>,
 }
 
 impl DefaultScanner {
@@ -81,7 +90,10 @@ impl DefaultScanner {
     /// let scanner = DefaultScanner::new();
     /// ```
     pub fn new() -> Self {
-        Self { verbose: false }
+        Self {
+            verbose: false,
+            progress: None,
+        }
     }
 
     /// Creates a new DefaultScanner with verbose output enabled.
@@ -100,16 +112,35 @@ impl DefaultScanner {
         self
     }
 
+    /// Calls `progress` with the path of each repository as it is found.
+    ///
+    /// Frontends use this to show progress while scanning large trees; it is
+    /// called on the scanning thread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use git_projects_core::DefaultScanner;
+    ///
+    /// let scanner = DefaultScanner::new()
+    ///     .with_progress(|path| eprintln!("Found {}", path.display()));
+    /// ```
+    pub fn with_progress(mut self, progress: impl Fn(&Path) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
     /// Extracts metadata for a single Git repository.
     ///
     /// This is the core function that populates a [`GitProject`] with all
-    /// relevant information using gitoxide.
+    /// relevant information using gitoxide. `root` is the scan root the
+    /// repository was found under.
     ///
     /// # Errors
     ///
     /// Returns an error if critical Git operations fail. Non-critical failures
     /// (like missing config) result in `None` values in the returned struct.
-    fn analyze_repository(&self, repo: gix::Repository) -> Result<GitProject> {
+    fn analyze_repository(&self, repo: gix::Repository, root: &Path) -> Result<GitProject> {
         let path = repo.workdir().unwrap_or_else(|| repo.path());
         
         if self.verbose {
@@ -152,21 +183,43 @@ impl DefaultScanner {
         // Extract Git configuration (user.name, user.email)
         let config = git_analyzer::extract_git_config(path).ok();
 
+        // Extract HEAD state (branch, commit)
+        let head = git_analyzer::extract_head(path).ok();
+
+        // Record where the project was found, so it can be located on other machines
+        let relative_path = path.strip_prefix(root).ok().map(|relative| {
+            if relative.as_os_str().is_empty() {
+                PathBuf::from(".")
+            } else {
+                relative.to_path_buf()
+            }
+        });
+
         Ok(GitProject {
             name,
             path: path.to_path_buf(),
+            scan_root: Some(root.to_path_buf()),
+            relative_path,
             remotes,
             config,
             is_submodule,
             has_submodules,
             last_scanned: Utc::now(),
+            head,
+            tags: Vec::new(),
+            notes: None,
         })
     }
 
     /// Scans a single root path for Git repositories.
     ///
     /// This is called once per root path in the configuration.
-    fn scan_root(&self, root: &Path, config: &ScanConfig) -> Result<Vec<GitProject>> {
+    fn scan_root(
+        &self,
+        root: &Path,
+        config: &ScanConfig,
+        ignore: &IgnoreSet,
+    ) -> Result<Vec<GitProject>> {
         // Validate root path exists
         if !root.exists() {
             return Err(Error::path_not_found(root));
@@ -192,7 +245,12 @@ impl DefaultScanner {
             walker = walker.max_depth(max_depth);
         }
 
-        for entry in walker {
+        // Ignored directories are pruned, so nothing below them is visited
+        let entries = walker
+            .into_iter()
+            .filter_entry(|entry| !ignore.is_ignored(root, entry.path()));
+
+        for entry in entries {
             // Skip entries that we can't read (permission issues, etc.)
             let entry = match entry {
                 Ok(e) => e,
@@ -241,11 +299,15 @@ impl DefaultScanner {
                     };
 
                     if should_include {
-                        match self.analyze_repository(repo) {
+                        match self.analyze_repository(repo, root) {
                             Ok(project) => {
                                 visited_repos.insert(path.to_path_buf());
                                 projects.push(project);
 
+                                if let Some(progress) = &self.progress {
+                                    progress(path);
+                                }
+
                                 if self.verbose {
                                     eprintln!(
                                         "  Found: {} ({})",
@@ -284,6 +346,15 @@ impl DefaultScanner {
     }
 }
 
+impl std::fmt::Debug for DefaultScanner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefaultScanner")
+            .field("verbose", &self.verbose)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
 impl Default for DefaultScanner {
     fn default() -> Self {
         Self::new()
@@ -292,10 +363,11 @@ impl Default for DefaultScanner {
 
 impl ProjectScanner for DefaultScanner {
     fn scan(&self, config: &ScanConfig) -> Result<Vec<GitProject>> {
+        let ignore = IgnoreSet::new(&config.ignore_patterns)?;
         let mut all_projects = Vec::new();
 
         for root in &config.root_paths {
-            match self.scan_root(root, config) {
+            match self.scan_root(root, config, &ignore) {
                 Ok(mut projects) => {
                     all_projects.append(&mut projects);
                 }
@@ -313,6 +385,59 @@ impl ProjectScanner for DefaultScanner {
     }
 }
 
+/// Compiled [`ScanConfig::ignore_patterns`].
+struct IgnoreSet {
+    /// Patterns matched against the path relative to the scan root.
+    relative: GlobSet,
+    /// Patterns matched against the full path.
+    absolute: GlobSet,
+}
+
+impl IgnoreSet {
+    fn new(patterns: &[String]) -> Result<Self> {
+        let mut relative = GlobSetBuilder::new();
+        let mut absolute = GlobSetBuilder::new();
+
+        for pattern in patterns {
+            let trimmed = pattern.trim_end_matches('/');
+            let (builder, glob) = if trimmed.starts_with('/') {
+                (&mut absolute, trimmed.to_string())
+            } else if trimmed.contains('/') {
+                (&mut relative, trimmed.to_string())
+            } else {
+                // A bare name matches at any depth, as in .gitignore
+                (&mut relative, format!("**/{}", trimmed))
+            };
+            let glob = GlobBuilder::new(&glob)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| Error::invalid_pattern(e.to_string()))?;
+            builder.add(glob);
+        }
+
+        let build = |builder: GlobSetBuilder| {
+            builder
+                .build()
+                .map_err(|e| Error::invalid_pattern(e.to_string()))
+        };
+        Ok(Self {
+            relative: build(relative)?,
+            absolute: build(absolute)?,
+        })
+    }
+
+    /// Checks whether `path`, found below `root`, should be skipped.
+    ///
+    /// The root itself is never ignored.
+    fn is_ignored(&self, root: &Path, path: &Path) -> bool {
+        match path.strip_prefix(root) {
+            Ok(relative) if relative.as_os_str().is_empty() => false,
+            Ok(relative) => self.relative.is_match(relative) || self.absolute.is_match(path),
+            Err(_) => self.absolute.is_match(path),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -396,6 +521,7 @@ mod tests {
             max_depth: Some(3),
             follow_symlinks: false,
             include_submodules: true,
+            ignore_patterns: vec![],
         };
 
         let projects = scanner.scan(&config).unwrap();
@@ -410,6 +536,7 @@ mod tests {
             max_depth: Some(3),
             follow_symlinks: false,
             include_submodules: true,
+            ignore_patterns: vec![],
         };
 
         let result = scanner.scan(&config);
@@ -432,6 +559,7 @@ mod tests {
             max_depth: Some(2),
             follow_symlinks: false,
             include_submodules: true,
+            ignore_patterns: vec![],
         };
 
         // Note: This will fail to analyze because it's not a real Git repo
@@ -460,6 +588,7 @@ mod tests {
             max_depth: Some(1),
             follow_symlinks: false,
             include_submodules: true,
+            ignore_patterns: vec![],
         };
         let projects = scanner.scan(&config).unwrap();
         assert_eq!(projects.len(), 0);
@@ -470,6 +599,7 @@ mod tests {
             max_depth: Some(3),
             follow_symlinks: false,
             include_submodules: true,
+            ignore_patterns: vec![],
         };
         let projects = scanner.scan(&config).unwrap();
         assert_eq!(projects.len(), 1);
@@ -497,6 +627,7 @@ mod tests {
             max_depth: Some(1),
             follow_symlinks: false,
             include_submodules: true,
+            ignore_patterns: vec![],
         };
         let projects = scanner.scan(&config).unwrap();
         // Should find only real-repo. symlink-to-repo is a symlink, and is_git_repository checks for .git inside it.
@@ -515,12 +646,37 @@ mod tests {
             max_depth: Some(1),
             follow_symlinks: true,
             include_submodules: true,
+            ignore_patterns: vec![],
         };
         let projects = scanner.scan(&config).unwrap();
         // Should find both real-repo and symlink-to-repo
         assert_eq!(projects.len(), 2, "Should find 2 projects when follow_symlinks is true");
     }
 
+    #[test]
+    fn test_scan_reports_progress() {
+        let temp = TempDir::new().unwrap();
+        gix::init(temp.path().join("alpha")).unwrap();
+        gix::init(temp.path().join("beta")).unwrap();
+
+        let found = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&found);
+        let scanner = DefaultScanner::new()
+            .with_progress(move |path| seen.lock().unwrap().push(path.to_path_buf()));
+        let config = ScanConfig {
+            root_paths: vec![temp.path().to_path_buf()],
+            ..ScanConfig::default()
+        };
+
+        let projects = scanner.scan(&config).unwrap();
+        let mut found = found.lock().unwrap().clone();
+        found.sort();
+        let mut paths: Vec<PathBuf> = projects.into_iter().map(|p| p.path).collect();
+        paths.sort();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found, paths);
+    }
+
     #[test]
     fn test_scan_nested_repos() {
         let temp = TempDir::new().unwrap();
@@ -540,12 +696,85 @@ mod tests {
             max_depth: Some(3),
             follow_symlinks: false,
             include_submodules: true,
+            ignore_patterns: vec![],
         };
         let projects = scanner.scan(&config).unwrap();
         assert_eq!(projects.len(), 1);
         assert_eq!(projects[0].path, parent_repo);
     }
 
+    #[test]
+    fn test_scan_records_root_and_relative_path() {
+        let temp = TempDir::new().unwrap();
+        let repo_dir = temp.path().join("work").join("tool");
+        fs::create_dir_all(&repo_dir).unwrap();
+        create_mock_repo(&repo_dir).unwrap();
+
+        let scanner = DefaultScanner::new();
+        let mut config = ScanConfig {
+            root_paths: vec![temp.path().to_path_buf()],
+            max_depth: Some(3),
+            follow_symlinks: false,
+            include_submodules: true,
+            ignore_patterns: vec![],
+        };
+        let projects = scanner.scan(&config).unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].scan_root.as_deref(), Some(temp.path()));
+        assert_eq!(
+            projects[0].relative_path,
+            Some(PathBuf::from("work").join("tool"))
+        );
+
+        // Scanning the repository itself yields "."
+        config.root_paths = vec![repo_dir.clone()];
+        let projects = scanner.scan(&config).unwrap();
+        assert_eq!(projects[0].relative_path, Some(PathBuf::from(".")));
+    }
+
+    #[test]
+    fn test_scan_ignore_patterns() {
+        let temp = TempDir::new().unwrap();
+        for dir in [
+            "app",
+            "deps/node_modules/dep",
+            "vendor/lib",
+            "work/build/out",
+        ] {
+            let repo_dir = temp.path().join(dir);
+            fs::create_dir_all(&repo_dir).unwrap();
+            create_mock_repo(&repo_dir).unwrap();
+        }
+
+        let scanner = DefaultScanner::new();
+        let mut config = ScanConfig {
+            root_paths: vec![temp.path().to_path_buf()],
+            max_depth: Some(4),
+            follow_symlinks: false,
+            include_submodules: true,
+            ignore_patterns: vec!["node_modules".to_string(), "vendor/".to_string()],
+        };
+        let mut names: Vec<_> = scanner
+            .scan(&config)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, ["app", "out"]);
+
+        config.ignore_patterns = vec![format!("{}/work/*", temp.path().display())];
+        assert_eq!(scanner.scan(&config).unwrap().len(), 3);
+        config.ignore_patterns = vec!["work/**".to_string()];
+        assert_eq!(scanner.scan(&config).unwrap().len(), 3);
+
+        config.ignore_patterns = vec!["a{b".to_string()];
+        assert!(matches!(
+            scanner.scan(&config),
+            Err(Error::InvalidPattern(_))
+        ));
+    }
+
     #[test]
     fn test_is_inside_known_repo() {
         let scanner = DefaultScanner::new();
@@ -580,6 +809,7 @@ mod tests {
             max_depth: Some(2),
             follow_symlinks: false,
             include_submodules: true,
+            ignore_patterns: vec![],
         };
 
         let projects = scanner.scan(&config).unwrap();
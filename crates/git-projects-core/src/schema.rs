@@ -0,0 +1,99 @@
+//! Versioned JSON output schema.
+//!
+//! The JSON output of a scan is a [`ProjectList`]: an envelope holding the
+//! [`SCHEMA_VERSION`] and the array of [`GitProject`]s. Its JSON Schema is
+//! generated from the Rust types with [`schemars`], so it cannot drift from
+//! what is actually serialized. The checked-in copy at `docs/API_SCHEMA.json`
+//! is verified against the generated schema by a test; regenerate it with:
+//!
+//! ```text
+//! cargo run --bin projects-cli -- schema > docs/API_SCHEMA.json
+//! ```
+//!
+//! # Versioning
+//!
+//! [`SCHEMA_VERSION`] follows semantic versioning: the major version changes
+//! when fields are removed, renamed or change type; the minor version when
+//! optional fields are added. Consumers should check the major version.
+
+use crate::models::GitProject;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The version of the JSON output schema.
+pub const SCHEMA_VERSION: &str = "1.3.0";
+
+/// Output of a git-projects-scanner run: the projects found, tagged with the
+/// version of the output schema.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[schemars(title = "git-projects-scanner output")]
+pub struct ProjectList {
+    /// Version of the output schema (semantic versioning).
+    #[schemars(extend("const" = SCHEMA_VERSION))]
+    pub schema_version: String,
+
+    /// The Git projects found during the scan.
+    pub projects: Vec<GitProject>,
+}
+
+impl ProjectList {
+    /// Wraps projects in an envelope with the current [`SCHEMA_VERSION`].
+    pub fn new(projects: Vec<GitProject>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION.to_string(),
+            projects,
+        }
+    }
+}
+
+/// Returns the JSON Schema of the scan output ([`ProjectList`]).
+///
+/// # Example
+///
+/// ```
+/// let schema = git_projects_core::schema::output_schema();
+/// assert_eq!(schema["properties"]["schema_version"]["const"], git_projects_core::SCHEMA_VERSION);
+/// ```
+pub fn output_schema() -> Value {
+    let schema = schemars::schema_for!(ProjectList);
+    schema.to_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::write_json;
+
+    #[test]
+    fn test_checked_in_schema_is_up_to_date() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../docs/API_SCHEMA.json");
+        let checked_in: Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert!(
+            checked_in == output_schema(),
+            "docs/API_SCHEMA.json is out of date; regenerate it with \
+             `cargo run --bin projects-cli -- schema > docs/API_SCHEMA.json`"
+        );
+    }
+
+    #[test]
+    fn test_json_output_matches_envelope() {
+        let project = GitProject::test("/home/user/tool");
+
+        let mut buf = Vec::new();
+        write_json(&mut buf, std::slice::from_ref(&project), None).unwrap();
+        let parsed: ProjectList = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed, ProjectList::new(vec![project]));
+    }
+
+    #[test]
+    fn test_schema_describes_serialized_fields() {
+        let schema = output_schema();
+        let project = &schema["$defs"]["GitProject"]["properties"];
+        for field in ["name", "path", "remotes", "is_submodule", "has_submodules"] {
+            assert!(project.get(field).is_some(), "missing {}", field);
+        }
+        assert!(project.get("local_path").is_none());
+    }
+}
@@ -0,0 +1,930 @@
+//! A local daemon that keeps scan results warm for GUI frontends.
+//!
+//! A [`Server`] holds the projects of the last scan in memory and rescans
+//! periodically ([`Server::watch`]), so frontends need not scan themselves.
+//! It speaks [JSON-RPC 2.0](https://www.jsonrpc.org/specification), one
+//! message per line, over a Unix socket ([`Server::serve_unix`]), and a small
+//! HTTP API on localhost ([`Server::serve_http`]). Results are the same
+//! serde models as the JSON output of the CLI.
+//!
+//! | Method      | Params                          | Result                              |
+//! |-------------|---------------------------------|-------------------------------------|
+//! | `scan`      | none                            | [`ProjectList`] after a fresh scan  |
+//! | `query`     | `filter`, `sort`, `order`       | [`ProjectList`] of the last scan    |
+//! | `show`      | `project`: a name or path       | [`ProjectDetails`]                  |
+//! | `subscribe` | none                            | `true`; then `changed` notifications |
+//!
+//! After `subscribe`, a connection receives a `changed` notification with a
+//! [`ChangedParams`] whenever a scan finds [changes](crate::diff). Over
+//! HTTP, the same methods are available as `POST /rpc`, and as
+//!
+//! - `GET /projects?filter=...&sort=...&order=...` for `query`,
+//! - `GET /show?project=...` for `show`,
+//! - `POST /scan` for `scan`, and
+//! - `GET /events`, a stream of server-sent `changed` events.
+//!
+//! There is no authentication: the socket is only accessible to its owner,
+//! and HTTP requests must be addressed to `localhost`, which keeps web pages
+//! from reaching the API through DNS rebinding.
+
+use crate::details::{find_project, project_details, ProjectDetails};
+use crate::diff::{diff, Change};
+use crate::error::{Error, Result};
+use crate::filter::Filter;
+use crate::models::GitProject;
+use crate::schema::ProjectList;
+use crate::sort::SortSpec;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::SyncSender;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+/// Name of the socket in the runtime directory.
+const SOCKET_FILE: &str = "git-projects.sock";
+
+/// Notifications queued for a subscriber before it is dropped as too slow.
+const SUBSCRIBER_QUEUE: usize = 16;
+
+/// How long writing to a client may block before the write fails.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON-RPC error code for malformed JSON.
+pub const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code for a message that is not a request.
+pub const INVALID_REQUEST: i64 = -32600;
+/// JSON-RPC error code for an unknown method.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for invalid parameters, e.g. a malformed filter.
+pub const INVALID_PARAMS: i64 = -32602;
+/// Error code for a failed scan or a project that cannot be read.
+pub const SERVER_ERROR: i64 = -32000;
+/// Error code for a `show` request that matches no project.
+pub const NOT_FOUND: i64 = -32001;
+
+/// A JSON-RPC request, or a notification if it has no `id`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Request {
+    /// Always `"2.0"`.
+    pub jsonrpc: String,
+
+    /// The id to answer with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+
+    /// The method, e.g. `"query"`.
+    pub method: String,
+
+    /// The parameters, by name.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub params: Value,
+}
+
+/// A JSON-RPC response, with either a result or an error.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Response {
+    /// Always `"2.0"`.
+    pub jsonrpc: String,
+
+    /// The id of the request; `null` if it could not be read.
+    pub id: Value,
+
+    /// The result, on success.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+
+    /// The error, on failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+/// The error of a failed request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcError {
+    /// The error code, e.g. [`METHOD_NOT_FOUND`].
+    pub code: i64,
+
+    /// A description of the error.
+    pub message: String,
+}
+
+/// A message from the server that answers no request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Notification {
+    /// Always `"2.0"`.
+    pub jsonrpc: String,
+
+    /// The kind of notification; currently always `"changed"`.
+    pub method: String,
+
+    /// The parameters, e.g. [`ChangedParams`].
+    pub params: Value,
+}
+
+/// Parameters of `query`; all are optional.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QueryParams {
+    /// A [filter expression](crate::filter).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+
+    /// Comma-separated sort keys, as for `--sort-by`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+
+    /// Comma-separated sort directions, as for `--order`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<String>,
+}
+
+/// Parameters of `show`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShowParams {
+    /// The path of a project, or a name to match fuzzily.
+    pub project: String,
+}
+
+/// Parameters of the `changed` notification.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChangedParams {
+    /// What changed since the previous scan.
+    pub changes: Vec<Change>,
+
+    /// The number of projects after the scan.
+    pub projects: usize,
+}
+
+/// Queues notifications for the thread of a subscriber
+type Subscriber = SyncSender<Notification>;
+
+/// The function that scans for projects
+type ScanFn = dyn Fn() -> Result<Vec<GitProject>> + Send + Sync;
+
+/// A daemon serving the results of a scan.
+///
+/// Cloning is cheap; clones share the same results and subscribers.
+#[derive(Clone)]
+pub struct Server {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    scan: Box<ScanFn>,
+    projects: RwLock<Vec<GitProject>>,
+    /// Held while scanning, so that scans do not overlap
+    scanning: Mutex<()>,
+    subscribers: Mutex<Vec<Subscriber>>,
+}
+
+impl std::fmt::Debug for Server {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Server")
+            .field("projects", &self.shared.projects.read().unwrap().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Server {
+    /// Creates a server that scans with `scan`, without projects until the
+    /// first [`rescan`](Self::rescan).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use git_projects_core::server::Server;
+    /// use git_projects_core::{DefaultScanner, ProjectScanner, ScanConfig};
+    /// use std::time::Duration;
+    ///
+    /// let config = ScanConfig::default();
+    /// let server = Server::new(move || DefaultScanner::new().scan(&config));
+    /// server.rescan()?;
+    /// server.watch(Duration::from_secs(60));
+    /// # #[cfg(unix)]
+    /// server.serve_unix(std::os::unix::net::UnixListener::bind("/tmp/projects.sock")?)?;
+    /// # Ok::<(), git_projects_core::Error>(())
+    /// ```
+    pub fn new(scan: impl Fn() -> Result<Vec<GitProject>> + Send + Sync + 'static) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                scan: Box::new(scan),
+                projects: RwLock::new(Vec::new()),
+                scanning: Mutex::new(()),
+                subscribers: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Returns the path of the socket: in `$XDG_RUNTIME_DIR` if set, and in
+    /// the cache directory otherwise.
+    pub fn default_socket_path() -> Option<PathBuf> {
+        dirs::runtime_dir()
+            .map(|dir| dir.join(SOCKET_FILE))
+            .or_else(|| crate::config::cache_dir().map(|dir| dir.join(SOCKET_FILE)))
+    }
+
+    /// Returns the projects of the last scan.
+    pub fn projects(&self) -> Vec<GitProject> {
+        self.shared.projects.read().unwrap().clone()
+    }
+
+    /// Scans, replaces the projects and notifies subscribers of changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the scan; the previous projects are kept then.
+    pub fn rescan(&self) -> Result<Vec<Change>> {
+        let _scanning = self.shared.scanning.lock().unwrap();
+        let projects = (self.shared.scan)()?;
+        let changes = {
+            let mut current = self.shared.projects.write().unwrap();
+            let changes = diff(&current, &projects);
+            *current = projects;
+            changes
+        };
+        if !changes.is_empty() {
+            let params = ChangedParams {
+                changes: changes.clone(),
+                projects: self.shared.projects.read().unwrap().len(),
+            };
+            self.notify(&Notification {
+                jsonrpc: "2.0".to_string(),
+                method: "changed".to_string(),
+                params: serde_json::to_value(params)?,
+            });
+        }
+        Ok(changes)
+    }
+
+    /// Rescans every `interval` in a background thread.
+    ///
+    /// Failed scans are skipped; the next one is tried after the interval.
+    pub fn watch(&self, interval: Duration) -> JoinHandle<()> {
+        let server = self.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            let _ = server.rescan();
+        })
+    }
+
+    /// Calls `subscriber` with every notification, until it returns `false`.
+    ///
+    /// The calls are made from a thread of its own, so a slow subscriber
+    /// never holds up a scan. Notifications are queued for it; once it falls
+    /// too far behind, it is dropped and gets no further notifications.
+    pub fn subscribe(&self, mut subscriber: impl FnMut(&Notification) -> bool + Send + 'static) {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(SUBSCRIBER_QUEUE);
+        std::thread::spawn(move || {
+            for notification in receiver {
+                if !subscriber(&notification) {
+                    return;
+                }
+            }
+        });
+        self.shared.subscribers.lock().unwrap().push(sender);
+    }
+
+    fn notify(&self, notification: &Notification) {
+        // Never blocks: subscribers that are gone or have a full queue are dropped
+        self.shared
+            .subscribers
+            .lock()
+            .unwrap()
+            .retain(|subscriber| subscriber.try_send(notification.clone()).is_ok());
+    }
+
+    /// Answers a request; `subscribe` is left to the transport.
+    ///
+    /// Returns `None` for notifications, which get no answer.
+    pub fn handle(&self, request: &Request) -> Option<Response> {
+        let result = self.dispatch(&request.method, &request.params);
+        let id = request.id.clone()?;
+        Some(response(id, result))
+    }
+
+    fn dispatch(&self, method: &str, params: &Value) -> std::result::Result<Value, RpcError> {
+        match method {
+            "scan" => {
+                self.rescan().map_err(|e| rpc_error(SERVER_ERROR, e))?;
+                to_value(ProjectList::new(self.projects()))
+            }
+            "query" => {
+                let params: QueryParams = params_of(params)?;
+                to_value(ProjectList::new(self.query(&params)?))
+            }
+            "show" => {
+                let params: ShowParams = params_of(params)?;
+                to_value(self.show(&params.project)?)
+            }
+            _ => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("Unknown method {}", method),
+            }),
+        }
+    }
+
+    fn query(&self, params: &QueryParams) -> std::result::Result<Vec<GitProject>, RpcError> {
+        let filter = params
+            .filter
+            .as_deref()
+            .map(Filter::parse)
+            .transpose()
+            .map_err(|e| rpc_error(INVALID_PARAMS, e))?;
+        let sort = params
+            .sort
+            .as_deref()
+            .map(|keys| SortSpec::parse(keys, params.order.as_deref()))
+            .transpose()
+            .map_err(|e| rpc_error(INVALID_PARAMS, e))?;
+
+        let mut projects = self.projects();
+        if let Some(filter) = filter {
+            projects.retain(|p| filter.matches(p));
+        }
+        if let Some(sort) = sort {
+            sort.sort(&mut projects);
+        }
+        Ok(projects)
+    }
+
+    fn show(&self, query: &str) -> std::result::Result<ProjectDetails, RpcError> {
+        let projects = self.shared.projects.read().unwrap();
+        let project = match projects.iter().find(|p| p.path == Path::new(query)) {
+            Some(project) => project,
+            None => find_project(&projects, query).map_err(|e| rpc_error(NOT_FOUND, e))?,
+        }
+        .clone();
+        drop(projects);
+        project_details(project).map_err(|e| rpc_error(SERVER_ERROR, e))
+    }
+
+    /// Serves JSON-RPC on a Unix socket, a thread per connection.
+    ///
+    /// Blocks for as long as the listener accepts connections.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if accepting a connection fails.
+    #[cfg(unix)]
+    pub fn serve_unix(&self, listener: UnixListener) -> Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let server = self.clone();
+            std::thread::spawn(move || server.serve_connection(stream));
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn serve_connection(&self, stream: UnixStream) {
+        let Ok(writer) = stream.try_clone() else {
+            return;
+        };
+        // A client that stops reading must not keep the writer locked
+        let _ = writer.set_write_timeout(Some(WRITE_TIMEOUT));
+        let writer = Arc::new(Mutex::new(writer));
+        fn send(writer: &Mutex<UnixStream>, message: &impl Serialize) -> bool {
+            let mut line = to_json(message);
+            line.push('\n');
+            writer.lock().unwrap().write_all(line.as_bytes()).is_ok()
+        }
+
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else {
+                return;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let request: Request = match parse_request(&line) {
+                Ok(request) => request,
+                Err(error) => {
+                    if !send(&writer, &response(Value::Null, Err(error))) {
+                        return;
+                    }
+                    continue;
+                }
+            };
+            let answer = if request.method == "subscribe" {
+                let subscriber = Arc::clone(&writer);
+                self.subscribe(move |notification| send(&subscriber, notification));
+                request.id.map(|id| response(id, Ok(Value::Bool(true))))
+            } else {
+                self.handle(&request)
+            };
+            if let Some(answer) = answer {
+                if !send(&writer, &answer) {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Serves the HTTP API, a thread per connection.
+    ///
+    /// Blocks for as long as the listener accepts connections. Bind the
+    /// listener to a loopback address.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if accepting a connection fails.
+    pub fn serve_http(&self, listener: TcpListener) -> Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let server = self.clone();
+            std::thread::spawn(move || {
+                let _ = server.serve_http_request(stream);
+            });
+        }
+        Ok(())
+    }
+
+    fn serve_http_request(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let Some(request) = read_http_request(&mut stream)? else {
+            return write_http(
+                &mut stream,
+                400,
+                &error_body(INVALID_REQUEST, "Bad request"),
+            );
+        };
+        if !is_local_host(request.host.as_deref()) {
+            return write_http(
+                &mut stream,
+                403,
+                &error_body(INVALID_REQUEST, "Only requests to localhost are allowed"),
+            );
+        }
+
+        let (path, query) = request
+            .target
+            .split_once('?')
+            .unwrap_or((request.target.as_str(), ""));
+        let params = query_params(query);
+        let result = match (request.method.as_str(), path) {
+            ("POST", "/rpc") => {
+                let answer = match parse_request(&String::from_utf8_lossy(&request.body)) {
+                    Ok(request) if request.method == "subscribe" => response(
+                        request.id.unwrap_or(Value::Null),
+                        Err(rpc_error(
+                            INVALID_REQUEST,
+                            "Use GET /events to subscribe over HTTP",
+                        )),
+                    ),
+                    Ok(request) => match self.handle(&request) {
+                        Some(answer) => answer,
+                        None => return write_http(&mut stream, 204, ""),
+                    },
+                    Err(error) => response(Value::Null, Err(error)),
+                };
+                return write_http(&mut stream, 200, &to_json(&answer));
+            }
+            ("GET", "/events") => return self.serve_events(stream),
+            ("POST", "/scan") => self.dispatch("scan", &Value::Null),
+            ("GET", "/projects") => self.dispatch("query", &params),
+            ("GET", "/show") => self.dispatch("show", &params),
+            (_, "/rpc" | "/events" | "/scan" | "/projects" | "/show") => {
+                return write_http(
+                    &mut stream,
+                    405,
+                    &error_body(INVALID_REQUEST, "Method not allowed"),
+                );
+            }
+            _ => {
+                return write_http(&mut stream, 404, &error_body(METHOD_NOT_FOUND, "Not found"));
+            }
+        };
+        match result {
+            Ok(value) => write_http(&mut stream, 200, &value.to_string()),
+            Err(error) => {
+                let status = match error.code {
+                    INVALID_PARAMS => 400,
+                    NOT_FOUND => 404,
+                    _ => 500,
+                };
+                write_http(&mut stream, status, &error_body(error.code, &error.message))
+            }
+        }
+    }
+
+    /// Streams `changed` notifications as server-sent events
+    fn serve_events(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        stream.write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\
+              Cache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        )?;
+        stream.flush()?;
+        self.subscribe(move |notification| {
+            let event = format!(
+                "event: {}\ndata: {}\n\n",
+                notification.method, notification.params
+            );
+            stream.write_all(event.as_bytes()).is_ok() && stream.flush().is_ok()
+        });
+        Ok(())
+    }
+}
+
+/// Serializes a response or notification
+fn to_json(message: &impl Serialize) -> String {
+    serde_json::to_string(message).expect("messages serialize")
+}
+
+fn response(id: Value, result: std::result::Result<Value, RpcError>) -> Response {
+    let (result, error) = match result {
+        Ok(result) => (Some(result), None),
+        Err(error) => (None, Some(error)),
+    };
+    Response {
+        jsonrpc: "2.0".to_string(),
+        id,
+        result,
+        error,
+    }
+}
+
+fn rpc_error(code: i64, error: impl std::fmt::Display) -> RpcError {
+    RpcError {
+        code,
+        message: error.to_string(),
+    }
+}
+
+fn to_value(value: impl Serialize) -> std::result::Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| rpc_error(SERVER_ERROR, e))
+}
+
+/// Reads named parameters; missing parameters are an empty object
+fn params_of<T: DeserializeOwned>(params: &Value) -> std::result::Result<T, RpcError> {
+    let params = match params {
+        Value::Null => Value::Object(Default::default()),
+        params => params.clone(),
+    };
+    serde_json::from_value(params).map_err(|e| rpc_error(INVALID_PARAMS, e))
+}
+
+fn parse_request(source: &str) -> std::result::Result<Request, RpcError> {
+    let value: Value = serde_json::from_str(source).map_err(|e| rpc_error(PARSE_ERROR, e))?;
+    let request: Request =
+        serde_json::from_value(value).map_err(|e| rpc_error(INVALID_REQUEST, e))?;
+    if request.jsonrpc != "2.0" {
+        return Err(rpc_error(INVALID_REQUEST, "jsonrpc must be \"2.0\""));
+    }
+    Ok(request)
+}
+
+/// The parts of an HTTP request the API looks at
+struct HttpRequest {
+    method: String,
+    target: String,
+    host: Option<String>,
+    body: Vec<u8>,
+}
+
+/// Largest request body accepted
+const MAX_BODY: usize = 1 << 20;
+
+/// Reads an HTTP/1.x request; `None` if it is malformed
+fn read_http_request(stream: &mut TcpStream) -> std::io::Result<Option<HttpRequest>> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(None);
+    };
+    let (method, target) = (method.to_string(), target.to_string());
+
+    let mut host = None;
+    let mut length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Ok(None);
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("host") {
+            host = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            match value.parse() {
+                Ok(value) if value <= MAX_BODY => length = value,
+                _ => return Ok(None),
+            }
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(HttpRequest {
+        method,
+        target,
+        host,
+        body,
+    }))
+}
+
+/// Whether a `Host` header names the local machine
+fn is_local_host(host: Option<&str>) -> bool {
+    let Some(host) = host else {
+        return false;
+    };
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    matches!(name, "localhost" | "127.0.0.1" | "::1")
+}
+
+/// Decodes `a=1&b=x%20y` into a JSON object of strings
+fn query_params(query: &str) -> Value {
+    // Escapes are decoded byte-wise; `%` may be followed by any character
+    let hex = |byte: u8| (byte as char).to_digit(16).map(|digit| digit as u8);
+    let decode = |s: &str| {
+        let bytes = s.as_bytes();
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'+' => out.push(b' '),
+                b'%' if i + 2 < bytes.len() => match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                    (Some(high), Some(low)) => {
+                        out.push(high << 4 | low);
+                        i += 2;
+                    }
+                    _ => out.push(b'%'),
+                },
+                byte => out.push(byte),
+            }
+            i += 1;
+        }
+        String::from_utf8_lossy(&out).into_owned()
+    };
+    Value::Object(
+        query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode(name), Value::String(decode(value)))
+            })
+            .collect(),
+    )
+}
+
+fn error_body(code: i64, message: &str) -> String {
+    serde_json::json!({ "error": { "code": code, "message": message } }).to_string()
+}
+
+fn write_http(stream: &mut TcpStream, status: u16, body: &str) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        status,
+        reason,
+        body.len()
+    );
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body.as_bytes())?;
+    stream.flush()
+}
+
+/// A JSON-RPC client for the Unix socket of a [`Server`].
+///
+/// # Example
+///
+/// ```no_run
+/// use git_projects_core::server::{Client, QueryParams};
+/// use git_projects_core::ProjectList;
+///
+/// let mut client = Client::connect(&Client::default_path().unwrap())?;
+/// let params = QueryParams {
+///     filter: Some(r#"service == "github""#.to_string()),
+///     ..QueryParams::default()
+/// };
+/// let list: ProjectList = client.call("query", &params)?;
+/// println!("{} repositories on GitHub", list.projects.len());
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct Client {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+    next_id: u64,
+    notifications: std::collections::VecDeque<Notification>,
+}
+
+#[cfg(unix)]
+impl Client {
+    /// Returns the default socket path, see [`Server::default_socket_path`].
+    pub fn default_path() -> Option<PathBuf> {
+        Server::default_socket_path()
+    }
+
+    /// Connects to a server.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the socket cannot be reached.
+    pub fn connect(path: &Path) -> Result<Self> {
+        let writer = UnixStream::connect(path)?;
+        Ok(Self {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+            next_id: 1,
+            notifications: Default::default(),
+        })
+    }
+
+    /// Calls a method and waits for its result.
+    ///
+    /// Notifications that arrive in the meantime are kept for
+    /// [`next_notification`](Self::next_notification).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Rpc`] if the server answers with an error,
+    /// [`Error::Io`] if the connection fails, or [`Error::Json`] if the
+    /// result does not have the expected type.
+    pub fn call<T: DeserializeOwned>(&mut self, method: &str, params: impl Serialize) -> Result<T> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let request = Request {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::from(id)),
+            method: method.to_string(),
+            params: serde_json::to_value(params)?,
+        };
+        let mut line = serde_json::to_string(&request)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+
+        loop {
+            let message = self.read_message()?;
+            if message.get("id").is_none() {
+                self.notifications
+                    .push_back(serde_json::from_value(message)?);
+                continue;
+            }
+            let response: Response = serde_json::from_value(message)?;
+            if response.id != id {
+                continue;
+            }
+            if let Some(error) = response.error {
+                return Err(Error::Rpc {
+                    code: error.code,
+                    message: error.message,
+                });
+            }
+            return Ok(serde_json::from_value(
+                response.result.unwrap_or(Value::Null),
+            )?);
+        }
+    }
+
+    /// Waits for the next notification, after a `subscribe` call.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the connection fails or is closed.
+    pub fn next_notification(&mut self) -> Result<Notification> {
+        if let Some(notification) = self.notifications.pop_front() {
+            return Ok(notification);
+        }
+        loop {
+            let message = self.read_message()?;
+            if message.get("id").is_none() {
+                return Ok(serde_json::from_value(message)?);
+            }
+        }
+    }
+
+    fn read_message(&mut self) -> Result<Value> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "the server closed the connection",
+            )));
+        }
+        Ok(serde_json::from_str(&line)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispatch_errors() {
+        let server = Server::new(|| Ok(Vec::new()));
+        let request = |method: &str, params: Value| Request {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::from(1)),
+            method: method.to_string(),
+            params,
+        };
+        let code = |request: Request| server.handle(&request).unwrap().error.unwrap().code;
+
+        assert_eq!(code(request("nope", Value::Null)), METHOD_NOT_FOUND);
+        assert_eq!(
+            code(request("query", serde_json::json!({ "filter": "name ==" }))),
+            INVALID_PARAMS
+        );
+        assert_eq!(code(request("show", Value::Null)), INVALID_PARAMS);
+        assert_eq!(
+            code(request("show", serde_json::json!({ "project": "x" }))),
+            NOT_FOUND
+        );
+
+        let mut notification = request("query", Value::Null);
+        notification.id = None;
+        assert!(server.handle(&notification).is_none());
+
+        assert_eq!(parse_request("{").unwrap_err().code, PARSE_ERROR);
+        assert_eq!(
+            parse_request(r#"{"jsonrpc": "1.0", "method": "scan"}"#)
+                .unwrap_err()
+                .code,
+            INVALID_REQUEST
+        );
+    }
+
+    #[test]
+    fn test_slow_subscribers_are_dropped() {
+        // Every scan finds one more project, so every rescan notifies
+        let scans = std::sync::atomic::AtomicUsize::new(0);
+        let server = Server::new(move || {
+            let count = scans.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok((0..count)
+                .map(|i| GitProject::test(format!("/src/p{}", i)))
+                .collect())
+        });
+
+        // A subscriber that never returns, like a client that stops reading
+        let (block, blocked) = std::sync::mpsc::channel::<()>();
+        let blocked = Mutex::new(blocked);
+        server.subscribe(move |_| blocked.lock().unwrap().recv().is_ok());
+        let (sender, received) = std::sync::mpsc::channel();
+        server.subscribe(move |notification| sender.send(notification.clone()).is_ok());
+
+        // The first scan finds nothing new; each later one notifies both
+        server.rescan().unwrap();
+        for count in 1..SUBSCRIBER_QUEUE + 3 {
+            server.rescan().unwrap();
+            let notification = received.recv_timeout(Duration::from_secs(5)).unwrap();
+            assert_eq!(notification.params["projects"], count);
+        }
+        assert_eq!(server.shared.subscribers.lock().unwrap().len(), 1);
+        drop(block);
+    }
+
+    #[test]
+    fn test_http_helpers() {
+        assert!(is_local_host(Some("localhost:8080")));
+        assert!(is_local_host(Some("127.0.0.1")));
+        assert!(is_local_host(Some("[::1]:8080")));
+        assert!(!is_local_host(Some("evil.example:8080")));
+        assert!(!is_local_host(None));
+
+        assert_eq!(
+            query_params("filter=service+%3D%3D+%22github%22&sort=name&x"),
+            serde_json::json!({
+                "filter": "service == \"github\"",
+                "sort": "name",
+                "x": "",
+            })
+        );
+        assert_eq!(query_params("a=100%"), serde_json::json!({ "a": "100%" }));
+        assert_eq!(
+            query_params("q=%aé&filter=%é&n=%+1"),
+            serde_json::json!({ "q": "%aé", "filter": "%é", "n": "% 1" })
+        );
+        assert_eq!(
+            query_params("q=caf%C3%A9"),
+            serde_json::json!({ "q": "café" })
+        );
+    }
+}
@@ -0,0 +1,512 @@
+//! Hierarchical, locale-aware sorting of scan results.
+//!
+//! A [`SortSpec`] is an ordered list of `(key, direction)` pairs. Projects are
+//! compared by the first key; ties are broken by the next key, and so on.
+//! Sorting is stable, so projects that compare equal on all keys keep their
+//! scan order.
+//!
+//! Text is compared with the Unicode Collation Algorithm tailored to the
+//! requested locale (e.g., `ä` sorts next to `a` in German, but after `z` in
+//! Swedish), so results match what users expect from their file manager.
+//!
+//! # Example
+//!
+//! ```
+//! use git_projects_core::SortSpec;
+//!
+//! let spec = SortSpec::parse("service,account,name", Some("asc,asc,desc"))
+//!     .unwrap()
+//!     .with_locale("de");
+//! # let mut projects: Vec<git_projects_core::GitProject> = vec![];
+//! spec.sort(&mut projects);
+//! ```
+
+use crate::error::{Error, Result};
+use crate::filter::{Field, FieldValue};
+use crate::l10n::DEFAULT_LOCALE;
+use crate::models::GitProject;
+use chrono::{DateTime, Utc};
+use icu_collator::options::CollatorOptions;
+use icu_collator::{Collator, CollatorBorrowed};
+use icu_locale_core::Locale;
+use std::cmp::Ordering;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A property of [`GitProject`] that can be sorted by.
+///
+/// Covers every [filter field](Field) plus the scan timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// A field shared with the filter language (e.g., `name`, `service`).
+    Field(Field),
+    /// The time the project was scanned.
+    LastScanned,
+}
+
+impl SortKey {
+    /// Returns the name used for this key in `--sort-by` lists.
+    pub fn name(self) -> &'static str {
+        match self {
+            SortKey::Field(field) => field.name(),
+            SortKey::LastScanned => "last_scanned",
+        }
+    }
+
+    /// Returns the names of all available sort keys.
+    pub fn names() -> Vec<&'static str> {
+        Field::ALL
+            .iter()
+            .map(|f| f.name())
+            .chain(std::iter::once(SortKey::LastScanned.name()))
+            .collect()
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s == SortKey::LastScanned.name() {
+            return Ok(SortKey::LastScanned);
+        }
+        Field::from_name(s).map(SortKey::Field).ok_or_else(|| {
+            Error::invalid_sort(format!(
+                "unknown sort key '{}' (known keys: {})",
+                s,
+                SortKey::names().join(", ")
+            ))
+        })
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The direction of a single sort key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortDirection {
+    /// Smallest first (A → Z, oldest first, `false` before `true`).
+    #[default]
+    Asc,
+    /// Largest first.
+    Desc,
+}
+
+impl FromStr for SortDirection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "asc" => Ok(SortDirection::Asc),
+            "desc" => Ok(SortDirection::Desc),
+            other => Err(Error::invalid_sort(format!(
+                "invalid sort order '{}' (expected asc or desc)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Predefined sort specifications for common use cases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortPreset {
+    /// By repository name.
+    Name,
+    /// By full path.
+    Path,
+    /// By scan time, newest first.
+    Recent,
+    /// By service, then account, then name.
+    Service,
+}
+
+impl From<SortPreset> for SortSpec {
+    fn from(preset: SortPreset) -> Self {
+        use SortDirection::{Asc, Desc};
+        let keys = match preset {
+            SortPreset::Name => vec![(SortKey::Field(Field::Name), Asc)],
+            SortPreset::Path => vec![(SortKey::Field(Field::Path), Asc)],
+            SortPreset::Recent => vec![(SortKey::LastScanned, Desc)],
+            SortPreset::Service => vec![
+                (SortKey::Field(Field::Service), Asc),
+                (SortKey::Field(Field::Account), Asc),
+                (SortKey::Field(Field::Name), Asc),
+            ],
+        };
+        SortSpec::new(keys)
+    }
+}
+
+/// An ordered list of sort keys with directions, plus the collation locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortSpec {
+    keys: Vec<(SortKey, SortDirection)>,
+    locale: String,
+}
+
+impl SortSpec {
+    /// Creates a sort specification from `(key, direction)` pairs.
+    ///
+    /// Text is collated using the [default locale](DEFAULT_LOCALE) until
+    /// [`with_locale`](Self::with_locale) is called.
+    pub fn new(keys: Vec<(SortKey, SortDirection)>) -> Self {
+        Self {
+            keys,
+            locale: DEFAULT_LOCALE.to_string(),
+        }
+    }
+
+    /// Parses comma-separated key and direction lists.
+    ///
+    /// `keys` is e.g. `"service,account,name"`; `orders` is e.g.
+    /// `"asc,asc,desc"`. Keys without a matching direction are sorted
+    /// ascending.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSort`] if a key or direction is unknown, the
+    /// key list is empty, or there are more directions than keys.
+    pub fn parse(keys: &str, orders: Option<&str>) -> Result<Self> {
+        let keys: Vec<SortKey> = keys
+            .split(',')
+            .filter(|k| !k.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_>>()?;
+        if keys.is_empty() {
+            return Err(Error::invalid_sort("no sort keys given"));
+        }
+
+        let orders: Vec<SortDirection> = match orders {
+            Some(orders) => orders.split(',').map(str::parse).collect::<Result<_>>()?,
+            None => Vec::new(),
+        };
+        if orders.len() > keys.len() {
+            return Err(Error::invalid_sort(format!(
+                "got {} sort orders for {} sort keys",
+                orders.len(),
+                keys.len()
+            )));
+        }
+
+        let pairs = keys
+            .into_iter()
+            .enumerate()
+            .map(|(i, key)| (key, orders.get(i).copied().unwrap_or_default()))
+            .collect();
+        Ok(Self::new(pairs))
+    }
+
+    /// Sets the locale used to collate text (e.g., `"de"`, `"sv"`).
+    ///
+    /// Unknown locales fall back to the root collation order.
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = locale.to_string();
+        self
+    }
+
+    /// Appends another key, used to break ties of all previous keys.
+    pub fn then(mut self, key: SortKey, direction: SortDirection) -> Self {
+        self.keys.push((key, direction));
+        self
+    }
+
+    /// Returns the `(key, direction)` pairs in priority order.
+    pub fn keys(&self) -> &[(SortKey, SortDirection)] {
+        &self.keys
+    }
+
+    /// Returns the collation locale.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Builds a reusable comparator for this specification.
+    ///
+    /// Creating the comparator loads the collation data for the locale, so
+    /// reuse it when comparing many projects.
+    pub fn comparator(&self) -> Comparator<'_> {
+        let options = CollatorOptions::default();
+        let collator = self
+            .locale
+            .parse::<Locale>()
+            .ok()
+            .and_then(|locale| Collator::try_new((&locale).into(), options).ok())
+            .unwrap_or_else(|| {
+                Collator::try_new(Default::default(), options)
+                    .expect("root collation data is compiled in")
+            });
+        Comparator {
+            keys: &self.keys,
+            collator,
+        }
+    }
+
+    /// Sorts projects in place (stable).
+    ///
+    /// The sort values of each project are computed once, not on every
+    /// comparison.
+    pub fn sort(&self, projects: &mut [GitProject]) {
+        let comparator = self.comparator();
+        let values: Vec<SortValues> = projects.iter().map(|p| comparator.values(p)).collect();
+        let mut order: Vec<usize> = (0..projects.len()).collect();
+        order.sort_by(|&a, &b| comparator.compare_values(&values[a], &values[b]));
+        // The values borrow the projects
+        drop(values);
+
+        // Move each project to its place: `target[i]` is where the project
+        // now at `i` belongs
+        let mut target = vec![0; order.len()];
+        for (position, &index) in order.iter().enumerate() {
+            target[index] = position;
+        }
+        for i in 0..target.len() {
+            while target[i] != i {
+                let j = target[i];
+                projects.swap(i, j);
+                target.swap(i, j);
+            }
+        }
+    }
+}
+
+impl Default for SortSpec {
+    fn default() -> Self {
+        SortPreset::Name.into()
+    }
+}
+
+/// Compares projects according to a [`SortSpec`].
+pub struct Comparator<'a> {
+    keys: &'a [(SortKey, SortDirection)],
+    collator: CollatorBorrowed<'static>,
+}
+
+/// The values of a project for each key of a [`SortSpec`].
+///
+/// Reading some values, such as the fork relation, takes a moment, so
+/// compute them once with [`Comparator::values`] when sorting many projects.
+pub struct SortValues<'a>(Vec<SortValue<'a>>);
+
+/// The value of a single sort key
+enum SortValue<'a> {
+    /// Text values in collation order, none if the project has no value
+    Text(Vec<String>),
+    Bool(bool),
+    Int(i64),
+    /// Paths are ordered component-wise, not by collation
+    Path(&'a Path),
+    Time(DateTime<Utc>),
+}
+
+impl Comparator<'_> {
+    /// Compares two projects by all keys in order.
+    pub fn compare(&self, a: &GitProject, b: &GitProject) -> Ordering {
+        self.compare_values(&self.values(a), &self.values(b))
+    }
+
+    /// Returns the values of a project for all keys.
+    pub fn values<'p>(&self, project: &'p GitProject) -> SortValues<'p> {
+        let values = self
+            .keys
+            .iter()
+            .map(|&(key, _)| match key {
+                SortKey::LastScanned => SortValue::Time(project.last_scanned),
+                SortKey::Field(Field::Path) => SortValue::Path(&project.path),
+                SortKey::Field(field) => match field.value(project) {
+                    FieldValue::Text(mut values) => {
+                        values.sort_by(|x, y| self.collator.compare(x, y));
+                        SortValue::Text(values)
+                    }
+                    FieldValue::Bool(b) => SortValue::Bool(b),
+                    FieldValue::Int(i) => SortValue::Int(i),
+                },
+            })
+            .collect();
+        SortValues(values)
+    }
+
+    /// Compares the [values](Self::values) of two projects by all keys in
+    /// order.
+    pub fn compare_values(&self, a: &SortValues<'_>, b: &SortValues<'_>) -> Ordering {
+        self.keys
+            .iter()
+            .zip(a.0.iter().zip(&b.0))
+            .map(|(&(_, direction), (a, b))| self.compare_value(direction, a, b))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    fn compare_value(&self, direction: SortDirection, a: &SortValue, b: &SortValue) -> Ordering {
+        let apply = |o: Ordering| match direction {
+            SortDirection::Asc => o,
+            SortDirection::Desc => o.reverse(),
+        };
+
+        match (a, b) {
+            (SortValue::Text(a), SortValue::Text(b)) => match (a.is_empty(), b.is_empty()) {
+                // Projects without a value always sort last
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => apply(self.compare_text(a, b)),
+            },
+            (SortValue::Bool(a), SortValue::Bool(b)) => apply(a.cmp(b)),
+            (SortValue::Int(a), SortValue::Int(b)) => apply(a.cmp(b)),
+            (SortValue::Path(a), SortValue::Path(b)) => apply(a.cmp(b)),
+            (SortValue::Time(a), SortValue::Time(b)) => apply(a.cmp(b)),
+            _ => Ordering::Equal,
+        }
+    }
+
+    /// Compares multi-valued text (e.g., the services of all remotes).
+    ///
+    /// Both lists are collated already; they are compared element by
+    /// element.
+    fn compare_text(&self, a: &[String], b: &[String]) -> Ordering {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| self.collator.compare(x, y))
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn project(name: &str, services: &[&str]) -> GitProject {
+        services.iter().fold(
+            GitProject::test(format!("/p/{}", name)),
+            |project, service| {
+                project.with_remote(&format!("https://{}.com/acme/{}.git", service, name))
+            },
+        )
+    }
+
+    fn names(projects: &[GitProject]) -> Vec<&str> {
+        projects.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let spec = SortSpec::parse("service, account,name", Some("asc,desc")).unwrap();
+        assert_eq!(
+            spec.keys(),
+            &[
+                (SortKey::Field(Field::Service), SortDirection::Asc),
+                (SortKey::Field(Field::Account), SortDirection::Desc),
+                (SortKey::Field(Field::Name), SortDirection::Asc),
+            ]
+        );
+        assert_eq!(
+            SortSpec::parse("last_scanned", None).unwrap().keys(),
+            &[(SortKey::LastScanned, SortDirection::Asc)]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            SortSpec::parse("nmae", None),
+            Err(Error::InvalidSort(_))
+        ));
+        assert!(SortSpec::parse("name", Some("up")).is_err());
+        assert!(SortSpec::parse("name", Some("asc,desc")).is_err());
+        assert!(SortSpec::parse("", None).is_err());
+    }
+
+    #[test]
+    fn test_multi_key_with_directions() {
+        let mut projects = vec![
+            project("b", &["github"]),
+            project("a", &["gitlab"]),
+            project("c", &["github"]),
+            project("local", &[]),
+        ];
+
+        SortSpec::parse("service,name", Some("asc,desc"))
+            .unwrap()
+            .sort(&mut projects);
+        assert_eq!(names(&projects), ["c", "b", "a", "local"]);
+    }
+
+    #[test]
+    fn test_missing_values_sort_last_in_both_directions() {
+        let mut projects = vec![project("local", &[]), project("a", &["github"])];
+        SortSpec::parse("service", Some("desc"))
+            .unwrap()
+            .sort(&mut projects);
+        assert_eq!(names(&projects), ["a", "local"]);
+    }
+
+    #[test]
+    fn test_service_considers_all_remotes() {
+        // "b" has a GitHub remote in second position and must sort before "a"
+        let mut projects = vec![
+            project("a", &["gitlab"]),
+            project("b", &["gitlab", "github"]),
+        ];
+        SortSpec::from(SortPreset::Service).sort(&mut projects);
+        assert_eq!(names(&projects), ["b", "a"]);
+    }
+
+    #[test]
+    fn test_locale_aware_collation() {
+        let mut projects = vec![
+            project("zebra", &[]),
+            project("Äpfel", &[]),
+            project("apfel", &[]),
+            project("Banane", &[]),
+        ];
+
+        SortSpec::default().with_locale("de").sort(&mut projects);
+        assert_eq!(names(&projects), ["apfel", "Äpfel", "Banane", "zebra"]);
+
+        SortSpec::default().with_locale("sv").sort(&mut projects);
+        assert_eq!(names(&projects), ["apfel", "Banane", "zebra", "Äpfel"]);
+    }
+
+    #[test]
+    fn test_stable_and_recent_preset() {
+        let now = Utc::now();
+        let mut projects = vec![project("x", &[]), project("y", &[]), project("z", &[])];
+        projects[0].last_scanned = now - Duration::seconds(10);
+        projects[1].last_scanned = now;
+        projects[2].last_scanned = now - Duration::seconds(10);
+
+        SortSpec::from(SortPreset::Recent).sort(&mut projects);
+        assert_eq!(names(&projects), ["y", "x", "z"]);
+    }
+
+    #[test]
+    fn test_sort_matches_comparator() {
+        let services = ["github", "gitlab", "codeberg"];
+        let mut projects: Vec<GitProject> = (0..40)
+            .map(|i| project(&format!("p{}", i * 7 % 40), &services[..i % 4]))
+            .collect();
+        let spec = SortSpec::parse("service,name", Some("desc")).unwrap();
+        let mut expected = projects.clone();
+        let comparator = spec.comparator();
+        expected.sort_by(|a, b| comparator.compare(a, b));
+
+        spec.sort(&mut projects);
+        assert_eq!(names(&projects), names(&expected));
+    }
+
+    #[test]
+    fn test_unknown_locale_falls_back() {
+        let mut projects = vec![project("b", &[]), project("a", &[])];
+        SortSpec::default()
+            .with_locale("not a locale!")
+            .sort(&mut projects);
+        assert_eq!(names(&projects), ["a", "b"]);
+    }
+}
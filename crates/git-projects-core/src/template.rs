@@ -0,0 +1,329 @@
+//! User-defined output templates.
+//!
+//! Templates use the Jinja2 syntax of [minijinja](https://docs.rs/minijinja),
+//! so one tool can produce `.mrconfig` sections, shell aliases, README tables
+//! and similar text from scan results:
+//!
+//! ```text
+//! {{ name }},{{ remotes.0.url | default("-") }}
+//! alias cd-{{ name | lower }}='cd {{ path }}'
+//! {% for remote in remotes %}{{ remote.name }}={{ remote.url }} {% endfor %}
+//! ```
+//!
+//! # Variables
+//!
+//! Every field of [`GitProject`] is available under its JSON name (`name`,
+//! `path`, `scan_root`, `relative_path`, `remotes`, `config`,
+//! `is_submodule`, `has_submodules`, `last_scanned`, `head`, `tags`, `notes`), plus these
+//! derived values:
+//!
+//! - `remote`: the primary remote (`origin`, or the first remote)
+//! - `identity`: normalized identity of the primary remote (`host/owner/name`)
+//! - `web_url`: browser URL of the primary remote on a known platform
+//! - `fork`: the [`ForkRelation`], if the project is a fork
+//! - `submodules`: the scanned submodules directly inside this project
+//!   (requires scanning with submodules enabled)
+//!
+//! Each entry of `remotes` also has `identity` and `web_url`.
+//!
+//! Missing values render as empty strings, and lookups on them (such as
+//! `remotes.0.url` for a project without remotes) stay empty instead of
+//! failing, so the `default` filter can supply a fallback. All builtin
+//! filters are available, e.g. `lower`, `upper`, `default`, `replace`,
+//! `join`, `length` and `tojson`.
+//!
+//! # Rendering Modes
+//!
+//! [`Template::render_each`] renders the template once per project, one
+//! result per line. [`Template::render_document`] renders it once with all
+//! projects in a `projects` list, for documents with a header or footer.
+
+use crate::error::{Error, Result};
+use crate::fork::ForkRelation;
+use crate::identity::RemoteIdentity;
+use crate::models::{GitConfig, GitProject, HeadInfo, RemoteUrl};
+use chrono::{DateTime, Utc};
+use minijinja::{context, Environment, UndefinedBehavior};
+use serde::Serialize;
+use std::path::Path;
+
+/// Name under which a template given as a string is registered.
+const INLINE_NAME: &str = "<template>";
+
+/// A compiled output template.
+///
+/// # Example
+///
+/// ```
+/// # use git_projects_core::{GitProject, RemoteUrl, Template};
+/// # use std::path::PathBuf;
+/// # let project = GitProject {
+/// #     name: "Tool".to_string(),
+/// #     ..GitProject::test("/home/user/tool")
+/// # };
+/// let template = Template::new("{{ name | lower }}: {{ remotes.0.url | default('local') }}")?;
+/// assert_eq!(template.render_each(&[project])?, "tool: local\n");
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub struct Template {
+    env: Environment<'static>,
+    name: String,
+}
+
+impl Template {
+    /// Compiles a template from a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Template`] if the template has a syntax error.
+    pub fn new(source: impl Into<String>) -> Result<Self> {
+        Self::compile(INLINE_NAME.to_string(), source.into())
+    }
+
+    /// Loads and compiles a template file.
+    ///
+    /// Error messages refer to the file path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read, or
+    /// [`Error::Template`] if the template has a syntax error.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        Self::compile(path.display().to_string(), source)
+    }
+
+    fn compile(name: String, source: String) -> Result<Self> {
+        let mut env = Environment::new();
+        env.set_undefined_behavior(UndefinedBehavior::Chainable);
+        // Render files verbatim, so a blank line at the end separates entries
+        env.set_keep_trailing_newline(true);
+        env.add_template_owned(name.clone(), source)
+            .map_err(template_error)?;
+        Ok(Self { env, name })
+    }
+
+    /// Renders the template once per project.
+    ///
+    /// Each result is terminated by a newline, unless it already ends with one.
+    /// The `submodules` of each project are looked up among `projects`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Template`] if rendering fails, e.g. because of an
+    /// unknown filter or a type error.
+    pub fn render_each(&self, projects: &[GitProject]) -> Result<String> {
+        let template = self.env.get_template(&self.name).map_err(template_error)?;
+        let mut out = String::new();
+        for project in projects {
+            let rendered = template
+                .render(ProjectContext::new(project, projects))
+                .map_err(template_error)?;
+            out.push_str(&rendered);
+            if !rendered.ends_with('\n') {
+                out.push('\n');
+            }
+        }
+        Ok(out)
+    }
+
+    /// Renders the template once, with all projects in the `projects` list.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Template`] if rendering fails.
+    pub fn render_document(&self, projects: &[GitProject]) -> Result<String> {
+        let template = self.env.get_template(&self.name).map_err(template_error)?;
+        let contexts: Vec<ProjectContext<'_>> = projects
+            .iter()
+            .map(|p| ProjectContext::new(p, projects))
+            .collect();
+        template
+            .render(context! { projects => contexts })
+            .map_err(template_error)
+    }
+}
+
+/// The variables available to a template for one project.
+///
+/// Mirrors the fields of [`GitProject`], with `remotes` replaced by
+/// [`RemoteContext`]s.
+#[derive(Serialize)]
+struct ProjectContext<'a> {
+    name: &'a str,
+    path: &'a Path,
+    scan_root: Option<&'a Path>,
+    relative_path: Option<&'a Path>,
+    remotes: Vec<RemoteContext<'a>>,
+    config: Option<&'a GitConfig>,
+    is_submodule: bool,
+    has_submodules: bool,
+    last_scanned: DateTime<Utc>,
+    head: Option<&'a HeadInfo>,
+    tags: &'a [String],
+    notes: Option<&'a str>,
+    remote: Option<RemoteContext<'a>>,
+    identity: Option<String>,
+    web_url: Option<String>,
+    fork: Option<ForkRelation>,
+    submodules: Vec<ProjectContext<'a>>,
+}
+
+impl<'a> ProjectContext<'a> {
+    fn new(project: &'a GitProject, all: &'a [GitProject]) -> Self {
+        let identity = project.identity();
+        let submodules = all
+            .iter()
+            .filter(|candidate| {
+                candidate.is_submodule
+                    && enclosing_project(candidate, all).map(|p| &p.path) == Some(&project.path)
+            })
+            .map(|submodule| ProjectContext::new(submodule, all))
+            .collect();
+
+        Self {
+            name: &project.name,
+            path: &project.path,
+            scan_root: project.scan_root.as_deref(),
+            relative_path: project.relative_path.as_deref(),
+            remotes: project.remotes.iter().map(RemoteContext::new).collect(),
+            config: project.config.as_ref(),
+            is_submodule: project.is_submodule,
+            has_submodules: project.has_submodules,
+            last_scanned: project.last_scanned,
+            head: project.head.as_ref(),
+            tags: &project.tags,
+            notes: project.notes.as_deref(),
+            remote: project.primary_remote().map(RemoteContext::new),
+            web_url: identity.as_ref().and_then(RemoteIdentity::web_url),
+            identity: identity.map(|id| id.to_string()),
+            fork: project.fork_relation(),
+            submodules,
+        }
+    }
+}
+
+/// A remote with its derived identity and web URL.
+#[derive(Serialize)]
+struct RemoteContext<'a> {
+    #[serde(flatten)]
+    remote: &'a RemoteUrl,
+    identity: Option<String>,
+    web_url: Option<String>,
+}
+
+impl<'a> RemoteContext<'a> {
+    fn new(remote: &'a RemoteUrl) -> Self {
+        let identity = RemoteIdentity::parse(&remote.url);
+        Self {
+            remote,
+            web_url: identity.as_ref().and_then(RemoteIdentity::web_url),
+            identity: identity.map(|id| id.to_string()),
+        }
+    }
+}
+
+/// Returns the innermost project that contains `project`.
+fn enclosing_project<'a>(project: &GitProject, all: &'a [GitProject]) -> Option<&'a GitProject> {
+    all.iter()
+        .filter(|outer| outer.path != project.path && project.path.starts_with(&outer.path))
+        .max_by_key(|outer| outer.path.components().count())
+}
+
+fn template_error(e: minijinja::Error) -> Error {
+    Error::template(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(path: &str, urls: &[&str], is_submodule: bool) -> GitProject {
+        let project = GitProject {
+            is_submodule,
+            ..GitProject::test(path)
+        };
+        urls.iter().enumerate().fold(project, |project, (i, url)| {
+            project.with_named_remote(if i == 0 { "origin" } else { "upstream" }, url)
+        })
+    }
+
+    #[test]
+    fn test_render_each_with_index_and_filters() {
+        let projects = vec![
+            project("/w/Tool", &["git@github.com:acme/tool.git"], false),
+            project("/w/notes", &[], false),
+        ];
+        let template =
+            Template::new("{{ name | lower }}\t{{ remotes.0.url | default('-') }}").unwrap();
+        assert_eq!(
+            template.render_each(&projects).unwrap(),
+            "tool\tgit@github.com:acme/tool.git\nnotes\t-\n"
+        );
+    }
+
+    #[test]
+    fn test_loop_over_remotes_and_derived_values() {
+        let projects = vec![project(
+            "/w/tool",
+            &[
+                "git@github.com:me/tool.git",
+                "https://github.com/acme/tool.git",
+            ],
+            false,
+        )];
+        let template = Template::new(
+            "{% for r in remotes %}{{ r.name }}={{ r.web_url }} {% endfor %}{{ fork.upstream_identity.owner }}",
+        )
+        .unwrap();
+        assert_eq!(
+            template.render_each(&projects).unwrap(),
+            "origin=https://github.com/me/tool upstream=https://github.com/acme/tool acme\n"
+        );
+    }
+
+    #[test]
+    fn test_submodules_are_nested() {
+        let projects = vec![
+            project("/w/app", &[], false),
+            project("/w/app/vendor/lib", &[], true),
+            project("/w/app/vendor/lib/deps/inner", &[], true),
+        ];
+        let template =
+            Template::new("{{ name }}:{% for s in submodules %} {{ s.name }}{% endfor %}").unwrap();
+        assert_eq!(
+            template.render_each(&projects).unwrap(),
+            "app: lib\nlib: inner\ninner:\n"
+        );
+    }
+
+    #[test]
+    fn test_trailing_newline_is_kept() {
+        let projects = vec![project("/w/a", &[], false), project("/w/b", &[], false)];
+        let template = Template::new("[{{ name }}]\n\n").unwrap();
+        assert_eq!(template.render_each(&projects).unwrap(), "[a]\n\n[b]\n\n");
+    }
+
+    #[test]
+    fn test_render_document() {
+        let projects = vec![project("/w/a", &[], false), project("/w/b", &[], false)];
+        let template =
+            Template::new("| Name |\n|---|\n{% for p in projects %}| {{ p.name }} |\n{% endfor %}")
+                .unwrap();
+        assert_eq!(
+            template.render_document(&projects).unwrap(),
+            "| Name |\n|---|\n| a |\n| b |\n"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(Template::new("{{ name "), Err(Error::Template(_))));
+        let template = Template::new("{{ name | nope }}");
+        let err = match template {
+            Ok(t) => t.render_each(&[project("/w/a", &[], false)]).unwrap_err(),
+            Err(e) => e,
+        };
+        assert!(err.to_string().contains("nope"));
+    }
+}
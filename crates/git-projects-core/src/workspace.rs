@@ -0,0 +1,578 @@
+//! Editor workspaces for a set of projects.
+//!
+//! [`vscode_workspace`] renders a VS Code `.code-workspace` file with a
+//! folder per project, and [`write_jetbrains`] writes an `.idea` directory
+//! with a module per project, so that a JetBrains IDE opens them all in one
+//! window with each repository as a Git root. Folders and modules are named
+//! after the service, account and name of each project ([`folder_name`]).
+//!
+//! Both update existing files instead of replacing them: the settings,
+//! extensions and other keys of a workspace file are kept, as are folders,
+//! modules and VCS mappings that are already listed. Submodules are left
+//! out, since they are opened with their parent.
+
+use crate::config::write_atomic;
+use crate::error::{Error, Result};
+use crate::manifest_format::xml_escape;
+use crate::models::GitProject;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Returns the name of a project in a workspace: `service/account/name`,
+/// leaving out what the primary remote does not tell.
+///
+/// # Example
+///
+/// ```
+/// use git_projects_core::workspace::folder_name;
+/// use git_projects_core::{GitProject, RemoteUrl};
+///
+/// let mut project = GitProject::test("/src/tool");
+/// assert_eq!(folder_name(&project), "tool");
+///
+/// project.remotes.push(RemoteUrl {
+///     name: "origin".to_string(),
+///     url: "git@github.com:acme/tool.git".to_string(),
+///     service: Some("github".to_string()),
+///     account: Some("acme".to_string()),
+/// });
+/// assert_eq!(folder_name(&project), "github/acme/tool");
+/// ```
+pub fn folder_name(project: &GitProject) -> String {
+    let remote = project.primary_remote();
+    remote
+        .and_then(|r| r.service.as_deref())
+        .into_iter()
+        .chain(remote.and_then(|r| r.account.as_deref()))
+        .chain([project.name.as_str()])
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Returns the projects to list with unique folder names, numbering the
+/// second and later clones of the same repository, and absolute paths
+fn named_projects(projects: &[GitProject]) -> Vec<(String, PathBuf)> {
+    let mut seen = HashSet::new();
+    projects
+        .iter()
+        .filter(|p| !p.is_submodule)
+        .map(|project| {
+            let base = folder_name(project);
+            let mut name = base.clone();
+            let mut n = 1;
+            while !seen.insert(name.clone()) {
+                n += 1;
+                name = format!("{} ({})", base, n);
+            }
+            let path = std::fs::canonicalize(&project.path)
+                .or_else(|_| std::path::absolute(&project.path))
+                .unwrap_or_else(|_| project.path.clone());
+            (name, path)
+        })
+        .collect()
+}
+
+/// Renders a VS Code workspace with a folder per project.
+///
+/// Folder paths are relative to `base`, the canonical directory of the
+/// workspace file, for projects inside it and absolute otherwise. If `existing` holds the
+/// current content of the file, its folders and all other keys are kept
+/// and only folders for new projects are added; comments and trailing
+/// commas are accepted but not kept.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidWorkspace`] if `existing` is not a workspace file.
+///
+/// # Example
+///
+/// ```
+/// use git_projects_core::workspace::vscode_workspace;
+/// use git_projects_core::GitProject;
+/// use std::path::Path;
+///
+/// let projects = [GitProject::test("/src/tool")];
+/// let existing = r#"{
+///     // Keep the formatter
+///     "folders": [],
+///     "settings": { "editor.formatOnSave": true },
+/// }"#;
+/// let workspace = vscode_workspace(&projects, Some(Path::new("/src")), Some(existing))?;
+/// assert!(workspace.contains(r#""path": "tool""#));
+/// assert!(workspace.contains("formatOnSave"));
+/// # Ok::<(), git_projects_core::Error>(())
+/// ```
+pub fn vscode_workspace(
+    projects: &[GitProject],
+    base: Option<&Path>,
+    existing: Option<&str>,
+) -> Result<String> {
+    let mut workspace = match existing {
+        Some(source) => parse_jsonc(source)?,
+        None => json!({ "folders": [], "settings": {} }),
+    };
+    let root = workspace
+        .as_object_mut()
+        .ok_or_else(|| Error::invalid_workspace("expected an object"))?;
+    let folders = root
+        .entry("folders")
+        .or_insert_with(|| Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or_else(|| Error::invalid_workspace("`folders` is not an array"))?;
+
+    let listed: HashSet<String> = folders
+        .iter()
+        .filter_map(|folder| folder.get("path")?.as_str())
+        .map(|path| path.trim_end_matches('/').to_string())
+        .collect();
+    for (name, path) in named_projects(projects) {
+        let path = match base.and_then(|base| path.strip_prefix(base).ok()) {
+            Some(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+            Some(relative) => relative.display().to_string(),
+            None => path.display().to_string(),
+        };
+        if listed.contains(&path) {
+            continue;
+        }
+        let mut folder = Map::new();
+        folder.insert("name".to_string(), Value::String(name));
+        folder.insert("path".to_string(), Value::String(path));
+        folders.push(Value::Object(folder));
+    }
+
+    Ok(serde_json::to_string_pretty(&workspace)? + "\n")
+}
+
+/// Parses JSON with comments and trailing commas, as VS Code writes them
+fn parse_jsonc(source: &str) -> Result<Value> {
+    let mut json = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                json.push(c);
+                while let Some(c) = chars.next() {
+                    json.push(c);
+                    match c {
+                        '\\' => json.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = '\0';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+                json.push(' ');
+            }
+            ']' | '}' => {
+                // Drop a trailing comma before the closing bracket
+                let content_end = json.trim_end().len();
+                if json[..content_end].ends_with(',') {
+                    json.remove(content_end - 1);
+                }
+                json.push(c);
+            }
+            c => json.push(c),
+        }
+    }
+    serde_json::from_str(&json).map_err(|e| Error::invalid_workspace(e.to_string()))
+}
+
+/// Writes a JetBrains project into `dir`: `.idea/modules.xml` and
+/// `.idea/vcs.xml`, and a module file per project in `.idea/modules`.
+///
+/// Modules are named after [`folder_name`] with dots instead of slashes,
+/// which the IDE shows as groups by service and account. Existing module
+/// files are left alone. `modules.xml` and `vcs.xml` are edited in place:
+/// only modules and VCS mappings that are not listed yet are added, and
+/// everything else in them is kept as it is. Other files in `.idea` are not
+/// touched.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] if a file cannot be written, or
+/// [`Error::InvalidWorkspace`] if an existing `modules.xml` or `vcs.xml`
+/// cannot be parsed.
+pub fn write_jetbrains(projects: &[GitProject], dir: &Path) -> Result<()> {
+    let idea = dir.join(".idea");
+    std::fs::create_dir_all(idea.join("modules"))?;
+
+    let mut modules = Vec::new();
+    let mut mappings = Vec::new();
+    for (name, path) in named_projects(projects) {
+        let file_name = format!("{}.iml", name.replace('/', "."));
+        let module_path = idea.join("modules").join(&file_name);
+        if !module_path.exists() {
+            write_atomic(&module_path, &jetbrains_module(&path))?;
+        }
+        let file_path = format!("$PROJECT_DIR$/.idea/modules/{}", file_name);
+        let escaped = xml_escape(&file_path);
+        let module = format!(
+            "<module fileurl=\"file://{}\" filepath=\"{}\" />",
+            escaped, escaped
+        );
+        modules.push((file_path, module));
+
+        let directory = path.display().to_string();
+        let mapping = format!(
+            "<mapping directory=\"{}\" vcs=\"Git\" />",
+            xml_escape(&directory)
+        );
+        mappings.push((directory, mapping));
+    }
+
+    let modules_xml = Settings {
+        component: "ProjectModuleManager",
+        container: Some("modules"),
+        tag: "module",
+        key: "filepath",
+    };
+    modules_xml.add(&idea.join("modules.xml"), &modules)?;
+    let vcs_xml = Settings {
+        component: "VcsDirectoryMappings",
+        container: None,
+        tag: "mapping",
+        key: "directory",
+    };
+    vcs_xml.add(&idea.join("vcs.xml"), &mappings)
+}
+
+/// Renders a module whose content root is the project
+fn jetbrains_module(path: &Path) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <module type=\"GENERAL_MODULE\" version=\"4\">\n  \
+         <component name=\"NewModuleRootManager\" inherit-compiler-output=\"true\">\n    \
+         <exclude-output />\n    \
+         <content url=\"file://{}\" />\n    \
+         <orderEntry type=\"sourceFolder\" forTests=\"false\" />\n  \
+         </component>\n\
+         </module>\n",
+        xml_escape(&path.display().to_string())
+    )
+}
+
+/// Where the entries of this tool live in a JetBrains settings file
+struct Settings {
+    /// The name of the `<component>` of the `<project>`
+    component: &'static str,
+    /// The element in the component holding the entries, if any
+    container: Option<&'static str>,
+    /// The tag of the entries
+    tag: &'static str,
+    /// The attribute that identifies an entry
+    key: &'static str,
+}
+
+impl Settings {
+    /// Adds the entries, given as key and element, whose key is not listed in
+    /// the file at `path` yet
+    ///
+    /// The new elements are spliced into the source, so everything else in
+    /// the file, formatting included, stays as it is. Missing components and
+    /// containers are created; the file is only written if something was
+    /// added.
+    fn add(&self, path: &Path, entries: &[(String, String)]) -> Result<()> {
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<project version=\"4\">\n</project>\n"
+                    .to_string()
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let invalid = |message: &dyn std::fmt::Display| {
+            Error::invalid_workspace(format!("{}: {}", path.display(), message))
+        };
+        let document = roxmltree::Document::parse(&source).map_err(|e| invalid(&e))?;
+        let project = document.root_element();
+        if !project.has_tag_name("project") {
+            return Err(invalid(&"expected a <project> element"));
+        }
+
+        let listed: HashSet<&str> = document
+            .descendants()
+            .filter(|node| node.has_tag_name(self.tag))
+            .filter_map(|node| node.attribute(self.key))
+            .collect();
+        let mut added = HashSet::new();
+        let new: Vec<&str> = entries
+            .iter()
+            .filter(|(key, _)| !listed.contains(key.as_str()) && added.insert(key))
+            .map(|(_, element)| element.as_str())
+            .collect();
+        if new.is_empty() {
+            return Ok(());
+        }
+
+        // The element to add to, and the elements to create in it first
+        let component = project.children().find(|node| {
+            node.has_tag_name("component") && node.attribute("name") == Some(self.component)
+        });
+        let (parent, missing) = match (component, self.container) {
+            (None, container) => (
+                project,
+                [format!("component name=\"{}\"", self.component)]
+                    .into_iter()
+                    .chain(container.map(str::to_string))
+                    .collect(),
+            ),
+            (Some(component), Some(container)) => {
+                match component
+                    .children()
+                    .find(|node| node.has_tag_name(container))
+                {
+                    Some(node) => (node, Vec::new()),
+                    None => (component, vec![container.to_string()]),
+                }
+            }
+            (Some(component), None) => (component, Vec::new()),
+        };
+
+        let indent = indentation(&source, parent.range().start);
+        let mut fragment = String::new();
+        let mut depth = 1;
+        for open in &missing {
+            let _ = write!(fragment, "\n{}{}<{}>", indent, "  ".repeat(depth), open);
+            depth += 1;
+        }
+        for element in new {
+            let _ = write!(fragment, "\n{}{}{}", indent, "  ".repeat(depth), element);
+        }
+        for open in missing.iter().rev() {
+            depth -= 1;
+            let tag = open.split(' ').next().unwrap_or(open);
+            let _ = write!(fragment, "\n{}{}</{}>", indent, "  ".repeat(depth), tag);
+        }
+
+        let range = parent.range();
+        let element = &source[range.clone()];
+        let updated = match element.strip_suffix("/>") {
+            // `<modules />` gets content and a closing tag
+            Some(open) => format!(
+                "{}{}>{}\n{}</{}>{}",
+                &source[..range.start],
+                open.trim_end(),
+                fragment,
+                indent,
+                parent.tag_name().name(),
+                &source[range.end..]
+            ),
+            None => {
+                let close = range.start + element.rfind("</").unwrap_or(element.len());
+                let end = source[..close].trim_end().len();
+                format!(
+                    "{}{}\n{}{}",
+                    &source[..end],
+                    fragment,
+                    indent,
+                    &source[close..]
+                )
+            }
+        };
+        write_atomic(path, &updated)?;
+        Ok(())
+    }
+}
+
+/// Returns the whitespace before `offset` on its line
+fn indentation(source: &str, offset: usize) -> &str {
+    let line = &source[source[..offset].rfind('\n').map_or(0, |i| i + 1)..offset];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project(path: &str, account: Option<&str>) -> GitProject {
+        let project = GitProject::test(path);
+        match account {
+            Some(account) => {
+                let url = format!("https://github.com/{}/{}.git", account, project.name);
+                project.with_remote(&url)
+            }
+            None => project,
+        }
+    }
+
+    #[test]
+    fn test_vscode_workspace() {
+        let projects = [
+            project("/src/acme/tool", Some("acme")),
+            project("/src/fork/tool", Some("acme")),
+            project("/opt/scratch", None),
+        ];
+        let rendered = vscode_workspace(&projects, Some(Path::new("/src")), None).unwrap();
+        let workspace: Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(
+            workspace["folders"],
+            json!([
+                { "name": "github/acme/tool", "path": "acme/tool" },
+                { "name": "github/acme/tool (2)", "path": "fork/tool" },
+                { "name": "scratch", "path": "/opt/scratch" },
+            ])
+        );
+        assert_eq!(workspace["settings"], json!({}));
+    }
+
+    #[test]
+    fn test_vscode_workspace_keeps_user_settings() {
+        let existing = r#"{
+            /* Renamed by hand */
+            "folders": [{ "name": "Tool", "path": "acme/tool/" }, { "path": "docs" },],
+            "settings": { "files.exclude": { "**/target": true } }, // "// not a comment"
+            "extensions": { "recommendations": ["rust-lang.rust-analyzer"] }
+        }"#;
+        let projects = [
+            project("/src/acme/tool", Some("acme")),
+            project("/src/acme/lib", Some("acme")),
+        ];
+        let rendered =
+            vscode_workspace(&projects, Some(Path::new("/src")), Some(existing)).unwrap();
+        let workspace: Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(
+            workspace["folders"],
+            json!([
+                { "name": "Tool", "path": "acme/tool/" },
+                { "path": "docs" },
+                { "name": "github/acme/lib", "path": "acme/lib" },
+            ])
+        );
+        assert_eq!(workspace["settings"]["files.exclude"]["**/target"], true);
+        assert_eq!(
+            workspace["extensions"]["recommendations"][0],
+            "rust-lang.rust-analyzer"
+        );
+
+        assert!(vscode_workspace(&projects, None, Some("[]")).is_err());
+        assert!(vscode_workspace(&projects, None, Some("{\"folders\": {}}")).is_err());
+    }
+
+    #[test]
+    fn test_write_jetbrains() {
+        let dir = TempDir::new().unwrap();
+        let idea = dir.path().join(".idea");
+        let projects = [
+            project("/src/acme/tool", Some("acme")),
+            project("/src/scratch", None),
+        ];
+        write_jetbrains(&projects, dir.path()).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(idea.join("modules.xml")).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <project version=\"4\">\n  \
+             <component name=\"ProjectModuleManager\">\n    \
+             <modules>\n      \
+             <module fileurl=\"file://$PROJECT_DIR$/.idea/modules/github.acme.tool.iml\" \
+             filepath=\"$PROJECT_DIR$/.idea/modules/github.acme.tool.iml\" />\n      \
+             <module fileurl=\"file://$PROJECT_DIR$/.idea/modules/scratch.iml\" \
+             filepath=\"$PROJECT_DIR$/.idea/modules/scratch.iml\" />\n    \
+             </modules>\n  \
+             </component>\n\
+             </project>\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(idea.join("vcs.xml")).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <project version=\"4\">\n  \
+             <component name=\"VcsDirectoryMappings\">\n    \
+             <mapping directory=\"/src/acme/tool\" vcs=\"Git\" />\n    \
+             <mapping directory=\"/src/scratch\" vcs=\"Git\" />\n  \
+             </component>\n\
+             </project>\n"
+        );
+        assert!(std::fs::read_to_string(idea.join("modules/scratch.iml"))
+            .unwrap()
+            .contains("<content url=\"file:///src/scratch\" />"));
+    }
+
+    #[test]
+    fn test_write_jetbrains_keeps_existing_settings() {
+        let dir = TempDir::new().unwrap();
+        let idea = dir.path().join(".idea");
+        std::fs::create_dir_all(idea.join("modules")).unwrap();
+        let modules = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <project version=\"4\">\n  \
+             <component name=\"ProjectModuleManager\">\n    \
+             <modules>\n      \
+             <module fileurl=\"file://$USER_HOME$/tool.iml\" \
+             filepath=\"$PROJECT_DIR$/.idea/modules/github.acme.tool.iml\" group=\"Mine\" />\n    \
+             </modules>\n  \
+             </component>\n\
+             </project>\n";
+        std::fs::write(idea.join("modules.xml"), modules).unwrap();
+        let vcs = "<project version=\"4\"><component name=\"Other\" />\
+             <component name=\"VcsDirectoryMappings\">\
+             <mapping directory=\"$PROJECT_DIR$\" vcs=\"hg4idea\" /></component></project>";
+        std::fs::write(idea.join("vcs.xml"), vcs).unwrap();
+        std::fs::write(idea.join("modules/github.acme.tool.iml"), "custom").unwrap();
+
+        let projects = [
+            project("/src/acme/tool", Some("acme")),
+            project("/src/scratch", None),
+        ];
+        write_jetbrains(&projects, dir.path()).unwrap();
+        let written = |name: &str| std::fs::read_to_string(idea.join(name)).unwrap();
+        let (modules_after, vcs_after) = (written("modules.xml"), written("vcs.xml"));
+        // Running it again changes nothing
+        write_jetbrains(&projects, dir.path()).unwrap();
+        assert_eq!(written("modules.xml"), modules_after);
+        assert_eq!(written("vcs.xml"), vcs_after);
+
+        // The existing module is kept as it is, the new one is added after it
+        let (before, after) = modules.split_once("    </modules>").unwrap();
+        assert_eq!(
+            modules_after,
+            format!(
+                "{}      <module fileurl=\"file://$PROJECT_DIR$/.idea/modules/scratch.iml\" \
+                 filepath=\"$PROJECT_DIR$/.idea/modules/scratch.iml\" />\n    </modules>{}",
+                before, after
+            )
+        );
+        assert_eq!(
+            vcs_after,
+            "<project version=\"4\"><component name=\"Other\" />\
+             <component name=\"VcsDirectoryMappings\">\
+             <mapping directory=\"$PROJECT_DIR$\" vcs=\"hg4idea\" />\n  \
+             <mapping directory=\"/src/acme/tool\" vcs=\"Git\" />\n  \
+             <mapping directory=\"/src/scratch\" vcs=\"Git\" />\n\
+             </component></project>"
+        );
+        assert_eq!(written("modules/github.acme.tool.iml"), "custom");
+
+        // A component that is only declared gets its container
+        std::fs::write(
+            idea.join("modules.xml"),
+            "<project version=\"4\">\n  <component name=\"ProjectModuleManager\" />\n</project>\n",
+        )
+        .unwrap();
+        write_jetbrains(&projects[1..], dir.path()).unwrap();
+        assert_eq!(
+            written("modules.xml"),
+            "<project version=\"4\">\n  \
+             <component name=\"ProjectModuleManager\">\n    \
+             <modules>\n      \
+             <module fileurl=\"file://$PROJECT_DIR$/.idea/modules/scratch.iml\" \
+             filepath=\"$PROJECT_DIR$/.idea/modules/scratch.iml\" />\n    \
+             </modules>\n  \
+             </component>\n\
+             </project>\n"
+        );
+
+        std::fs::write(idea.join("vcs.xml"), "<settings />").unwrap();
+        assert!(write_jetbrains(&projects, dir.path()).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! Integration tests of the `serve` daemon over its Unix socket and HTTP.
+
+#![cfg(unix)]
+
+use git_projects_core::server::{
+    ChangedParams, Client, QueryParams, Server, ShowParams, INVALID_PARAMS, NOT_FOUND,
+};
+use git_projects_core::{ChangeKind, DefaultScanner, Error, ProjectDetails, ProjectList};
+use git_projects_core::{ProjectScanner, ScanConfig};
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::os::unix::net::UnixListener;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Starts a server scanning `root` on a socket in `dir`
+fn start(root: &Path, dir: &Path) -> (Server, PathBuf) {
+    let config = ScanConfig {
+        root_paths: vec![root.to_path_buf()],
+        ..ScanConfig::default()
+    };
+    let server = Server::new(move || DefaultScanner::new().scan(&config));
+    server.rescan().unwrap();
+
+    let socket = dir.join("daemon.sock");
+    let listener = UnixListener::bind(&socket).unwrap();
+    let serving = server.clone();
+    std::thread::spawn(move || serving.serve_unix(listener));
+    (server, socket)
+}
+
+fn init(path: &Path) {
+    gix::init(path).unwrap();
+}
+
+/// Sends a raw HTTP request and returns the status and body
+fn http(port: u16, request: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    let body = response.split_once("\r\n\r\n").unwrap().1.to_string();
+    (status, body)
+}
+
+#[test]
+fn test_query_and_show_over_socket() {
+    let root = TempDir::new().unwrap();
+    let dir = TempDir::new().unwrap();
+    init(&root.path().join("alpha"));
+    init(&root.path().join("beta"));
+    let (_server, socket) = start(root.path(), dir.path());
+
+    let mut client = Client::connect(&socket).unwrap();
+    let list: ProjectList = client.call("query", ()).unwrap();
+    assert_eq!(list.projects.len(), 2);
+
+    let params = QueryParams {
+        filter: Some(r#"name == "beta""#.to_string()),
+        ..QueryParams::default()
+    };
+    let list: ProjectList = client.call("query", &params).unwrap();
+    assert_eq!(list.projects.len(), 1);
+    assert_eq!(list.projects[0].name, "beta");
+
+    let params = QueryParams {
+        sort: Some("name".to_string()),
+        order: Some("desc".to_string()),
+        ..QueryParams::default()
+    };
+    let list: ProjectList = client.call("query", &params).unwrap();
+    let names: Vec<&str> = list.projects.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["beta", "alpha"]);
+
+    let details: ProjectDetails = client
+        .call(
+            "show",
+            &ShowParams {
+                project: "alpha".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(details.project.name, "alpha");
+
+    let error = client
+        .call::<ProjectDetails>(
+            "show",
+            &ShowParams {
+                project: "gamma".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::Rpc {
+            code: NOT_FOUND,
+            ..
+        }
+    ));
+
+    let params = QueryParams {
+        filter: Some("name ==".to_string()),
+        ..QueryParams::default()
+    };
+    let error = client.call::<ProjectList>("query", &params).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::Rpc {
+            code: INVALID_PARAMS,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_subscribe_receives_changes() {
+    let root = TempDir::new().unwrap();
+    let dir = TempDir::new().unwrap();
+    init(&root.path().join("alpha"));
+    let (server, socket) = start(root.path(), dir.path());
+
+    let mut subscriber = Client::connect(&socket).unwrap();
+    assert!(subscriber.call::<bool>("subscribe", ()).unwrap());
+
+    // A scan without changes sends nothing; a scan by another client does
+    let mut client = Client::connect(&socket).unwrap();
+    let list: ProjectList = client.call("scan", ()).unwrap();
+    assert_eq!(list.projects.len(), 1);
+    init(&root.path().join("beta"));
+    let list: ProjectList = client.call("scan", ()).unwrap();
+    assert_eq!(list.projects.len(), 2);
+
+    let notification = subscriber.next_notification().unwrap();
+    assert_eq!(notification.method, "changed");
+    let params: ChangedParams = serde_json::from_value(notification.params).unwrap();
+    assert_eq!(params.projects, 2);
+    assert_eq!(params.changes.len(), 1);
+    assert!(params.changes[0].path.ends_with("beta"));
+    assert_eq!(params.changes[0].kind, ChangeKind::Added);
+
+    std::fs::remove_dir_all(root.path().join("alpha")).unwrap();
+    assert_eq!(server.rescan().unwrap().len(), 1);
+    let notification = subscriber.next_notification().unwrap();
+    let params: ChangedParams = serde_json::from_value(notification.params).unwrap();
+    assert_eq!(params.changes[0].kind, ChangeKind::Removed);
+}
+
+#[test]
+fn test_http_api() {
+    let root = TempDir::new().unwrap();
+    let dir = TempDir::new().unwrap();
+    init(&root.path().join("alpha"));
+    init(&root.path().join("beta"));
+    let (server, _socket) = start(root.path(), dir.path());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || server.serve_http(listener));
+
+    let (status, body) = http(
+        port,
+        "GET /projects?filter=name+%3D%3D+%22alpha%22 HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    );
+    assert_eq!(status, 200);
+    let list: ProjectList = serde_json::from_str(&body).unwrap();
+    assert_eq!(list.projects.len(), 1);
+    assert_eq!(list.projects[0].name, "alpha");
+
+    let (status, body) = http(
+        port,
+        "GET /show?project=beta HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n",
+    );
+    assert_eq!(status, 200);
+    let details: ProjectDetails = serde_json::from_str(&body).unwrap();
+    assert_eq!(details.project.name, "beta");
+
+    let (status, _) = http(
+        port,
+        "GET /show?project=gamma HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    );
+    assert_eq!(status, 404);
+
+    let rpc = r#"{"jsonrpc": "2.0", "id": 7, "method": "scan"}"#;
+    let (status, body) = http(
+        port,
+        &format!(
+            "POST /rpc HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            rpc.len(),
+            rpc
+        ),
+    );
+    assert_eq!(status, 200);
+    let response: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(response["id"], 7);
+    assert_eq!(response["result"]["projects"].as_array().unwrap().len(), 2);
+
+    // Pages that resolve their own domain to 127.0.0.1 are turned away
+    let (status, _) = http(
+        port,
+        "GET /projects HTTP/1.1\r\nHost: attacker.example\r\n\r\n",
+    );
+    assert_eq!(status, 403);
+
+    let (status, _) = http(port, "DELETE /projects HTTP/1.1\r\nHost: localhost\r\n\r\n");
+    assert_eq!(status, 405);
+}
@@ -0,0 +1,247 @@
+#ifndef GIT_PROJECTS_H
+#define GIT_PROJECTS_H
+
+/* Generated by cbindgen from src/lib.rs; do not edit. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// The result of a function.
+typedef enum GpStatus {
+  // Success.
+  GP_STATUS_OK = 0,
+  // A pointer was `NULL` or a string was not valid UTF-8.
+  GP_STATUS_INVALID_ARGUMENT = 1,
+  // A root path does not exist.
+  GP_STATUS_PATH_NOT_FOUND = 2,
+  // A root path is not a directory.
+  GP_STATUS_NOT_A_DIRECTORY = 3,
+  // An ignore pattern is not a valid glob.
+  GP_STATUS_INVALID_PATTERN = 4,
+  // Reading from the filesystem failed.
+  GP_STATUS_IO = 5,
+  // A Git repository could not be read.
+  GP_STATUS_GIT = 6,
+  // The results could not be serialized.
+  GP_STATUS_JSON = 7,
+  // Any other error.
+  GP_STATUS_OTHER = 8,
+  // The library panicked; this is a bug.
+  GP_STATUS_PANIC = 9,
+} GpStatus;
+
+// The projects found by [`gp_scan`].
+typedef struct GpProjectList GpProjectList;
+
+// Settings of a scan: roots, depth, symlinks, submodules and ignore patterns.
+typedef struct GpScanConfig GpScanConfig;
+
+// A scanner, with an optional progress callback.
+typedef struct GpScanner GpScanner;
+
+// Called with the path of each repository found and the number found so
+// far, on the thread that called [`gp_scan`] or [`gp_scan_json`].
+typedef void (*GpProgressCallback)(const char *path, size_t found, void *user_data);
+
+// A remote of a project.
+typedef struct GpRemote {
+  // The name, e.g. `origin`.
+  const char *name;
+  // The fetch URL.
+  const char *url;
+  // The service, e.g. `github`, or `NULL` if unknown.
+  const char *service;
+  // The account or organization, or `NULL` if unknown.
+  const char *account;
+} GpRemote;
+
+// A project found by a scan.
+//
+// Strings are UTF-8 and owned by the [`GpProjectList`]; optional ones are
+// `NULL` when missing.
+typedef struct GpProject {
+  // The name of the directory.
+  const char *name;
+  // The absolute path of the working tree.
+  const char *path;
+  // The checked out branch, or `NULL` if `HEAD` is detached.
+  const char *branch;
+  // The commit of `HEAD`, or `NULL` if there is none yet.
+  const char *commit;
+  // The configured `user.name`, or `NULL`.
+  const char *user_name;
+  // The configured `user.email`, or `NULL`.
+  const char *user_email;
+  // The remotes, `remote_count` of them.
+  const struct GpRemote *remotes;
+  // The number of remotes.
+  size_t remote_count;
+  // Whether the project is a submodule of another.
+  bool is_submodule;
+  // Whether the project has submodules.
+  bool has_submodules;
+} GpProject;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns the version of the library, e.g. `"0.1.0"`.
+//
+// The string is static and must not be freed.
+const char *gp_version(void);
+
+// Returns a description of the last error on this thread, or `NULL`.
+//
+// The string stays valid until the next failing call on this thread and
+// must not be freed.
+const char *gp_last_error_message(void);
+
+// Creates a scan configuration without roots, three levels deep, not
+// following symlinks and including submodules.
+//
+// Release it with [`gp_scan_config_free`].
+struct GpScanConfig *gp_scan_config_new(void);
+
+// Releases a scan configuration; `NULL` is ignored.
+//
+// # Safety
+//
+// `config` must be `NULL` or returned by [`gp_scan_config_new`], and not
+// be used afterwards.
+void gp_scan_config_free(struct GpScanConfig *config);
+
+// Adds a directory to scan.
+//
+// # Safety
+//
+// `config` must be a valid configuration and `path` a NUL-terminated
+// string.
+enum GpStatus gp_scan_config_add_root(struct GpScanConfig *config, const char *path);
+
+// Adds a glob pattern of directories to skip, e.g. `**/node_modules`.
+//
+// # Safety
+//
+// `config` must be a valid configuration and `pattern` a NUL-terminated
+// string.
+enum GpStatus gp_scan_config_add_ignore_pattern(struct GpScanConfig *config, const char *pattern);
+
+// Sets how many levels below a root to scan; a negative depth is unlimited.
+//
+// # Safety
+//
+// `config` must be a valid configuration.
+enum GpStatus gp_scan_config_set_max_depth(struct GpScanConfig *config, int32_t depth);
+
+// Sets whether to follow symbolic links.
+//
+// # Safety
+//
+// `config` must be a valid configuration.
+enum GpStatus gp_scan_config_set_follow_symlinks(struct GpScanConfig *config, bool follow);
+
+// Sets whether to report submodules as projects of their own.
+//
+// # Safety
+//
+// `config` must be a valid configuration.
+enum GpStatus gp_scan_config_set_include_submodules(struct GpScanConfig *config, bool include);
+
+// Creates a scanner without progress callback.
+//
+// Release it with [`gp_scanner_free`].
+struct GpScanner *gp_scanner_new(void);
+
+// Releases a scanner; `NULL` is ignored.
+//
+// # Safety
+//
+// `scanner` must be `NULL` or returned by [`gp_scanner_new`], and not be
+// used afterwards.
+void gp_scanner_free(struct GpScanner *scanner);
+
+// Sets whether to log to stderr while scanning, for debugging.
+//
+// # Safety
+//
+// `scanner` must be a valid scanner.
+enum GpStatus gp_scanner_set_verbose(struct GpScanner *scanner, bool verbose);
+
+// Sets the progress callback, or removes it if `callback` is `NULL`.
+//
+// `user_data` is passed to every call of `callback` as is.
+//
+// # Safety
+//
+// `scanner` must be a valid scanner, and `callback` safe to call with
+// `user_data` for as long as it is set.
+enum GpStatus gp_scanner_set_progress(struct GpScanner *scanner,
+                                      GpProgressCallback callback,
+                                      void *user_data);
+
+// Scans and returns the projects as a JSON document, in the format of
+// `projects-cli --json`.
+//
+// On success, `*json_out` is set to a string to release with
+// [`gp_string_free`]; on failure, it is set to `NULL`.
+//
+// # Safety
+//
+// `scanner` and `config` must be valid handles and `json_out` a valid
+// pointer.
+enum GpStatus gp_scan_json(const struct GpScanner *scanner,
+                           const struct GpScanConfig *config,
+                           char **json_out);
+
+// Releases a string returned by the library; `NULL` is ignored.
+//
+// # Safety
+//
+// `s` must be `NULL` or returned by [`gp_scan_json`], and not be used
+// afterwards.
+void gp_string_free(char *s);
+
+// Scans and returns the projects as a list of [`GpProject`]s.
+//
+// On success, `*list_out` is set to a list to release with
+// [`gp_project_list_free`]; on failure, it is set to `NULL`.
+//
+// # Safety
+//
+// `scanner` and `config` must be valid handles and `list_out` a valid
+// pointer.
+enum GpStatus gp_scan(const struct GpScanner *scanner,
+                      const struct GpScanConfig *config,
+                      struct GpProjectList **list_out);
+
+// Returns the number of projects in a list; 0 for `NULL`.
+//
+// # Safety
+//
+// `list` must be `NULL` or a valid list.
+size_t gp_project_list_len(const struct GpProjectList *list);
+
+// Returns the project at `index`, or `NULL` if it is out of range.
+//
+// The project belongs to the list and is valid until the list is freed.
+//
+// # Safety
+//
+// `list` must be `NULL` or a valid list.
+const struct GpProject *gp_project_list_get(const struct GpProjectList *list, size_t index);
+
+// Releases a list and its projects; `NULL` is ignored.
+//
+// # Safety
+//
+// `list` must be `NULL` or returned by [`gp_scan`], and neither it nor its
+// projects be used afterwards.
+void gp_project_list_free(struct GpProjectList *list);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* GIT_PROJECTS_H */
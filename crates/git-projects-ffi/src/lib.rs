@@ -0,0 +1,614 @@
+//! C bindings for git-projects-core.
+//!
+//! This crate builds `libgit_projects` as a shared and a static library for
+//! frontends that cannot link Rust directly, such as the planned GTK and
+//! Windows apps. The declarations are in `include/git_projects.h`, generated
+//! with cbindgen; a test keeps it in sync with this file.
+//!
+//! A scan takes two opaque handles, a [`GpScanner`] and a [`GpScanConfig`],
+//! and returns the projects either as JSON ([`gp_scan_json`], the same
+//! document as `projects-cli --json`) or as C structs ([`gp_scan`]):
+//!
+//! ```c
+//! GpScanConfig *config = gp_scan_config_new();
+//! gp_scan_config_add_root(config, "/home/me/src");
+//! GpScanner *scanner = gp_scanner_new();
+//!
+//! GpProjectList *list = NULL;
+//! if (gp_scan(scanner, config, &list) != GP_STATUS_OK) {
+//!     fprintf(stderr, "%s\n", gp_last_error_message());
+//! } else {
+//!     for (size_t i = 0; i < gp_project_list_len(list); i++) {
+//!         printf("%s\n", gp_project_list_get(list, i)->path);
+//!     }
+//!     gp_project_list_free(list);
+//! }
+//! gp_scanner_free(scanner);
+//! gp_scan_config_free(config);
+//! ```
+//!
+//! Functions return a [`GpStatus`]; on failure, [`gp_last_error_message`]
+//! describes the error. Every object returned by a `_new` or scan function
+//! is owned by the caller and released with the matching `_free` function.
+
+use git_projects_core::ScanConfig;
+use git_projects_core::{DefaultScanner, Error, GitProject, ProjectList, ProjectScanner};
+use std::cell::RefCell;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The result of a function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpStatus {
+    /// Success.
+    Ok = 0,
+    /// A pointer was `NULL` or a string was not valid UTF-8.
+    InvalidArgument = 1,
+    /// A root path does not exist.
+    PathNotFound = 2,
+    /// A root path is not a directory.
+    NotADirectory = 3,
+    /// An ignore pattern is not a valid glob.
+    InvalidPattern = 4,
+    /// Reading from the filesystem failed.
+    Io = 5,
+    /// A Git repository could not be read.
+    Git = 6,
+    /// The results could not be serialized.
+    Json = 7,
+    /// Any other error.
+    Other = 8,
+    /// The library panicked; this is a bug.
+    Panic = 9,
+}
+
+impl From<&Error> for GpStatus {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::PathNotFound(_) => GpStatus::PathNotFound,
+            Error::NotADirectory(_) => GpStatus::NotADirectory,
+            Error::InvalidPattern(_) => GpStatus::InvalidPattern,
+            Error::Io(_) => GpStatus::Io,
+            Error::GitOpen { .. }
+            | Error::GitDiscover { .. }
+            | Error::GitConfig { .. }
+            | Error::GitRemote { .. }
+            | Error::GitHead { .. }
+            | Error::GitStatus { .. }
+            | Error::GitDetails { .. } => GpStatus::Git,
+            Error::Json(_) => GpStatus::Json,
+            _ => GpStatus::Other,
+        }
+    }
+}
+
+/// Called with the path of each repository found and the number found so
+/// far, on the thread that called [`gp_scan`] or [`gp_scan_json`].
+pub type GpProgressCallback =
+    Option<unsafe extern "C" fn(path: *const c_char, found: usize, user_data: *mut c_void)>;
+
+/// Settings of a scan: roots, depth, symlinks, submodules and ignore patterns.
+pub struct GpScanConfig {
+    config: ScanConfig,
+}
+
+/// A scanner, with an optional progress callback.
+pub struct GpScanner {
+    verbose: bool,
+    progress: GpProgressCallback,
+    user_data: *mut c_void,
+}
+
+/// A remote of a project.
+#[repr(C)]
+#[derive(Debug)]
+pub struct GpRemote {
+    /// The name, e.g. `origin`.
+    pub name: *const c_char,
+    /// The fetch URL.
+    pub url: *const c_char,
+    /// The service, e.g. `github`, or `NULL` if unknown.
+    pub service: *const c_char,
+    /// The account or organization, or `NULL` if unknown.
+    pub account: *const c_char,
+}
+
+/// A project found by a scan.
+///
+/// Strings are UTF-8 and owned by the [`GpProjectList`]; optional ones are
+/// `NULL` when missing.
+#[repr(C)]
+#[derive(Debug)]
+pub struct GpProject {
+    /// The name of the directory.
+    pub name: *const c_char,
+    /// The absolute path of the working tree.
+    pub path: *const c_char,
+    /// The checked out branch, or `NULL` if `HEAD` is detached.
+    pub branch: *const c_char,
+    /// The commit of `HEAD`, or `NULL` if there is none yet.
+    pub commit: *const c_char,
+    /// The configured `user.name`, or `NULL`.
+    pub user_name: *const c_char,
+    /// The configured `user.email`, or `NULL`.
+    pub user_email: *const c_char,
+    /// The remotes, `remote_count` of them.
+    pub remotes: *const GpRemote,
+    /// The number of remotes.
+    pub remote_count: usize,
+    /// Whether the project is a submodule of another.
+    pub is_submodule: bool,
+    /// Whether the project has submodules.
+    pub has_submodules: bool,
+}
+
+/// The projects found by [`gp_scan`].
+pub struct GpProjectList {
+    projects: Vec<GpProject>,
+    // Storage the pointers of `projects` point into
+    _remotes: Vec<Vec<GpRemote>>,
+    _strings: Vec<CString>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(message: impl ToString) {
+    let message = CString::new(message.to_string().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn fail(status: GpStatus, message: impl ToString) -> GpStatus {
+    set_error(message);
+    status
+}
+
+/// Runs `f`, turning errors and panics into a status
+fn guard(f: impl FnOnce() -> Result<(), GpStatus>) -> GpStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => GpStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => fail(GpStatus::Panic, "git-projects panicked"),
+    }
+}
+
+/// Reads a UTF-8 string argument
+///
+/// # Safety
+///
+/// `s` must be `NULL` or a NUL-terminated string.
+unsafe fn str_arg<'a>(s: *const c_char, name: &str) -> Result<&'a str, GpStatus> {
+    if s.is_null() {
+        return Err(fail(GpStatus::InvalidArgument, format!("{} is NULL", name)));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|_| fail(GpStatus::InvalidArgument, format!("{} is not UTF-8", name)))
+}
+
+/// Returns the version of the library, e.g. `"0.1.0"`.
+///
+/// The string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn gp_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Returns a description of the last error on this thread, or `NULL`.
+///
+/// The string stays valid until the next failing call on this thread and
+/// must not be freed.
+#[no_mangle]
+pub extern "C" fn gp_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |m| m.as_ptr()))
+}
+
+/// Creates a scan configuration without roots, three levels deep, not
+/// following symlinks and including submodules.
+///
+/// Release it with [`gp_scan_config_free`].
+#[no_mangle]
+pub extern "C" fn gp_scan_config_new() -> *mut GpScanConfig {
+    let config = ScanConfig {
+        root_paths: Vec::new(),
+        ..ScanConfig::default()
+    };
+    Box::into_raw(Box::new(GpScanConfig { config }))
+}
+
+/// Releases a scan configuration; `NULL` is ignored.
+///
+/// # Safety
+///
+/// `config` must be `NULL` or returned by [`gp_scan_config_new`], and not
+/// be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scan_config_free(config: *mut GpScanConfig) {
+    if !config.is_null() {
+        drop(Box::from_raw(config));
+    }
+}
+
+/// Adds a directory to scan.
+///
+/// # Safety
+///
+/// `config` must be a valid configuration and `path` a NUL-terminated
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scan_config_add_root(
+    config: *mut GpScanConfig,
+    path: *const c_char,
+) -> GpStatus {
+    guard(|| {
+        let config = config_arg(config)?;
+        let path = str_arg(path, "path")?;
+        config.config.root_paths.push(PathBuf::from(path));
+        Ok(())
+    })
+}
+
+/// Adds a glob pattern of directories to skip, e.g. `**/node_modules`.
+///
+/// # Safety
+///
+/// `config` must be a valid configuration and `pattern` a NUL-terminated
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scan_config_add_ignore_pattern(
+    config: *mut GpScanConfig,
+    pattern: *const c_char,
+) -> GpStatus {
+    guard(|| {
+        let config = config_arg(config)?;
+        let pattern = str_arg(pattern, "pattern")?;
+        config.config.ignore_patterns.push(pattern.to_string());
+        Ok(())
+    })
+}
+
+/// Sets how many levels below a root to scan; a negative depth is unlimited.
+///
+/// # Safety
+///
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scan_config_set_max_depth(
+    config: *mut GpScanConfig,
+    depth: i32,
+) -> GpStatus {
+    guard(|| {
+        config_arg(config)?.config.max_depth = usize::try_from(depth).ok();
+        Ok(())
+    })
+}
+
+/// Sets whether to follow symbolic links.
+///
+/// # Safety
+///
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scan_config_set_follow_symlinks(
+    config: *mut GpScanConfig,
+    follow: bool,
+) -> GpStatus {
+    guard(|| {
+        config_arg(config)?.config.follow_symlinks = follow;
+        Ok(())
+    })
+}
+
+/// Sets whether to report submodules as projects of their own.
+///
+/// # Safety
+///
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scan_config_set_include_submodules(
+    config: *mut GpScanConfig,
+    include: bool,
+) -> GpStatus {
+    guard(|| {
+        config_arg(config)?.config.include_submodules = include;
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// `config` must be `NULL` or a valid configuration.
+unsafe fn config_arg<'a>(config: *mut GpScanConfig) -> Result<&'a mut GpScanConfig, GpStatus> {
+    config
+        .as_mut()
+        .ok_or_else(|| fail(GpStatus::InvalidArgument, "config is NULL"))
+}
+
+/// Creates a scanner without progress callback.
+///
+/// Release it with [`gp_scanner_free`].
+#[no_mangle]
+pub extern "C" fn gp_scanner_new() -> *mut GpScanner {
+    Box::into_raw(Box::new(GpScanner {
+        verbose: false,
+        progress: None,
+        user_data: ptr::null_mut(),
+    }))
+}
+
+/// Releases a scanner; `NULL` is ignored.
+///
+/// # Safety
+///
+/// `scanner` must be `NULL` or returned by [`gp_scanner_new`], and not be
+/// used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scanner_free(scanner: *mut GpScanner) {
+    if !scanner.is_null() {
+        drop(Box::from_raw(scanner));
+    }
+}
+
+/// Sets whether to log to stderr while scanning, for debugging.
+///
+/// # Safety
+///
+/// `scanner` must be a valid scanner.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scanner_set_verbose(
+    scanner: *mut GpScanner,
+    verbose: bool,
+) -> GpStatus {
+    guard(|| {
+        scanner_arg(scanner)?.verbose = verbose;
+        Ok(())
+    })
+}
+
+/// Sets the progress callback, or removes it if `callback` is `NULL`.
+///
+/// `user_data` is passed to every call of `callback` as is.
+///
+/// # Safety
+///
+/// `scanner` must be a valid scanner, and `callback` safe to call with
+/// `user_data` for as long as it is set.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scanner_set_progress(
+    scanner: *mut GpScanner,
+    callback: GpProgressCallback,
+    user_data: *mut c_void,
+) -> GpStatus {
+    guard(|| {
+        let scanner = scanner_arg(scanner)?;
+        scanner.progress = callback;
+        scanner.user_data = user_data;
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// `scanner` must be `NULL` or a valid scanner.
+unsafe fn scanner_arg<'a>(scanner: *mut GpScanner) -> Result<&'a mut GpScanner, GpStatus> {
+    scanner
+        .as_mut()
+        .ok_or_else(|| fail(GpStatus::InvalidArgument, "scanner is NULL"))
+}
+
+/// The progress callback with its user data, for the scanning thread
+struct Progress {
+    callback: unsafe extern "C" fn(*const c_char, usize, *mut c_void),
+    user_data: *mut c_void,
+    found: AtomicUsize,
+}
+
+impl Progress {
+    fn report(&self, path: &Path) {
+        let found = self.found.fetch_add(1, Ordering::Relaxed) + 1;
+        let path = CString::new(path.to_string_lossy().into_owned()).unwrap_or_default();
+        // SAFETY: the caller of gp_scanner_set_progress vouches for the pair
+        unsafe { (self.callback)(path.as_ptr(), found, self.user_data) };
+    }
+}
+
+// The scan runs on the calling thread, so the callback is never called
+// from another one.
+unsafe impl Send for Progress {}
+unsafe impl Sync for Progress {}
+
+/// Scans with the settings of the handles
+///
+/// # Safety
+///
+/// Both pointers must be `NULL` or valid handles.
+unsafe fn run_scan(
+    scanner: *const GpScanner,
+    config: *const GpScanConfig,
+) -> Result<Vec<GitProject>, GpStatus> {
+    let scanner = scanner
+        .as_ref()
+        .ok_or_else(|| fail(GpStatus::InvalidArgument, "scanner is NULL"))?;
+    let config = config
+        .as_ref()
+        .ok_or_else(|| fail(GpStatus::InvalidArgument, "config is NULL"))?;
+
+    let mut core = DefaultScanner::new().with_verbose(scanner.verbose);
+    if let Some(callback) = scanner.progress {
+        let progress = Progress {
+            callback,
+            user_data: scanner.user_data,
+            found: AtomicUsize::new(0),
+        };
+        core = core.with_progress(move |path| progress.report(path));
+    }
+    core.scan(&config.config)
+        .map_err(|e| fail(GpStatus::from(&e), e))
+}
+
+/// Scans and returns the projects as a JSON document, in the format of
+/// `projects-cli --json`.
+///
+/// On success, `*json_out` is set to a string to release with
+/// [`gp_string_free`]; on failure, it is set to `NULL`.
+///
+/// # Safety
+///
+/// `scanner` and `config` must be valid handles and `json_out` a valid
+/// pointer.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scan_json(
+    scanner: *const GpScanner,
+    config: *const GpScanConfig,
+    json_out: *mut *mut c_char,
+) -> GpStatus {
+    if json_out.is_null() {
+        return fail(GpStatus::InvalidArgument, "json_out is NULL");
+    }
+    *json_out = ptr::null_mut();
+    guard(|| {
+        let projects = run_scan(scanner, config)?;
+        let json = serde_json::to_string(&ProjectList::new(projects))
+            .map_err(|e| fail(GpStatus::Json, e))?;
+        let json = CString::new(json).map_err(|e| fail(GpStatus::Json, e))?;
+        *json_out = json.into_raw();
+        Ok(())
+    })
+}
+
+/// Releases a string returned by the library; `NULL` is ignored.
+///
+/// # Safety
+///
+/// `s` must be `NULL` or returned by [`gp_scan_json`], and not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn gp_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Scans and returns the projects as a list of [`GpProject`]s.
+///
+/// On success, `*list_out` is set to a list to release with
+/// [`gp_project_list_free`]; on failure, it is set to `NULL`.
+///
+/// # Safety
+///
+/// `scanner` and `config` must be valid handles and `list_out` a valid
+/// pointer.
+#[no_mangle]
+pub unsafe extern "C" fn gp_scan(
+    scanner: *const GpScanner,
+    config: *const GpScanConfig,
+    list_out: *mut *mut GpProjectList,
+) -> GpStatus {
+    if list_out.is_null() {
+        return fail(GpStatus::InvalidArgument, "list_out is NULL");
+    }
+    *list_out = ptr::null_mut();
+    guard(|| {
+        let projects = run_scan(scanner, config)?;
+        *list_out = Box::into_raw(Box::new(project_list(&projects)));
+        Ok(())
+    })
+}
+
+/// Converts projects to C structs, keeping the strings alive in the list
+fn project_list(projects: &[GitProject]) -> GpProjectList {
+    let mut strings = Vec::new();
+    let mut string = |s: &str| {
+        let s = CString::new(s.replace('\0', " ")).unwrap_or_default();
+        // The heap buffer does not move when the CString does
+        let ptr = s.as_ptr();
+        strings.push(s);
+        ptr
+    };
+    let mut optional = |s: Option<&String>| s.map_or(ptr::null(), |s| string(s));
+
+    let mut remotes = Vec::with_capacity(projects.len());
+    let mut list = Vec::with_capacity(projects.len());
+    for project in projects {
+        let project_remotes: Vec<GpRemote> = project
+            .remotes
+            .iter()
+            .map(|remote| GpRemote {
+                name: optional(Some(&remote.name)),
+                url: optional(Some(&remote.url)),
+                service: optional(remote.service.as_ref()),
+                account: optional(remote.account.as_ref()),
+            })
+            .collect();
+        let head = project.head.as_ref();
+        let config = project.config.as_ref();
+        list.push(GpProject {
+            name: optional(Some(&project.name)),
+            path: optional(Some(&project.path.to_string_lossy().into_owned())),
+            branch: optional(head.and_then(|h| h.branch.as_ref())),
+            commit: optional(head.and_then(|h| h.commit.as_ref())),
+            user_name: optional(config.and_then(|c| c.user_name.as_ref())),
+            user_email: optional(config.and_then(|c| c.user_email.as_ref())),
+            remotes: if project_remotes.is_empty() {
+                ptr::null()
+            } else {
+                project_remotes.as_ptr()
+            },
+            remote_count: project_remotes.len(),
+            is_submodule: project.is_submodule,
+            has_submodules: project.has_submodules,
+        });
+        remotes.push(project_remotes);
+    }
+
+    GpProjectList {
+        projects: list,
+        _remotes: remotes,
+        _strings: strings,
+    }
+}
+
+/// Returns the number of projects in a list; 0 for `NULL`.
+///
+/// # Safety
+///
+/// `list` must be `NULL` or a valid list.
+#[no_mangle]
+pub unsafe extern "C" fn gp_project_list_len(list: *const GpProjectList) -> usize {
+    list.as_ref().map_or(0, |list| list.projects.len())
+}
+
+/// Returns the project at `index`, or `NULL` if it is out of range.
+///
+/// The project belongs to the list and is valid until the list is freed.
+///
+/// # Safety
+///
+/// `list` must be `NULL` or a valid list.
+#[no_mangle]
+pub unsafe extern "C" fn gp_project_list_get(
+    list: *const GpProjectList,
+    index: usize,
+) -> *const GpProject {
+    list.as_ref()
+        .and_then(|list| list.projects.get(index))
+        .map_or(ptr::null(), |project| project)
+}
+
+/// Releases a list and its projects; `NULL` is ignored.
+///
+/// # Safety
+///
+/// `list` must be `NULL` or returned by [`gp_scan`], and neither it nor its
+/// projects be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn gp_project_list_free(list: *mut GpProjectList) {
+    if !list.is_null() {
+        drop(Box::from_raw(list));
+    }
+}
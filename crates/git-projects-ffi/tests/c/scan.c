@@ -0,0 +1,99 @@
+/* Scans a directory through the C API and checks the results.
+ *
+ * Usage: scan ROOT EXPECTED_PROJECTS
+ * The project named "alpha" must have a remote "origin" on GitHub. */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "git_projects.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                   \
+    do {                                                                   \
+        if (!(condition)) {                                                \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,         \
+                    __LINE__, #condition);                                 \
+            failures++;                                                    \
+        }                                                                  \
+    } while (0)
+
+static void count_progress(const char *path, size_t found, void *user_data) {
+    size_t *calls = user_data;
+    CHECK(path != NULL && path[0] != '\0');
+    CHECK(found == *calls + 1);
+    *calls = found;
+}
+
+int main(int argc, char **argv) {
+    if (argc != 3) {
+        fprintf(stderr, "usage: %s ROOT EXPECTED_PROJECTS\n", argv[0]);
+        return 2;
+    }
+    size_t expected = (size_t)strtoul(argv[2], NULL, 10);
+
+    CHECK(strlen(gp_version()) > 0);
+
+    GpScanConfig *config = gp_scan_config_new();
+    CHECK(gp_scan_config_add_root(config, argv[1]) == GP_STATUS_OK);
+    CHECK(gp_scan_config_set_max_depth(config, -1) == GP_STATUS_OK);
+    CHECK(gp_scan_config_add_ignore_pattern(config, "**/ignored") == GP_STATUS_OK);
+
+    GpScanner *scanner = gp_scanner_new();
+    size_t calls = 0;
+    CHECK(gp_scanner_set_progress(scanner, count_progress, &calls) == GP_STATUS_OK);
+
+    /* Results as structs */
+    GpProjectList *list = NULL;
+    CHECK(gp_scan(scanner, config, &list) == GP_STATUS_OK);
+    CHECK(gp_project_list_len(list) == expected);
+    CHECK(calls == expected);
+    CHECK(gp_project_list_get(list, expected) == NULL);
+
+    const GpProject *alpha = NULL;
+    for (size_t i = 0; i < gp_project_list_len(list); i++) {
+        const GpProject *project = gp_project_list_get(list, i);
+        CHECK(project->path[0] == '/');
+        if (strcmp(project->name, "alpha") == 0) {
+            alpha = project;
+        }
+    }
+    CHECK(alpha != NULL);
+    if (alpha != NULL) {
+        CHECK(alpha->remote_count == 1);
+        CHECK(strcmp(alpha->remotes[0].name, "origin") == 0);
+        CHECK(strcmp(alpha->remotes[0].service, "github") == 0);
+        CHECK(strcmp(alpha->remotes[0].account, "acme") == 0);
+        CHECK(!alpha->is_submodule);
+    }
+    gp_project_list_free(list);
+
+    /* Results as JSON */
+    CHECK(gp_scanner_set_progress(scanner, NULL, NULL) == GP_STATUS_OK);
+    char *json = NULL;
+    CHECK(gp_scan_json(scanner, config, &json) == GP_STATUS_OK);
+    CHECK(json != NULL && strstr(json, "\"schema_version\"") != NULL);
+    CHECK(json != NULL && strstr(json, "github.com/acme/alpha") != NULL);
+    gp_string_free(json);
+
+    /* Errors */
+    CHECK(gp_scan_config_add_ignore_pattern(config, "[") == GP_STATUS_OK);
+    list = (GpProjectList *)1;
+    CHECK(gp_scan(scanner, config, &list) == GP_STATUS_INVALID_PATTERN);
+    CHECK(list == NULL);
+    CHECK(gp_last_error_message() != NULL);
+    CHECK(gp_scan_config_add_root(NULL, "x") == GP_STATUS_INVALID_ARGUMENT);
+    CHECK(gp_scan_config_add_root(config, NULL) == GP_STATUS_INVALID_ARGUMENT);
+    CHECK(strstr(gp_last_error_message(), "path") != NULL);
+
+    gp_scanner_free(scanner);
+    gp_scan_config_free(config);
+
+    if (failures > 0) {
+        fprintf(stderr, "%d checks failed\n", failures);
+        return 1;
+    }
+    return 0;
+}
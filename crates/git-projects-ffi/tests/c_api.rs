@@ -0,0 +1,76 @@
+//! Builds the C test program in `tests/c` against the shared library and
+//! runs it on a few repositories.
+
+#![cfg(unix)]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+/// The directory with `libgit_projects`, next to the `deps` directory of
+/// this test
+fn library_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+fn init(path: &Path, remote: Option<&str>) {
+    let repo = gix::init(path).unwrap();
+    if let Some(url) = remote {
+        let config = repo.path().join("config");
+        let mut content = std::fs::read_to_string(&config).unwrap();
+        content.push_str(&format!("[remote \"origin\"]\n\turl = {}\n", url));
+        std::fs::write(config, content).unwrap();
+    }
+}
+
+#[test]
+fn test_c_program() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = TempDir::new().unwrap();
+    init(
+        &root.path().join("alpha"),
+        Some("https://github.com/acme/alpha.git"),
+    );
+    init(&root.path().join("work/beta"), None);
+    init(&root.path().join("ignored/gamma"), None);
+
+    let build = TempDir::new().unwrap();
+    let program = build.path().join("scan");
+    let libraries = library_dir();
+    let compiler = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(&compiler)
+        .arg(dir.join("tests/c/scan.c"))
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(dir.join("include"))
+        .arg("-L")
+        .arg(&libraries)
+        .arg(format!("-Wl,-rpath,{}", libraries.display()))
+        .arg("-lgit_projects")
+        .arg("-o")
+        .arg(&program)
+        .status();
+    let status = match status {
+        Ok(status) => status,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!("skipped: no C compiler ({})", compiler);
+            return;
+        }
+        Err(e) => panic!("failed to run {}: {}", compiler, e),
+    };
+    assert!(status.success(), "compiling tests/c/scan.c failed");
+
+    let output = Command::new(&program)
+        .arg(root.path())
+        .arg("2")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
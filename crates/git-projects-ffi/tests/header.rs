@@ -0,0 +1,28 @@
+//! Checks that the checked-in C header matches the sources.
+
+use std::path::Path;
+
+#[test]
+fn test_header_is_up_to_date() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(dir.join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(dir.join("src/lib.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
+    let path = dir.join("include/git_projects.h");
+    if std::env::var_os("UPDATE_HEADER").is_some() {
+        std::fs::write(&path, &generated).unwrap();
+    }
+    let current = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        current == generated,
+        "include/git_projects.h is stale; regenerate it with \
+         UPDATE_HEADER=1 cargo test -p git-projects-ffi --test header"
+    );
+}